@@ -116,22 +116,21 @@ impl ScalePage {
         for syllable in syllables.iter() {
             add_note(syllable, false);
         }
-        let new_bar = |index: usize| {
+        let new_bar = |index: usize, count: usize| {
             ProtoBar::new(
                 vec![
                     ProtoBarLayer::new("notes".to_owned(), vec![
-                        Slice::new(SliceBegin::Index(index), SliceEnd::Count(4), None),
+                        Slice::new(SliceBegin::Index(index), SliceEnd::Count(count), None),
                     ])
                 ],
             )
         };
+        // Pentatonic and other shorter scales don't fill up the last bar.
+        let bars = (0..entries.len())
+            .step_by(4)
+            .map(|index| new_bar(index, (entries.len() - index).min(4)))
+            .collect();
         let track = ProtoTrack::new("notes".to_owned(), TrackKind::Vocal, entries);
-        let bars = vec![
-            new_bar(0),
-            new_bar(4),
-            new_bar(8),
-            new_bar(12),
-        ];
         let section = ProtoSection::new("notes".to_owned(), SectionKind::Verse, bars);
         ProtoTab::new(
             ProtoTab::new_uuid().as_str(),
@@ -4,16 +4,18 @@ pub mod long_juan_feng;
 pub mod scarborough_fair;
 pub mod test;
 
-pub fn main() {
-    write_tab(&test::new_tab(), "../notation_viewer/assets/tabs/test.ron");
+pub fn main() -> anyhow::Result<()> {
+    write_tab(&test::new_tab(), "../notation_viewer/assets/tabs/test.ron")?;
     write_tab(
         &scarborough_fair::new_tab(),
         "../notation_viewer/assets/tabs/scarborough_fair.ron",
-    );
+    )?;
     write_tab(
         &long_juan_feng::new_tab(),
         "../notation_viewer/assets/tabs/zh-CN/long_juan_feng.ron",
-    );
+    )?;
+    write_json_schema("../../model/notation_proto/tab.schema.json")?;
     let result = parse_get_tab_file("src/test.rs");
     println!("{:?}", result);
+    Ok(())
 }
@@ -8,9 +8,10 @@ pub struct MidiMessage {
     pub duration: Units,
     pub delay: bool,
     pub midi: StructuredShortMessage,
+    pub velocity_factor: f32,
 }
 impl MidiMessage {
-    pub const DELAY_GAP: Units = Units(Units::_MIN_ACCURACY * 2.0);
+    pub const DELAY_GAP: Units = Units::new(2, 100_000);
     pub fn new(
         pass_mode: EntryPassMode,
         pos: BarPosition,
@@ -24,6 +25,7 @@ impl MidiMessage {
             duration,
             delay,
             midi,
+            velocity_factor: 1.0,
         }
     }
     pub fn of_entry(entry: &LaneEntry, delay: bool, midi: StructuredShortMessage) -> Self {
@@ -33,8 +35,16 @@ impl MidiMessage {
             duration: entry.tied_units(),
             delay,
             midi,
+            velocity_factor: 1.0,
         }
     }
+    pub fn with_velocity_factor(mut self, velocity_factor: f32) -> Self {
+        self.velocity_factor = velocity_factor;
+        self
+    }
+    pub fn calc_velocity(&self, velocity: u8) -> u8 {
+        (velocity as f32 * self.velocity_factor).round().clamp(0.0, 127.0) as u8
+    }
     pub fn bar_ordinal(&self) -> usize {
         self.pos.bar_ordinal
     }
@@ -48,6 +58,11 @@ impl MidiMessage {
     pub fn effect_units(&self) -> Units {
         self.effect_position().into()
     }
+    /// Start and end of the note in the tab, the same for its note on and note off.
+    pub fn note_range(&self) -> (Units, Units) {
+        let start = Units::from(self.pos);
+        (start, start + self.duration)
+    }
     pub fn to_midi(&self) -> [u8; 3] {
         [
             self.midi.status_byte(),
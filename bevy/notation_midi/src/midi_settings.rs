@@ -14,11 +14,23 @@ pub struct MidiSettings {
     pub guitar_velocity: u8,
     pub piano_mute: bool,
     pub piano_velocity: u8,
+    pub bass_mute: bool,
+    pub bass_velocity: u8,
+    pub ukulele_mute: bool,
+    pub ukulele_velocity: u8,
+    pub mandolin_mute: bool,
+    pub mandolin_velocity: u8,
+    pub drums_mute: bool,
+    pub drums_velocity: u8,
     pub use_internal_synth: bool,
     pub click_sound: u8,
     pub vocal_sound: u8,
     pub guitar_sound: u8,
     pub piano_sound: u8,
+    pub bass_sound: u8,
+    pub ukulele_sound: u8,
+    pub mandolin_sound: u8,
+    pub drums_kit: u8,
     pub seeking_track: TrackKind,
     pub seeking_init_channel: bool,
 }
@@ -36,11 +48,25 @@ impl Default for MidiSettings {
             guitar_velocity: 120,
             piano_mute: false,
             piano_velocity: 110,
+            bass_mute: false,
+            bass_velocity: 110,
+            ukulele_mute: false,
+            ukulele_velocity: 110,
+            mandolin_mute: false,
+            mandolin_velocity: 110,
+            drums_mute: false,
+            drums_velocity: 100,
             use_internal_synth: Self::default_use_internal_synth(),
             click_sound: GMSoundSet::Dulcimer as u8,
             vocal_sound: GMSoundSet::Cello as u8,
             guitar_sound: GMSoundSet::AcousticGuitarSteel as u8,
             piano_sound: GMSoundSet::AcousticGrandPiano as u8,
+            bass_sound: GMSoundSet::ElectricBassFinger as u8,
+            // no ukulele or mandolin in general midi, use the closest plucked ones
+            ukulele_sound: GMSoundSet::AcousticGuitarNylon as u8,
+            mandolin_sound: GMSoundSet::Banjo as u8,
+            // program on the percussion channel selects the kit, 0 is the standard one
+            drums_kit: 0,
             seeking_track: TrackKind::Guitar,
             seeking_init_channel: true,
         }
@@ -61,6 +87,10 @@ impl MidiSettings {
             TrackKind::Vocal => Some((self.vocal_sound, self.vocal_velocity)),
             TrackKind::Guitar => Some((self.guitar_sound, self.guitar_velocity)),
             TrackKind::Piano => Some((self.piano_sound, self.piano_velocity)),
+            TrackKind::Bass => Some((self.bass_sound, self.bass_velocity)),
+            TrackKind::Ukulele => Some((self.ukulele_sound, self.ukulele_velocity)),
+            TrackKind::Mandolin => Some((self.mandolin_sound, self.mandolin_velocity)),
+            TrackKind::Drums => Some((self.drums_kit, self.drums_velocity)),
             _ => None,
         }
     }
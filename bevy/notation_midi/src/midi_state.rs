@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::sync::Arc;
 use bevy::prelude::*;
 
 use helgoboss_midi::{controller_numbers, Channel, StructuredShortMessage, U7};
 use notation_model::prelude::*;
+use notation_model::util::is_note_end_shadowed;
 use crate::prelude::*;
 
 use crate::midi_hub::MidiHub;
@@ -11,6 +13,8 @@ use crate::prelude::{MidiMessage, MidiSettings, MidiUtil};
 
 pub const DEFAULT_PROGRAM: u8 = 0;
 pub const DEFAULT_VELOCITY: u8 = 64;
+/// General MIDI reserves channel 10 (index 9) for percussion.
+pub const PERCUSSION_CHANNEL: usize = 9;
 
 #[derive(Debug)]
 pub struct MidiChannel {
@@ -59,6 +63,30 @@ impl MidiChannel {
             false
         }
     }
+    /// Voices of a track share the channel, when they sound the same key at the
+    /// same time, a note off is dropped if another note of the key is still
+    /// sounding at its end, so one voice doesn't cut the other.
+    ///
+    /// Only overlapping notes are matched, see [`MidiMessage::note_range()`] and
+    /// [`is_note_end_shadowed()`].
+    pub fn drop_shadowed_note_offs(&mut self) {
+        self.ensure_sorted();
+        let mut notes: HashMap<u8, Vec<(Units, Units)>> = HashMap::new();
+        for msg in self.messages.iter() {
+            if let StructuredShortMessage::NoteOn { key_number, .. } = msg.midi {
+                notes.entry(key_number.into()).or_default().push(msg.note_range());
+            }
+        }
+        self.messages.retain(|msg| match msg.midi {
+            StructuredShortMessage::NoteOff { key_number, .. } => {
+                let (_, end) = msg.note_range();
+                !notes
+                    .get(&key_number.into())
+                    .is_some_and(|x| is_note_end_shadowed(x, end))
+            }
+            _ => true,
+        });
+    }
     pub fn calc_next_index(&mut self, position: &BarPosition) {
         let pos_units = Units::from(*position);
         for (index, value) in self.messages.iter().enumerate() {
@@ -128,6 +156,34 @@ impl MidiChannel {
                                 settings.piano_velocity
                             };
                         }
+                        TrackKind::Bass => {
+                            velocity = if !seeking && settings.bass_mute {
+                                0
+                            } else {
+                                settings.bass_velocity
+                            };
+                        }
+                        TrackKind::Ukulele => {
+                            velocity = if !seeking && settings.ukulele_mute {
+                                0
+                            } else {
+                                settings.ukulele_velocity
+                            };
+                        }
+                        TrackKind::Mandolin => {
+                            velocity = if !seeking && settings.mandolin_mute {
+                                0
+                            } else {
+                                settings.mandolin_velocity
+                            };
+                        }
+                        TrackKind::Drums => {
+                            velocity = if !seeking && settings.drums_mute {
+                                0
+                            } else {
+                                settings.drums_velocity
+                            };
+                        }
                         _ => (),
                     }
                 }
@@ -152,7 +208,7 @@ impl MidiChannel {
                     count += 1;
                     if !bypass {
                         if !is_seeking || next.should_send_in_seeking() {
-                            hub.send(settings, speed, next, velocity);
+                            hub.send(settings, speed, next, next.calc_velocity(velocity));
                         }
                     }
                 } else {
@@ -306,7 +362,7 @@ impl MidiState {
             let beat_duration = Units::from(signature.beat_unit);
             for bar in tab.bars.iter() {
                 for beat in 0..signature.bar_beats {
-                    let in_bar_pos = Units(beat as f32 * Units::from(signature.beat_unit).0);
+                    let in_bar_pos = Units::from(signature.beat_unit) * beat as i64;
                     let root = bar
                         .get_chord(Some(in_bar_pos))
                         .map(|x| x.root)
@@ -348,12 +404,29 @@ impl MidiState {
         self.reset_channels();
         let mut index: usize = 0;
         self.create_click_channel(settings, hub, &tab, &mut index);
+        let mut percussion_used = false;
         for track in tab.tracks.iter() {
+            if index == PERCUSSION_CHANNEL {
+                index += 1;
+            }
             if index >= self.channels.len() {
                 return;
             }
             if let Some(params) = settings.get_track_channel_params(&track.kind) {
-                if let Some(channel) = self.channels.get_mut(index) {
+                if track.kind == TrackKind::Drums {
+                    if percussion_used {
+                        println!("switch_tab(), percussion channel already used, skip: {}", track);
+                        continue;
+                    }
+                    percussion_used = true;
+                    if let Some(channel) = self.channels.get_mut(PERCUSSION_CHANNEL) {
+                        channel.setup(settings, hub, params, track);
+                        println!(
+                            "switch_tab(), setup percussion channel: [{}] -> {}, {} - {}",
+                            PERCUSSION_CHANNEL, params.0, params.1, track
+                        );
+                    }
+                } else if let Some(channel) = self.channels.get_mut(index) {
                     channel.setup(settings, hub, params, track);
                     println!(
                         "switch_tab(), setup channel: [{}] -> {}, {} - {}",
@@ -363,17 +436,36 @@ impl MidiState {
                 }
             }
         }
+        let mut multi_voice_tracks: Vec<Arc<Track>> = Vec::new();
         for bar in tab.bars.iter() {
             for ((_k, _i), lane) in bar.lanes.iter() {
                 if let Some(channel) = self.get_channel_mut(&lane.track.id, &lane.track.kind) {
                     for entry in lane.entries.iter() {
-                        if let Some(msgs) = MidiUtil::get_midi_msgs(channel, bar, &entry) {
-                            for msg in msgs {
-                                channel.add_message(MidiMessage::of_entry(entry, msg.0, msg.1));
+                        if let Some(drum_entry) = entry.proto().as_drum() {
+                            for msg in MidiUtil::get_drum_midi_msgs(channel, entry, drum_entry) {
+                                channel.add_message(msg);
+                            }
+                        } else if let Some(msgs) = MidiUtil::get_midi_msgs(channel, bar, entry) {
+                            if let Some(ornament) = entry.get_ornament() {
+                                for msg in MidiUtil::get_ornament_midi_msgs(entry, &ornament, &msgs) {
+                                    channel.add_message(msg);
+                                }
+                            } else {
+                                for msg in msgs {
+                                    channel.add_message(MidiMessage::of_entry(entry, msg.0, msg.1));
+                                }
                             }
                         }
                     }
                 }
+                if lane.is_multi_voice() && !multi_voice_tracks.iter().any(|x| Arc::ptr_eq(x, &lane.track)) {
+                    multi_voice_tracks.push(lane.track.clone());
+                }
+            }
+        }
+        for track in multi_voice_tracks.iter() {
+            if let Some(channel) = self.get_channel_mut(&track.id, &track.kind) {
+                channel.drop_shadowed_note_offs();
             }
         }
         self.play_control = PlayControl::new(&tab);
@@ -387,7 +479,7 @@ impl MidiState {
     ) {
         self.play_control
             .position
-            .set_in_bar(bar_props.bar_ordinal, Units(0.0));
+            .set_in_bar(bar_props.bar_ordinal, Units::ZERO);
         if self.play_control.is_bar_in_range(bar_props.bar_ordinal) {
             for channel in self.channels.iter_mut() {
                 channel.calc_next_index(&self.play_control.position.bar);
@@ -470,44 +562,51 @@ impl MidiState {
                         Some(x.props.clone())
                     }
                 }) {
-                    self.setup_seek(
-                        pos.with_in_bar_pos(props.in_bar_pos - Units::HALF_MIN_ACCURACY),
-                    );
+                    self.setup_seek(pos.with_in_bar_pos(props.in_bar_pos));
                     return true;
                 } else {
-                    self.setup_seek(BarPosition::new(
-                        pos.bar_units,
-                        pos.bar_ordinal,
-                        pos.bar_units - Units::HALF_MIN_ACCURACY,
-                    ));
+                    self.setup_seek(pos.with_in_bar_pos(Self::calc_bar_end(&pos)));
                     return true;
                 }
             }
         }
         false
     }
+    /// Last position on the grid of the bar, no entry can start after it.
+    fn calc_bar_end(pos: &BarPosition) -> Units {
+        pos.bar_units - Units::new(1, Units::RESOLUTION)
+    }
     /*
      * The reason for this logic is to show the guitar view's string animation properly, can't seek to
      *  next bar directly, that will make the guitar view changed all in a sudden, so did a seek to
-     *  almost end of the bar, then the next time pass the first note on next bar.
+     *  the end of the bar, then the next time pass the first notes on next bar.
      * A bit hacky, but works fine.
      */
     fn seek_passed(&mut self, settings: &MidiSettings) -> bool {
-        if self.tab.is_some() {
-            let mut pos = self.play_control.position.bar;
-            if pos.in_bar_pos >= pos.bar_units - Units::MIN_ACCURACY {
-                pos = BarPosition::new(pos.bar_units, pos.bar_ordinal + 1, Units::MIN_ACCURACY);
-                if let Some(bar) = self.tab.as_ref().unwrap().get_bar(pos) {
-                    if let Some(props) = bar.get_next_entry(pos.in_bar_pos, &|x| {
-                        if x.track_kind() != settings.seeking_track {
-                            None
-                        } else if x.prev_is_tie() {
+        if let Some(tab) = self.tab.as_ref() {
+            let pos = self.play_control.position.bar;
+            if pos.in_bar_pos >= Self::calc_bar_end(&pos) {
+                let next = BarPosition::new(pos.bar_units, pos.bar_ordinal + 1, Units::ZERO);
+                if let Some(bar) = tab.get_bar(next) {
+                    let predicate = |x: &LaneEntry| {
+                        if x.track_kind() != settings.seeking_track || x.prev_is_tie() {
                             None
                         } else {
-                            Some(x.props.clone())
+                            Some(x.props.in_bar_pos)
                         }
-                    }) {
-                        self.setup_seek(pos.with_in_bar_pos(props.in_bar_pos));
+                    };
+                    // get_next_entry() only looks after the position, check the bar start first.
+                    let in_bar_pos = bar
+                        .get_entry_in_lanes(&|x| {
+                            if x.props.in_bar_pos == Units::ZERO {
+                                predicate(x)
+                            } else {
+                                None
+                            }
+                        })
+                        .or_else(|| bar.get_next_entry(Units::ZERO, &predicate));
+                    if let Some(in_bar_pos) = in_bar_pos {
+                        self.setup_seek(next.with_in_bar_pos(in_bar_pos));
                         return true;
                     }
                 }
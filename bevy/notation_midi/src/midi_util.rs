@@ -2,14 +2,18 @@ use std::convert::TryFrom;
 
 use helgoboss_midi::{Channel, KeyNumber, StructuredShortMessage, U7};
 use notation_model::prelude::{
-    CoreEntry, Entry, FrettedEntry4, FrettedEntry6, LaneEntry, Note, Pick, Semitones, TabBar, Tone,
+    CoreEntry, DrumEntry, DrumHit, Entry, FrettedEntry4, FrettedEntry6, LaneEntry, Note, Ornament, Pick, Semitones,
+    TabBar, Tone, Units,
 };
 
-use crate::prelude::MidiChannel;
+use crate::prelude::{MidiChannel, MidiMessage};
 
 pub struct MidiUtil();
 
 impl MidiUtil {
+    pub const DRUM_ACCENT_FACTOR: f32 = 1.25;
+    pub const DRUM_FLAM_GRACE_FACTOR: f32 = 0.6;
+    pub const DRUM_FLAM_GAP: Units = Units::new(1, 64);
     pub fn note_midi_key_number(note: &Note) -> Option<KeyNumber> {
         #[cfg(not(target_arch = "wasm32"))]
         let midi_note = Semitones::from(*note).0 + 12 - 1; //Not sure why got a higher pitch when playing, temp fix for get it right in video
@@ -63,7 +67,7 @@ impl MidiUtil {
             .map(|x| (true, x))
             .collect();
         play_msgs.append(&mut stop_msgs);
-        if play_msgs.len() > 0 {
+        if !play_msgs.is_empty() {
             Some(play_msgs)
         } else {
             None
@@ -80,6 +84,107 @@ impl MidiUtil {
             _ => None,
         }
     }
+    /// Drum hits carry their own accent and flam timing, so they are
+    /// converted to [`MidiMessage`]s directly instead of plain short messages.
+    pub fn get_drum_midi_msgs(
+        channel: &MidiChannel,
+        entry: &LaneEntry,
+        drum_entry: &DrumEntry,
+    ) -> Vec<MidiMessage> {
+        let mut msgs = vec![];
+        for hit in drum_entry.get_hits().iter() {
+            Self::add_drum_hit_msgs(channel, entry, hit, &mut msgs);
+        }
+        msgs
+    }
+    fn add_drum_hit_msgs(
+        channel: &MidiChannel,
+        entry: &LaneEntry,
+        hit: &DrumHit,
+        msgs: &mut Vec<MidiMessage>,
+    ) {
+        let key_number = match KeyNumber::try_from(hit.piece.gm_key()) {
+            Ok(key_number) => key_number,
+            Err(_) => return,
+        };
+        let on_msg = StructuredShortMessage::NoteOn {
+            channel: channel.channel,
+            key_number,
+            velocity: channel.velocity,
+        };
+        let off_msg = StructuredShortMessage::NoteOff {
+            channel: channel.channel,
+            key_number,
+            velocity: channel.velocity,
+        };
+        let factor = if hit.accent {
+            Self::DRUM_ACCENT_FACTOR
+        } else {
+            1.0
+        };
+        if hit.flam {
+            msgs.push(
+                MidiMessage::of_entry(entry, false, on_msg)
+                    .with_velocity_factor(Self::DRUM_FLAM_GRACE_FACTOR),
+            );
+            let mut main = MidiMessage::of_entry(entry, false, on_msg).with_velocity_factor(factor);
+            main.pos = main.pos.with_delay(Self::DRUM_FLAM_GAP);
+            msgs.push(main);
+        } else {
+            msgs.push(MidiMessage::of_entry(entry, false, on_msg).with_velocity_factor(factor));
+        }
+        msgs.push(MidiMessage::of_entry(entry, true, off_msg));
+    }
+    /// Plays the main notes of the entry with its ornament, the ornament notes
+    /// steal their time from the start of the main notes.
+    pub fn get_ornament_midi_msgs(
+        entry: &LaneEntry,
+        ornament: &Ornament,
+        msgs: &[(bool, StructuredShortMessage)],
+    ) -> Vec<MidiMessage> {
+        let mut result = vec![];
+        for step in ornament.calc_steps(entry.tied_units()).iter() {
+            for (delay, midi) in msgs.iter() {
+                if let Some(midi) = Self::shift_midi_msg(midi, step.offset) {
+                    let mut msg = MidiMessage::of_entry(entry, *delay, midi);
+                    msg.pos = msg.pos.with_delay(step.pos);
+                    msg.duration = step.units;
+                    result.push(msg);
+                }
+            }
+        }
+        result
+    }
+    fn shift_midi_msg(
+        midi: &StructuredShortMessage,
+        offset: Semitones,
+    ) -> Option<StructuredShortMessage> {
+        let shift = |key_number: KeyNumber| {
+            let key = u8::from(key_number) as i16 + offset.0 as i16;
+            u8::try_from(key).ok().and_then(|x| KeyNumber::try_from(x).ok())
+        };
+        match *midi {
+            StructuredShortMessage::NoteOn {
+                channel,
+                key_number,
+                velocity,
+            } => shift(key_number).map(|key_number| StructuredShortMessage::NoteOn {
+                channel,
+                key_number,
+                velocity,
+            }),
+            StructuredShortMessage::NoteOff {
+                channel,
+                key_number,
+                velocity,
+            } => shift(key_number).map(|key_number| StructuredShortMessage::NoteOff {
+                channel,
+                key_number,
+                velocity,
+            }),
+            _ => Some(*midi),
+        }
+    }
     pub fn get_midi_msgs(
         channel: &MidiChannel,
         bar: &TabBar,
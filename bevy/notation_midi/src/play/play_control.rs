@@ -14,7 +14,7 @@ pub struct PlaySpeed {
 impl PlaySpeed {
     pub fn new(tab_meta: &TabMeta) -> Self {
         let units_per_second =
-            Bpm::from(tab_meta.tempo) as f32 / 60.0 * Units::from(tab_meta.signature.beat_unit).0;
+            Bpm::from(tab_meta.tempo) as f32 / 60.0 * Units::from(tab_meta.signature.beat_unit).to_f32();
         Self {
             seconds_per_unit: 1.0 / units_per_second,
             units_per_second,
@@ -34,10 +34,10 @@ impl PlaySpeed {
         }
     }
     pub fn calc_units(&self, seconds: f32) -> Units {
-        Units(seconds * self.units_per_second * self.factor)
+        Units::from_f32(seconds * self.units_per_second * self.factor)
     }
     pub fn calc_seconds(&self, units: Units) -> f32 {
-        units.0 * self.seconds_per_unit / self.factor
+        units.to_f32() * self.seconds_per_unit / self.factor
     }
 }
 
@@ -115,7 +115,7 @@ impl PlayControl {
         } else {
             self.play_state = PlayState::Paused;
             //self.position
-            //    .set_in_bar(self.position.bar.bar_ordinal, Units(0.0));
+            //    .set_in_bar(self.position.bar.bar_ordinal, Units::ZERO);
             true
         }
     }
@@ -123,7 +123,7 @@ impl PlayControl {
         if !self.play_state.is_stopped() {
             self.play_state = PlayState::Stopped;
         }
-        self.position.set_in_bar(self.begin_bar_ordinal, Units(0.0));
+        self.position.set_in_bar(self.begin_bar_ordinal, Units::ZERO);
         true
     }
     pub fn _tick_to_position(&mut self, jumped: bool, pos: TabPosition) -> TickResult {
@@ -152,9 +152,9 @@ impl PlayControl {
             let delta_units = if self.position.bar.bar_ordinal < self.begin_bar_ordinal
                 || self.position.bar.bar_ordinal > self.end_bar_ordinal
             {
-                self.position.set_in_bar(self.begin_bar_ordinal, Units(0.0));
+                self.position.set_in_bar(self.begin_bar_ordinal, Units::ZERO);
                 jumped = true;
-                Units(0.0)
+                Units::ZERO
             } else {
                 self.play_speed.calc_units(delta_seconds)
             };
@@ -175,7 +175,7 @@ impl PlayControl {
         BarPosition::new(
             self.position.bar.bar_units,
             self.begin_bar_ordinal,
-            Units(0.0),
+            Units::ZERO,
         )
     }
     pub fn has_selection(&self, add_ready_section: bool) -> bool {
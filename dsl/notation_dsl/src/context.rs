@@ -4,7 +4,7 @@ use crate::core::duration::DurationTweakDsl;
 use crate::core::octave::OctaveTweakDsl;
 use fehler::{throw, throws};
 use notation_proto::prelude::{
-    Duration, Key, Note, Octave, Scale, Syllable, GUITAR_STRING_NUM, Pitch,
+    Duration, Key, Note, Octave, Scale, Syllable, TrackKind, GUITAR_STRING_NUM, Pitch,
 };
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
@@ -18,11 +18,13 @@ lazy_static! {
 
 #[derive(Copy, Clone, Debug)]
 pub struct FrettedContext {
+    pub track_kind: TrackKind,
     pub string_num: usize,
 }
 impl Default for FrettedContext {
     fn default() -> Self {
         Self {
+            track_kind: TrackKind::Guitar,
             string_num: GUITAR_STRING_NUM,
         }
     }
@@ -106,6 +108,13 @@ impl Context {
     pub fn set_octave(octave: Octave) {
         CONTEXT.write().unwrap().octave = octave;
     }
+    pub fn set_track_kind(track_kind: TrackKind) {
+        let fretted = &mut CONTEXT.write().unwrap().fretted;
+        fretted.track_kind = track_kind;
+        if let Some(string_num) = track_kind.string_num() {
+            fretted.string_num = string_num;
+        }
+    }
 }
 
 impl Context {
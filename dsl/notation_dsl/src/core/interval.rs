@@ -17,9 +17,16 @@ impl IntervalDsl {
 impl Parse for IntervalDsl {
     #[throws(Error)]
     fn parse(input: ParseStream) -> Self {
-        let interval = match input.parse::<LitInt>()?.base10_parse::<u8>()? {
-            1 => Interval::Unison,
-            2 | 9 => {
+        // `9`, `11` and `13` stay in the octave for old tabs, `^9`, `^11` and `^13` are compound.
+        let compound = if input.peek(Token![^]) {
+            input.parse::<Token![^]>()?;
+            true
+        } else {
+            false
+        };
+        let interval = match (compound, input.parse::<LitInt>()?.base10_parse::<u8>()?) {
+            (false, 1) => Interval::Unison,
+            (false, 2 | 9) => {
                 if input.peek(Token![-]) {
                     input.parse::<Token![-]>()?;
                     Interval::Minor2nd
@@ -27,7 +34,7 @@ impl Parse for IntervalDsl {
                     Interval::Major2nd
                 }
             }
-            3 => {
+            (false, 3) => {
                 if input.peek(Token![-]) {
                     input.parse::<Token![-]>()?;
                     Interval::Minor3nd
@@ -35,7 +42,7 @@ impl Parse for IntervalDsl {
                     Interval::Major3nd
                 }
             }
-            4 | 11 => {
+            (false, 4 | 11) => {
                 if input.peek(Token![+]) {
                     input.parse::<Token![+]>()?;
                     Interval::Augmented4th
@@ -43,7 +50,7 @@ impl Parse for IntervalDsl {
                     Interval::Perfect4th
                 }
             }
-            5 => {
+            (false, 5) => {
                 if input.peek(Token![%]) {
                     input.parse::<Token![%]>()?;
                     Interval::Diminished5th
@@ -54,7 +61,7 @@ impl Parse for IntervalDsl {
                     Interval::Perfect5th
                 }
             }
-            6 | 13 => {
+            (false, 6 | 13) => {
                 if input.peek(Token![-]) {
                     input.parse::<Token![-]>()?;
                     Interval::Minor6th
@@ -62,7 +69,7 @@ impl Parse for IntervalDsl {
                     Interval::Major6th
                 }
             }
-            7 => {
+            (false, 7) => {
                 if input.peek(Token![%]) {
                     input.parse::<Token![%]>()?;
                     Interval::Diminished7th
@@ -73,7 +80,34 @@ impl Parse for IntervalDsl {
                     Interval::Major7th
                 }
             }
-            8 => Interval::Perfect8ve,
+            (false, 8) => Interval::Perfect8ve,
+            (true, 9) => {
+                if input.peek(Token![-]) {
+                    input.parse::<Token![-]>()?;
+                    Interval::Minor9th
+                } else if input.peek(Token![+]) {
+                    input.parse::<Token![+]>()?;
+                    Interval::Augmented9th
+                } else {
+                    Interval::Major9th
+                }
+            }
+            (true, 11) => {
+                if input.peek(Token![+]) {
+                    input.parse::<Token![+]>()?;
+                    Interval::Augmented11th
+                } else {
+                    Interval::Perfect11th
+                }
+            }
+            (true, 13) => {
+                if input.peek(Token![-]) {
+                    input.parse::<Token![-]>()?;
+                    Interval::Minor13th
+                } else {
+                    Interval::Major13th
+                }
+            }
             _ => throw!(Error::new(input.span(), "Invalid Interval")),
         };
         IntervalDsl::new(interval)
@@ -82,7 +116,7 @@ impl Parse for IntervalDsl {
 
 impl IntervalDsl {
     pub fn peek(input: ParseStream) -> bool {
-        input.peek(LitInt)
+        input.peek(LitInt) || input.peek(Token![^])
     }
 }
 
@@ -100,3 +134,29 @@ impl IntervalDsl {
         self.interval
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Option<Interval> {
+        syn::parse_str::<IntervalDsl>(text).ok().map(|x| x.to_proto())
+    }
+
+    #[test]
+    fn test_old_tokens_in_octave() {
+        assert_eq!(parse("9"), Some(Interval::Major2nd));
+        assert_eq!(parse("11"), Some(Interval::Perfect4th));
+        assert_eq!(parse("13"), Some(Interval::Major6th));
+    }
+
+    #[test]
+    fn test_compound() {
+        assert_eq!(parse("^9"), Some(Interval::Major9th));
+        assert_eq!(parse("^9-"), Some(Interval::Minor9th));
+        assert_eq!(parse("^9+"), Some(Interval::Augmented9th));
+        assert_eq!(parse("^11+"), Some(Interval::Augmented11th));
+        assert_eq!(parse("^13-"), Some(Interval::Minor13th));
+        assert_eq!(parse("^5"), None);
+    }
+}
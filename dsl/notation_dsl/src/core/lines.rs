@@ -0,0 +1,193 @@
+use fehler::{throw, throws};
+use notation_proto::prelude::{LyricEntry, LyricExtender, LyricLine, LyricWord};
+use notation_proto::proto_entry::ProtoEntry;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{parenthesized, Ident, LitInt, LitStr, Token};
+
+use crate::context::Context;
+
+use super::duration::DurationTweakDsl;
+
+/// Syllable of one verse, e.g. `1:zh "你" "nǐ" _`, the language is an ident or a
+/// string, followed by the word, the optional reading, and `-` or `_` for the extender.
+pub struct LineDsl {
+    pub verse: u8,
+    pub lang: String,
+    pub word: String,
+    pub phonetic: Option<String>,
+    pub extender: LyricExtender,
+}
+
+impl Parse for LineDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        let verse = input.parse::<LitInt>()?.base10_parse::<u8>()?;
+        let mut lang = String::new();
+        if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            lang = if input.peek(LitStr) {
+                input.parse::<LitStr>()?.value()
+            } else {
+                input.parse::<Ident>()?.to_string()
+            };
+        }
+        let word = input.parse::<LitStr>()?.value();
+        let phonetic = if input.peek(LitStr) {
+            Some(input.parse::<LitStr>()?.value())
+        } else {
+            None
+        };
+        let extender = if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            LyricExtender::Hyphen
+        } else if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+            LyricExtender::Melisma
+        } else {
+            LyricExtender::None
+        };
+        LineDsl {
+            verse,
+            lang,
+            word,
+            phonetic,
+            extender,
+        }
+    }
+}
+
+impl ToTokens for LineDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let LineDsl {
+            verse,
+            lang,
+            word,
+            phonetic,
+            extender,
+        } = self;
+        let mut line = quote! {
+            LyricLine::new(#verse, String::from(#lang), LyricWord::from(#word))
+        };
+        if let Some(phonetic) = phonetic {
+            line = quote! { #line.with_phonetic(String::from(#phonetic)) };
+        }
+        let extender_quote = match extender {
+            LyricExtender::None => None,
+            LyricExtender::Hyphen => Some(quote! { LyricExtender::Hyphen }),
+            LyricExtender::Melisma => Some(quote! { LyricExtender::Melisma }),
+        };
+        if let Some(extender) = extender_quote {
+            line = quote! { #line.with_extender(#extender) };
+        }
+        tokens.extend(line);
+    }
+}
+
+impl LineDsl {
+    pub fn to_proto(&self) -> LyricLine {
+        let mut line = LyricLine::new(self.verse, self.lang.clone(), LyricWord::from(self.word.clone()));
+        if let Some(phonetic) = &self.phonetic {
+            line = line.with_phonetic(phonetic.clone());
+        }
+        line.with_extender(self.extender)
+    }
+}
+
+/// Syllables of several verses or languages on the same notes, separated by `;`,
+/// e.g. `Lines(1 "Hel" -; 2 "Good"; 1:zh "你" "nǐ"),`.
+pub struct LinesDsl {
+    pub lines: Vec<LineDsl>,
+    pub duration_tweak: Option<DurationTweakDsl>,
+}
+
+impl Parse for LinesDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        let content;
+        let paren = parenthesized!(content in input);
+        let lines: Vec<LineDsl> = Punctuated::<LineDsl, Token![;]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+        if lines.is_empty() {
+            throw!(Error::new(paren.span, "Invalid Lines, expecting at least one line"));
+        }
+        let duration_tweak = DurationTweakDsl::try_parse(input);
+        LinesDsl {
+            lines,
+            duration_tweak,
+        }
+    }
+}
+
+impl ToTokens for LinesDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let LinesDsl {
+            lines,
+            duration_tweak,
+        } = self;
+        let duration_quote = Context::duration_quote(duration_tweak);
+        tokens.extend(quote! {
+            ProtoEntry::from(LyricEntry::from(
+                (vec![#(#lines),*], #duration_quote)
+            ))
+        });
+    }
+}
+
+impl LinesDsl {
+    pub fn to_proto(&self) -> ProtoEntry {
+        let lines = self.lines.iter().map(|x| x.to_proto()).collect::<Vec<_>>();
+        let duration = Context::tweaked_duration(&self.duration_tweak);
+        ProtoEntry::from(LyricEntry::from((lines, duration)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::Duration;
+    use syn::parse::Parser;
+
+    use super::*;
+
+    fn parse(text: &str) -> syn::Result<LinesDsl> {
+        (|input: ParseStream| LinesDsl::parse(input)).parse_str(text)
+    }
+
+    #[test]
+    fn test_lines_to_proto() {
+        let dsl = parse(r#"(1 "Hel" -; 2 "Good"; 1:zh "你" "nǐ" _; 1:"zh-CN" "好";),"#).unwrap();
+        let expected = vec![
+            LyricLine::new(1, "".into(), "Hel".into()).with_extender(LyricExtender::Hyphen),
+            LyricLine::new(2, "".into(), "Good".into()),
+            LyricLine::new(1, "zh".into(), "你".into())
+                .with_phonetic("nǐ".into())
+                .with_extender(LyricExtender::Melisma),
+            LyricLine::new(1, "zh-CN".into(), "好".into()),
+        ];
+        let duration = Context::tweaked_duration(&dsl.duration_tweak);
+        assert_ne!(duration, Context::duration());
+        assert_eq!(dsl.to_proto().as_lyric(), Some(&LyricEntry::Lines(expected, duration)));
+    }
+
+    #[test]
+    fn test_lines_to_tokens() {
+        let dsl = parse(r#"(1 "Hel" -; 1:zh "你" "nǐ")"#).unwrap();
+        let tokens = dsl.to_token_stream().to_string();
+        assert!(tokens.contains("LyricExtender :: Hyphen"));
+        assert!(tokens.contains("with_phonetic (String :: from (\"nǐ\"))"));
+        assert!(syn::parse2::<syn::Expr>(dsl.to_token_stream()).is_ok());
+        let duration: Duration = Context::tweaked_duration(&dsl.duration_tweak);
+        assert_eq!(dsl.to_proto().duration(), duration);
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        assert!(parse("()").is_err());
+        assert!(parse(r#"("Hel")"#).is_err());
+        assert!(parse(r#"(1: "Hel")"#).is_err());
+        assert!(parse(r#"(300 "Hel")"#).is_err());
+    }
+}
@@ -2,6 +2,7 @@ pub mod chord;
 pub mod duration;
 pub mod empty;
 pub mod interval;
+pub mod lines;
 pub mod note;
 pub mod octave;
 pub mod pitch_name;
@@ -0,0 +1,128 @@
+use fehler::throws;
+use notation_proto::prelude::{CoreEntry, DrumEntry};
+use notation_proto::proto_entry::ProtoEntry;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, ParseStream};
+use syn::Token;
+
+use crate::context::Context;
+use crate::core::duration::DurationTweakDsl;
+
+use super::drum_hit::DrumHitDsl;
+
+pub struct DrumDsl {
+    pub hits: Vec<DrumHitDsl>,
+    pub duration_tweak: Option<DurationTweakDsl>,
+}
+
+impl DrumDsl {
+    #[throws(Error)]
+    pub fn parse_without_paren(input: ParseStream, multied: bool, with_paren: bool) -> Self {
+        let mut hits = vec![];
+        if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+        } else {
+            while DrumHitDsl::peek(input) {
+                hits.push(input.parse()?);
+                if multied && !with_paren {
+                    break;
+                }
+            }
+        }
+        let duration_tweak = DurationTweakDsl::try_parse(input);
+        DrumDsl {
+            hits,
+            duration_tweak,
+        }
+    }
+}
+
+impl ToTokens for DrumDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let DrumDsl {
+            hits,
+            duration_tweak,
+        } = self;
+        let duration_quote = Context::duration_quote(duration_tweak);
+        if hits.is_empty() {
+            tokens.extend(quote! {
+                ProtoEntry::from(CoreEntry::from(#duration_quote))
+            });
+        } else {
+            let hits_quote: Vec<_> = hits.iter().map(|x| quote! { #x }).collect();
+            tokens.extend(quote! {
+                ProtoEntry::from(DrumEntry::from(
+                    (vec![
+                        #(#hits_quote),*
+                    ], #duration_quote)
+                ))
+            });
+        }
+    }
+}
+
+impl DrumDsl {
+    pub fn to_proto(&self) -> ProtoEntry {
+        let DrumDsl {
+            hits,
+            duration_tweak,
+        } = self;
+        let duration = Context::tweaked_duration(duration_tweak);
+        if hits.is_empty() {
+            ProtoEntry::from(CoreEntry::from(duration))
+        } else {
+            let hits: Vec<_> = hits.iter().map(|x| x.to_proto()).collect();
+            ProtoEntry::from(DrumEntry::from((hits, duration)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{Duration, DrumHit, DrumPiece};
+    use syn::parse::Parser;
+
+    use super::*;
+
+    fn parse(text: &str) -> syn::Result<ProtoEntry> {
+        (|input: ParseStream| DrumDsl::parse_without_paren(input, false, false))
+            .parse_str(text)
+            .map(|x| x.to_proto())
+    }
+
+    #[test]
+    fn test_hits_to_proto() {
+        let entry = parse("K H^ ~S ,").unwrap();
+        let hits = vec![
+            DrumHit::from(DrumPiece::Kick),
+            DrumHit::new(DrumPiece::HiHatClosed, true, false),
+            DrumHit::new(DrumPiece::Snare, false, true),
+        ];
+        assert_eq!(entry.as_drum(), Some(&DrumEntry::Hits(hits, Duration::_1_8)));
+        let entry = parse("T1 T2 T3 C R O").unwrap();
+        let hits = entry.as_drum().unwrap().get_hits();
+        let pieces: Vec<DrumPiece> = hits.iter().map(|x| x.piece).collect();
+        assert_eq!(
+            pieces,
+            vec![
+                DrumPiece::HighTom,
+                DrumPiece::MidTom,
+                DrumPiece::FloorTom,
+                DrumPiece::Crash,
+                DrumPiece::Ride,
+                DrumPiece::HiHatOpen,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rest_and_errors() {
+        let entry = parse("_ *").unwrap();
+        assert_eq!(entry.as_core(), Some(&CoreEntry::Rest(Duration::_1_2)));
+        assert!(parse("K X").is_err());
+        let single = (|input: ParseStream| DrumDsl::parse_without_paren(input, true, false))
+            .parse_str("K S");
+        assert!(single.is_err());
+    }
+}
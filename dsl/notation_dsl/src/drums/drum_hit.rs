@@ -0,0 +1,66 @@
+use fehler::{throw, throws};
+use notation_proto::prelude::{DrumHit, DrumPiece};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream};
+use syn::{Ident, Token};
+
+pub struct DrumHitDsl {
+    pub piece: DrumPiece,
+    pub accent: bool,
+    pub flam: bool,
+}
+
+impl Parse for DrumHitDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        let flam = if input.peek(Token![~]) {
+            input.parse::<Token![~]>()?;
+            true
+        } else {
+            false
+        };
+        let ident = input.parse::<Ident>()?;
+        let piece = match DrumPiece::from_ident(ident.to_string().as_str()) {
+            Some(piece) => piece,
+            None => throw!(Error::new(ident.span(), "Invalid Drum Piece")),
+        };
+        let accent = if input.peek(Token![^]) {
+            input.parse::<Token![^]>()?;
+            true
+        } else {
+            false
+        };
+        DrumHitDsl {
+            piece,
+            accent,
+            flam,
+        }
+    }
+}
+
+impl DrumHitDsl {
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Token![~]) || input.peek(Ident)
+    }
+}
+
+impl ToTokens for DrumHitDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let DrumHitDsl {
+            piece,
+            accent,
+            flam,
+        } = self;
+        let piece_ident = format_ident!("{}", format!("{:?}", piece));
+        tokens.extend(quote! {
+            DrumHit::new(DrumPiece::#piece_ident, #accent, #flam)
+        });
+    }
+}
+
+impl DrumHitDsl {
+    pub fn to_proto(&self) -> DrumHit {
+        DrumHit::new(self.piece, self.accent, self.flam)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod drum;
+pub mod drum_hit;
@@ -1,18 +1,20 @@
 use fehler::throws;
 use notation_proto::prelude::{
-    Fretboard4, Fretboard6, FrettedEntry4, FrettedEntry6, GuitarTuning,
-    GUITAR_FRET_NUM_ACOUSTIC, Pitch, Octave,
+    BassUtil, Fretboard4, Fretboard6, FrettedEntry4, FrettedEntry6, GuitarTuning, Semitones,
+    TrackKind, BASS_FRET_NUM_STANDARD, GUITAR_FRET_NUM_ACOUSTIC, MANDOLIN_FRET_NUM, UKULELE_FRET_NUM_CONCERT,
 };
 use notation_proto::proto_entry::ProtoEntry;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, Parse, ParseStream};
-use syn::{Ident, LitInt, Token};
+use syn::{LitInt, Token};
 
 use crate::context::Context;
 
+use super::tuning::TuningDsl;
+
 pub struct FretboardDsl {
-    pub tuning: Option<Ident>,
+    pub tuning: Option<TuningDsl>,
     pub fret_num: Option<usize>,
     pub capo: Option<u8>,
 }
@@ -33,7 +35,7 @@ impl Parse for FretboardDsl {
             if input.peek(kw::tuning) {
                 input.parse::<kw::tuning>()?;
                 input.parse::<Token![:]>()?;
-                tuning = Some(input.parse::<Ident>()?);
+                tuning = Some(input.parse::<TuningDsl>()?);
             } else if input.peek(kw::fret_num) {
                 input.parse::<kw::fret_num>()?;
                 input.parse::<Token![:]>()?;
@@ -54,6 +56,32 @@ impl Parse for FretboardDsl {
     }
 }
 
+impl FretboardDsl {
+    fn default_fret_num(track_kind: &TrackKind) -> usize {
+        match track_kind {
+            TrackKind::Ukulele => UKULELE_FRET_NUM_CONCERT,
+            TrackKind::Mandolin => MANDOLIN_FRET_NUM,
+            TrackKind::Bass => BASS_FRET_NUM_STANDARD,
+            _ => GUITAR_FRET_NUM_ACOUSTIC,
+        }
+    }
+    fn string_notes4(&self, track_kind: &TrackKind) -> [Semitones; 4] {
+        match &self.tuning {
+            Some(TuningDsl::Strings4(string_notes)) => *string_notes,
+            _ => track_kind
+                .default_fretboard4()
+                .unwrap_or_else(BassUtil::new_default_fretboard)
+                .string_notes,
+        }
+    }
+    fn guitar_tuning(&self) -> GuitarTuning {
+        match &self.tuning {
+            Some(TuningDsl::Guitar(tuning)) => *tuning,
+            _ => GuitarTuning::Standard,
+        }
+    }
+}
+
 impl ToTokens for FretboardDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let FretboardDsl {
@@ -61,28 +89,21 @@ impl ToTokens for FretboardDsl {
             fret_num,
             capo,
         } = self;
-        let string_num = Context::fretted().string_num;
-        let fret_num = fret_num.unwrap_or(match string_num {
-            6 => GUITAR_FRET_NUM_ACOUSTIC,
-            _ => GUITAR_FRET_NUM_ACOUSTIC,
-        });
+        let fretted = Context::fretted();
+        let fret_num = fret_num.unwrap_or(Self::default_fret_num(&fretted.track_kind));
         let capo = capo.unwrap_or(0);
-        let tuning_quote = match tuning {
-            Some(tuning) => {
-                let tuning_quote = tuning.to_string();
-                match string_num {
-                    _ => quote! { GuitarTuning::from_ident(#tuning_quote)},
-                }
-            }
-            None => match string_num {
-                _ => quote! { GuitarTuning::Standard },
+        let tuning_quote = match fretted.string_num {
+            4 => TuningDsl::quote_string_notes4(&self.string_notes4(&fretted.track_kind)),
+            _ => match tuning {
+                Some(tuning) => quote! { #tuning.into() },
+                None => quote! { GuitarTuning::Standard.into() },
             },
         };
-        let fretted_entry_quote = Context::fretted().fretted_entry_quote();
-        let fretboard_quote = Context::fretted().fretboard_quote();
+        let fretted_entry_quote = fretted.fretted_entry_quote();
+        let fretboard_quote = fretted.fretboard_quote();
         tokens.extend(quote! {
             ProtoEntry::from(#fretted_entry_quote::from(
-                #fretboard_quote::new(#fret_num, #tuning_quote.into(), #capo)
+                #fretboard_quote::new(#fret_num, #tuning_quote, #capo)
             ))
         });
     }
@@ -90,33 +111,20 @@ impl ToTokens for FretboardDsl {
 
 impl FretboardDsl {
     pub fn to_proto(&self) -> ProtoEntry {
-        let FretboardDsl {
-            tuning,
-            fret_num,
-            capo,
-        } = self;
-        let string_num = Context::fretted().string_num;
-        let fret_num = fret_num.unwrap_or(match string_num {
-            6 => GUITAR_FRET_NUM_ACOUSTIC,
-            _ => GUITAR_FRET_NUM_ACOUSTIC,
-        });
+        let FretboardDsl { fret_num, capo, .. } = self;
+        let fretted = Context::fretted();
+        let fret_num = fret_num.unwrap_or(Self::default_fret_num(&fretted.track_kind));
         let capo = capo.unwrap_or(0);
-        match string_num {
+        match fretted.string_num {
             4 => {
-                let tuning = [(Pitch::E, Octave::P2).into(); 4]; //todo;
-                ProtoEntry::from(FrettedEntry4::from(Fretboard4::new(fret_num, tuning, capo)))
-            }
-            _ => {
-                let tuning = match tuning {
-                    Some(ident) => GuitarTuning::from_ident(ident.to_string().as_str()),
-                    None => GuitarTuning::Standard,
-                };
-                ProtoEntry::from(FrettedEntry6::from(Fretboard6::new(
-                    fret_num,
-                    tuning.into(),
-                    capo,
-                )))
+                let string_notes = self.string_notes4(&fretted.track_kind);
+                ProtoEntry::from(FrettedEntry4::from(Fretboard4::new(fret_num, string_notes, capo)))
             }
+            _ => ProtoEntry::from(FrettedEntry6::from(Fretboard6::new(
+                fret_num,
+                self.guitar_tuning().into(),
+                capo,
+            ))),
         }
     }
 }
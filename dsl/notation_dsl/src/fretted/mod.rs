@@ -2,3 +2,4 @@ pub mod fretboard;
 pub mod pick;
 pub mod pick_note;
 pub mod shape;
+pub mod tuning;
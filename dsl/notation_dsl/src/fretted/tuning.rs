@@ -0,0 +1,243 @@
+use fehler::{throw, throws};
+use notation_proto::prelude::{
+    BassTuning, GuitarTuning, MandolinTuning, Octave, Pitch, PitchName, PitchSign, Semitones,
+    TrackKind, UkuleleTuning,
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Error, Parse, ParseStream};
+use syn::{bracketed, token, Ident, LitInt, Token};
+
+use crate::context::{Context, FrettedContext};
+
+/// Either a preset name (`DropD`, `OpenG`, `LowG` ...), or the open string pitches
+/// from the lowest string to the highest one, e.g. `[D A D G A D]`, `[Eb2 Ab2 Db3 Gb3 Bb3 Eb4]`,
+/// or `[#C #G #C #F #A #D]`, checked against the string number of the track while parsing.
+pub enum TuningDsl {
+    Guitar(GuitarTuning),
+    Strings4([Semitones; 4]),
+}
+
+impl Parse for TuningDsl {
+    #[throws(Error)]
+    fn parse(input: ParseStream) -> Self {
+        Self::parse_for(input, &Context::fretted())?
+    }
+}
+
+impl TuningDsl {
+    #[throws(Error)]
+    pub fn parse_for(input: ParseStream, fretted: &FrettedContext) -> Self {
+        if input.peek(token::Bracket) {
+            let content;
+            let bracket = bracketed!(content in input);
+            let mut pitches = vec![];
+            while !content.is_empty() {
+                pitches.push(Self::parse_pitch(&content)?);
+            }
+            match Self::from_pitches(fretted, &pitches) {
+                Some(tuning) => tuning,
+                None => throw!(Error::new(
+                    bracket.span,
+                    format!(
+                        "Invalid Tuning, expecting {} pitches, got: {}",
+                        fretted.string_num,
+                        pitches.len()
+                    )
+                )),
+            }
+        } else {
+            let ident = input.parse::<Ident>()?;
+            match Self::from_preset(fretted, ident.to_string().as_str()) {
+                Some(tuning) => tuning,
+                None => throw!(Error::new(
+                    ident.span(),
+                    format!("Invalid Tuning for {}: {}", fretted.track_kind, ident)
+                )),
+            }
+        }
+    }
+    fn from_preset(fretted: &FrettedContext, ident: &str) -> Option<Self> {
+        Some(match (fretted.string_num, fretted.track_kind) {
+            (6, _) => Self::Guitar(GuitarTuning::from_preset_ident(ident)?),
+            (4, TrackKind::Ukulele) => Self::Strings4(UkuleleTuning::from_preset_ident(ident)?.into()),
+            (4, TrackKind::Mandolin) => Self::Strings4(MandolinTuning::from_preset_ident(ident)?.into()),
+            (4, _) => Self::Strings4(BassTuning::from_preset_ident(ident)?.into()),
+            _ => return None,
+        })
+    }
+    fn from_pitches(fretted: &FrettedContext, pitches: &[(Pitch, Option<Octave>)]) -> Option<Self> {
+        Some(match (fretted.string_num, fretted.track_kind) {
+            (6, _) => Self::Guitar(GuitarTuning::from_pitches(Self::to_pitches(pitches)?)),
+            (4, TrackKind::Ukulele) => {
+                Self::Strings4(UkuleleTuning::from_pitches(Self::to_pitches(pitches)?).into())
+            }
+            (4, TrackKind::Mandolin) => {
+                Self::Strings4(MandolinTuning::from_pitches(Self::to_pitches(pitches)?).into())
+            }
+            (4, _) => Self::Strings4(BassTuning::from_pitches(Self::to_pitches(pitches)?).into()),
+            _ => return None,
+        })
+    }
+}
+
+impl TuningDsl {
+    #[throws(Error)]
+    fn parse_pitch(input: ParseStream) -> (Pitch, Option<Octave>) {
+        let mut semitones: i8 = 0;
+        while input.peek(Token![#]) {
+            input.parse::<Token![#]>()?;
+            semitones += 1;
+        }
+        let ident = input.parse::<Ident>()?;
+        let text = ident.to_string();
+        let mut chars = text.chars();
+        let name = match chars.next() {
+            Some('C') => PitchName::C,
+            Some('D') => PitchName::D,
+            Some('E') => PitchName::E,
+            Some('F') => PitchName::F,
+            Some('G') => PitchName::G,
+            Some('A') => PitchName::A,
+            Some('B') => PitchName::B,
+            _ => throw!(Error::new(ident.span(), "Invalid Pitch Name")),
+        };
+        let rest: String = chars.collect();
+        let flats = rest.chars().take_while(|x| *x == 'b').count();
+        let digits = &rest[flats..];
+        let mut octave = if digits.is_empty() {
+            None
+        } else {
+            match digits.parse::<i8>() {
+                Ok(v) => Some(Octave::from(Semitones(v * 12))),
+                Err(_) => throw!(Error::new(ident.span(), "Invalid Octave")),
+            }
+        };
+        semitones -= flats as i8;
+        if octave.is_none() && input.peek(LitInt) {
+            let v = input.parse::<LitInt>()?.base10_parse::<i8>()?;
+            octave = Some(Octave::from(Semitones(v * 12)));
+        }
+        if semitones.abs() > 2 {
+            throw!(Error::new(ident.span(), "Invalid Pitch Sign"));
+        }
+        (Pitch::new(name, PitchSign::from(Semitones(semitones))), octave)
+    }
+    fn to_pitches<const N: usize>(pitches: &[(Pitch, Option<Octave>)]) -> Option<[(Pitch, Option<Octave>); N]> {
+        if pitches.len() != N {
+            return None;
+        }
+        let mut v = [(Pitch::E, None); N];
+        v.copy_from_slice(pitches);
+        Some(v)
+    }
+    pub fn quote_string_notes4(string_notes: &[Semitones; 4]) -> TokenStream {
+        let notes: Vec<i8> = string_notes.iter().map(|x| x.0).collect();
+        quote! {
+            [#(Semitones(#notes)),*]
+        }
+    }
+}
+
+impl ToTokens for TuningDsl {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            TuningDsl::Guitar(GuitarTuning::Custom(string_notes)) => {
+                let notes: Vec<i8> = string_notes.iter().map(|x| x.0).collect();
+                quote! {
+                    GuitarTuning::Custom([#(Semitones(#notes)),*])
+                }
+            }
+            TuningDsl::Guitar(tuning) => {
+                let ident = tuning.to_ident();
+                quote! { GuitarTuning::from_ident(#ident) }
+            }
+            TuningDsl::Strings4(string_notes) => Self::quote_string_notes4(string_notes),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse::Parser;
+
+    use super::*;
+
+    fn parse(track_kind: TrackKind, text: &str) -> syn::Result<TuningDsl> {
+        let fretted = FrettedContext {
+            track_kind,
+            string_num: track_kind.string_num().unwrap(),
+        };
+        (|input: ParseStream| TuningDsl::parse_for(input, &fretted)).parse_str(text)
+    }
+
+    fn guitar(text: &str) -> Option<GuitarTuning> {
+        match parse(TrackKind::Guitar, text).ok()? {
+            TuningDsl::Guitar(tuning) => Some(tuning),
+            TuningDsl::Strings4(_) => None,
+        }
+    }
+
+    fn strings4(track_kind: TrackKind, text: &str) -> Option<[Semitones; 4]> {
+        match parse(track_kind, text).ok()? {
+            TuningDsl::Guitar(_) => None,
+            TuningDsl::Strings4(string_notes) => Some(string_notes),
+        }
+    }
+
+    #[test]
+    fn test_presets() {
+        assert_eq!(guitar("OpenD"), Some(GuitarTuning::OpenD));
+        assert_eq!(guitar("Dadgad"), Some(GuitarTuning::Dadgad));
+        assert_eq!(
+            strings4(TrackKind::Ukulele, "LowG"),
+            Some(UkuleleTuning::LowG.into())
+        );
+        assert_eq!(
+            strings4(TrackKind::Bass, "DropD"),
+            Some(BassTuning::DropD.into())
+        );
+        let err = parse(TrackKind::Guitar, "OpenDD").err().unwrap();
+        assert_eq!(err.to_string(), "Invalid Tuning for Guitar: OpenDD");
+        assert!(parse(TrackKind::Guitar, "LowG").is_err());
+        assert!(parse(TrackKind::Bass, "LowG").is_err());
+        assert!(parse(TrackKind::Mandolin, "DropD").is_err());
+    }
+
+    #[test]
+    fn test_pitch_names() {
+        assert_eq!(guitar("[D A D G A D]"), Some(GuitarTuning::Dadgad));
+        assert_eq!(guitar("[D A D #F A D]"), Some(GuitarTuning::OpenD));
+        assert_eq!(
+            guitar("[#D #G #C #F #A #D]"),
+            Some(GuitarTuning::StandardFlat)
+        );
+        assert_eq!(
+            strings4(TrackKind::Mandolin, "[G D A E]"),
+            Some(MandolinTuning::Standard.into())
+        );
+        assert!(parse(TrackKind::Guitar, "[D A D G A]").is_err());
+        assert!(parse(TrackKind::Bass, "[D A D G A D]").is_err());
+        assert!(parse(TrackKind::Guitar, "[H A D G B E]").is_err());
+        assert!(parse(TrackKind::Guitar, "[Ebbb A D G B E]").is_err());
+    }
+
+    #[test]
+    fn test_pitch_octaves() {
+        assert_eq!(
+            guitar("[Eb2 Ab2 Db3 Gb3 Bb3 Eb4]"),
+            Some(GuitarTuning::StandardFlat)
+        );
+        assert_eq!(
+            guitar("[Eb 2 Ab 2 Db 3 Gb 3 Bb 3 Eb 4]"),
+            Some(GuitarTuning::StandardFlat)
+        );
+        let low = match guitar("[D1 A D G B E]") {
+            Some(GuitarTuning::Custom(string_notes)) => string_notes,
+            _ => panic!("expecting a custom tuning"),
+        };
+        assert_eq!(low[5], (Pitch::D, Octave::P1).into());
+        assert_eq!(low[0], (Pitch::E, Octave::P4).into());
+        assert!(parse(TrackKind::Guitar, "[E2x A D G B E]").is_err());
+    }
+}
@@ -9,6 +9,7 @@ pub use {proc_macro2, quote, syn};
 
 pub mod context;
 pub mod core;
+pub mod drums;
 pub mod fretted;
 pub mod get_tab;
 pub mod helper;
@@ -8,8 +8,10 @@ use syn::Ident;
 use crate::context::ContextDsl;
 
 use crate::core::chord::ChordDsl;
+use crate::core::lines::LinesDsl;
 use crate::core::tone::ToneDsl;
 use crate::core::word::WordDsl;
+use crate::drums::drum::DrumDsl;
 use crate::fretted::fretboard::FretboardDsl;
 use crate::fretted::pick::PickDsl;
 use crate::fretted::shape::ShapeDsl;
@@ -25,9 +27,11 @@ pub enum EntryDsl {
     Tone(MultibleDsl<ToneDsl>),
     Chord(MultibleDsl<ChordDsl>),
     Word(MultibleDsl<WordDsl>),
+    Lines(LinesDsl),
     Pick(MultibleDsl<PickDsl>),
     Shape(ShapeDsl),
     Fretboard(FretboardDsl),
+    Drum(MultibleDsl<DrumDsl>),
 }
 
 impl EntryDsl {
@@ -42,9 +46,11 @@ impl EntryDsl {
                 "Tone" => Self::Tone(input.parse()?),
                 "Chord" => Self::Chord(input.parse()?),
                 "Word" => Self::Word(input.parse()?),
+                "Lines" => Self::Lines(input.parse()?),
                 "Pick" => Self::Pick(input.parse()?),
                 "Shape" => Self::Shape(input.parse()?),
                 "Fretboard" => Self::Fretboard(input.parse()?),
+                "Drum" => Self::Drum(input.parse()?),
                 _ => throw!(Error::new(input.span(), "Invalid Entry")),
             }
         }
@@ -59,9 +65,11 @@ impl ToTokens for EntryDsl {
             Self::Tone(x) => quote! { #x },
             Self::Chord(x) => quote! { #x },
             Self::Word(x) => quote! { #x },
+            Self::Lines(x) => quote! { #x },
             Self::Pick(x) => quote! { #x },
             Self::Shape(x) => quote! { #x },
             Self::Fretboard(x) => quote! { #x },
+            Self::Drum(x) => quote! { #x },
         });
     }
 }
@@ -73,9 +81,11 @@ impl EntryDsl {
             EntryDsl::Tone(x) => x.add_proto(entries),
             EntryDsl::Chord(x) => x.add_proto(entries),
             EntryDsl::Word(x) => x.add_proto(entries),
+            EntryDsl::Lines(x) => entries.push(x.to_proto()),
             EntryDsl::Pick(x) => x.add_proto(entries),
             EntryDsl::Shape(x) => entries.push(x.to_proto()),
             EntryDsl::Fretboard(x) => entries.push(x.to_proto()),
+            EntryDsl::Drum(x) => x.add_proto(entries),
         }
     }
 }
@@ -4,6 +4,7 @@ use notation_proto::prelude::BarLayer;
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::parse::{Error, ParseStream};
+use syn::LitInt;
 
 use crate::proto::slice::SliceDsl;
 
@@ -11,19 +12,30 @@ use super::id::IdDsl;
 
 pub struct LayerDsl {
     pub track: IdDsl,
+    pub voice: usize,
     pub slices: Vec<SliceDsl>,
 }
 
 mod kw {
     syn::custom_keyword!(Rounds);
+    syn::custom_keyword!(Voice);
 }
 
 impl LayerDsl {
     #[throws(Error)]
     pub fn parse_without_brace(input: ParseStream) -> Self {
         let track = input.parse()?;
+        let mut voice = BarLayer::MAIN_VOICE;
+        if input.peek(kw::Voice) {
+            input.parse::<kw::Voice>()?;
+            voice = input.parse::<LitInt>()?.base10_parse::<usize>()?;
+        }
         let slices = SliceDsl::parse_vec(input)?;
-        LayerDsl { track, slices }
+        LayerDsl {
+            track,
+            voice,
+            slices,
+        }
     }
     pub fn peek(input: ParseStream) -> bool {
         IdDsl::peek(input)
@@ -31,10 +43,14 @@ impl LayerDsl {
 }
 impl ToTokens for LayerDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let LayerDsl { track, slices } = self;
+        let LayerDsl {
+            track,
+            voice,
+            slices,
+        } = self;
         let slices_quote = SliceDsl::quote_vec(slices);
         tokens.extend(quote! {
-            BarLayer::new(#track.into(), #slices_quote)
+            BarLayer::new_voice(#track.into(), #voice, #slices_quote)
         });
     }
 }
@@ -42,6 +58,49 @@ impl ToTokens for LayerDsl {
 impl LayerDsl {
     pub fn to_proto(&self) -> BarLayer {
         let slices = self.slices.iter().map(|x| x.to_proto()).collect();
-        BarLayer::new(self.track.id.clone(), slices)
+        BarLayer::new_voice(self.track.id.clone(), self.voice, slices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{SliceBegin, SliceEnd};
+    use syn::parse::{Parse, Parser};
+
+    use crate::proto::bar::BarDsl;
+
+    use super::*;
+
+    fn parse_bar(text: &str) -> syn::Result<BarDsl> {
+        (|input: ParseStream| BarDsl::parse(input)).parse_str(text)
+    }
+
+    #[test]
+    fn test_voice_to_proto() {
+        let bar = parse_bar("{guitar [0 2] guitar Voice 1 [2 1] chords [0 1]}").unwrap().to_proto();
+        let voices: Vec<(&str, usize)> = bar.layers.iter().map(|x| (x.track.as_str(), x.voice)).collect();
+        assert_eq!(voices, vec![("guitar", 0), ("guitar", 1), ("chords", 0)]);
+        assert_eq!(bar.layers[1].slices.len(), 1);
+        assert!(matches!(bar.layers[1].slices[0].begin, SliceBegin::Index(2)));
+        assert!(matches!(bar.layers[1].slices[0].end, SliceEnd::Count(1)));
+    }
+
+    #[test]
+    fn test_voice_to_tokens() {
+        let layer = (|input: ParseStream| LayerDsl::parse(input)).parse_str("guitar Voice 2 [0 1]").unwrap();
+        let tokens = layer.to_token_stream();
+        assert!(syn::parse2::<syn::Expr>(tokens.clone()).is_ok());
+        assert_eq!(
+            tokens.to_string(),
+            quote! {
+                BarLayer::new_voice("guitar".into(), 2usize, vec![
+                    Slice::new(SliceBegin::Index(0usize), SliceEnd::Count(1usize), None)
+                ])
+            }
+            .to_string()
+        );
+        let main = (|input: ParseStream| LayerDsl::parse(input)).parse_str("guitar [0 1]").unwrap();
+        assert_eq!(main.voice, BarLayer::MAIN_VOICE);
+        assert!((|input: ParseStream| LayerDsl::parse(input)).parse_str("guitar Voice [0 1]").is_err());
     }
 }
@@ -6,6 +6,7 @@ use quote::{quote, ToTokens};
 use syn::parse::{Error, ParseStream};
 use syn::Ident;
 
+use crate::context::Context;
 use crate::proto::entry::EntryDsl;
 
 use super::id::IdDsl;
@@ -20,7 +21,8 @@ impl TrackDsl {
     #[throws(Error)]
     pub fn parse_without_brace(input: ParseStream) -> Self {
         let id = input.parse()?;
-        let kind = input.parse()?;
+        let kind: Ident = input.parse()?;
+        Context::set_track_kind(TrackKind::from_ident(kind.to_string().as_str()));
         let entries = EntryDsl::parse_vec(input)?;
         TrackDsl { id, kind, entries }
     }
@@ -30,6 +32,7 @@ impl ToTokens for TrackDsl {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let TrackDsl { id, kind, entries } = self;
         let kind_quote = kind.to_string();
+        Context::set_track_kind(TrackKind::from_ident(&kind_quote));
         let entries_quote = EntryDsl::quote_vec(entries);
         tokens.extend(quote! {
             Track::new(#id.into(), TrackKind::from_ident(#kind_quote), #entries_quote)
@@ -39,6 +42,7 @@ impl ToTokens for TrackDsl {
 
 impl TrackDsl {
     pub fn to_proto(&self) -> Track {
+        Context::set_track_kind(TrackKind::from_ident(self.kind.to_string().as_str()));
         let mut entries = Vec::new();
         for entry in self.entries.iter() {
             entry.add_proto(&mut entries);
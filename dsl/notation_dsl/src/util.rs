@@ -9,6 +9,7 @@ use notation_proto::prelude::ProtoEntry;
 use crate::core::chord::ChordDsl;
 use crate::core::tone::ToneDsl;
 use crate::core::word::WordDsl;
+use crate::drums::drum::DrumDsl;
 use crate::fretted::pick::PickDsl;
 use crate::proto::bar::BarDsl;
 use crate::proto::entry::{EntryDsl, MultibleDsl};
@@ -143,3 +144,4 @@ impl_multible_dsl!(ToneDsl);
 impl_multible_dsl!(ChordDsl);
 impl_multible_dsl!(WordDsl);
 impl_multible_dsl!(PickDsl);
+impl_multible_dsl!(DrumDsl);
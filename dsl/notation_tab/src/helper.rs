@@ -1,15 +1,13 @@
 use std::fs::File;
 use std::io::Write;
 
-use ron::ser::{to_string_pretty, PrettyConfig};
+use anyhow::Error;
 
 use notation_proto::prelude::*;
 
 pub fn convert_tab(tab: &Tab) -> String {
-    let pretty = PrettyConfig::new()
-        .separate_tuple_members(true)
-        .enumerate_arrays(true);
-    to_string_pretty(&tab, pretty).expect("Serialization failed")
+    let bytes = tab.encode(TabFormat::Ron).expect("Serialization failed");
+    String::from_utf8(bytes).expect("Serialization failed")
 }
 
 pub fn print_tab(tab: &Tab) {
@@ -17,9 +15,21 @@ pub fn print_tab(tab: &Tab) {
     println!("{}", s);
 }
 
-pub fn write_tab(tab: &Tab, path: &'static str) {
-    let s = convert_tab(tab);
-    let mut file = File::create(path).unwrap();
-    file.write_all(s.as_bytes()).unwrap();
-    println!("Tab written to: `{}` [{}] - {}", path, s.len(), tab);
+/// Write the tab in the format of the path's extension: `ron`, `json` or `ntab`.
+pub fn write_tab(tab: &Tab, path: &str) -> Result<(), Error> {
+    let format = TabFormat::from_path(path)?;
+    let bytes = tab.encode(format)?;
+    let mut file = File::create(path)?;
+    file.write_all(&bytes)?;
+    println!("Tab written to: `{}` [{}] - {}", path, bytes.len(), tab);
+    Ok(())
+}
+
+/// Write the JSON Schema of the `json` tab files.
+pub fn write_json_schema(path: &str) -> Result<(), Error> {
+    let schema = TabFormat::json_schema()?;
+    let mut file = File::create(path)?;
+    file.write_all(schema.as_bytes())?;
+    println!("Tab JSON Schema written to: `{}` [{}]", path, schema.len());
+    Ok(())
 }
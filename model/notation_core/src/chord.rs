@@ -4,9 +4,10 @@ use std::hash::Hash;
 use serde::{Deserialize, Serialize};
 
 use crate::interval::Interval;
-use crate::prelude::{Intervals, Semitones, Syllable};
+use crate::prelude::{ChordQuality, Intervals, Note, Semitones, Syllable};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Chord {
     pub root: Syllable,
     pub intervals: Intervals,
@@ -45,6 +46,64 @@ impl Chord {
         }
         None
     }
+    /// Chords made of the notes, best match first.
+    ///
+    /// Every note is tried as the root against the [`ChordQuality`] intervals, a missing
+    /// perfect fifth is allowed, and a bass other than the root gives a slash chord.
+    pub fn calc_candidates(notes: &[Note]) -> Vec<Chord> {
+        let pitch_class = |x: Syllable| Semitones::from(x).0.rem_euclid(12);
+        let mut syllables: Vec<Syllable> = Vec::new();
+        for note in notes.iter() {
+            if !syllables.iter().any(|x| pitch_class(*x) == pitch_class(note.syllable)) {
+                syllables.push(note.syllable);
+            }
+        }
+        let bass = match notes.iter().min_by_key(|x| Semitones::from(**x)) {
+            Some(note) => note.syllable,
+            None => return vec![],
+        };
+        if syllables.len() < 2 {
+            return vec![];
+        }
+        let mut candidates: Vec<(usize, Chord)> = Vec::new();
+        for root in syllables.iter() {
+            let mut others: Vec<i8> = syllables
+                .iter()
+                .map(|x| (pitch_class(*x) - pitch_class(*root)).rem_euclid(12))
+                .filter(|x| *x != 0)
+                .collect();
+            others.sort();
+            for quality in ChordQuality::ALL.iter() {
+                let intervals = quality.get_intervals();
+                let mut template: Vec<i8> = quality
+                    .get_semitones()
+                    .iter()
+                    .map(|x| x.0.rem_euclid(12))
+                    .collect();
+                template.sort();
+                template.dedup();
+                let mut score = if template == others {
+                    0
+                } else {
+                    let without_fifth: Vec<i8> = template.iter().filter(|x| **x != 7).copied().collect();
+                    if template.len() > without_fifth.len() && without_fifth == others {
+                        2
+                    } else {
+                        continue;
+                    }
+                };
+                let bass_interval = if pitch_class(bass) == pitch_class(*root) {
+                    None
+                } else {
+                    score += 1;
+                    intervals.iter().find(|x| x.is_matched(*root, bass)).copied()
+                };
+                candidates.push((score, Chord::new(*root, intervals.into(), bass_interval)));
+            }
+        }
+        candidates.sort_by_key(|x| x.0);
+        candidates.into_iter().map(|x| x.1).collect()
+    }
 }
 impl Hash for Chord {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
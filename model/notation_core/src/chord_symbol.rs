@@ -2,10 +2,13 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{Pitch, Syllable};
+use crate::prelude::{
+    Chord, Interval, Intervals, Key, Pitch, PitchName, PitchSign, Scale, Semitones, Syllable,
+};
 
 // https://hellomusictheory.com/learn/chord-inversions/
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ChordInversion {
     RootPosition,
     FirstInversion,
@@ -23,6 +26,7 @@ impl Display for ChordInversion {
 // https://www.earmaster.com/music-theory-online/ch05/chapter-5-4.html
 // https://en.wikipedia.org/wiki/Chord_names_and_symbols_(popular_music)
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ChordQuality {
     Major,
     Minor,
@@ -35,15 +39,212 @@ pub enum ChordQuality {
     Dominant7th,
     Diminished7th,
     HaldDiminished7th,
+    Major6th,
+    Minor6th,
+    SixNine,
+    Add9,
+    MinorAdd9,
+    Dominant9th,
+    Major9th,
+    Minor9th,
+    Dominant11th,
+    Minor11th,
+    Dominant13th,
+    Major13th,
+    Minor13th,
 }
-//Extended, Altered, Quartal and Quintal not supported
+//Altered, Quartal and Quintal not supported
 impl Display for ChordQuality {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
+impl ChordQuality {
+    pub const ALL: [Self; 24] = [
+        Self::Major,
+        Self::Minor,
+        Self::Diminished,
+        Self::Augmented,
+        Self::Suspended2th,
+        Self::Suspended4th,
+        Self::Major7th,
+        Self::Minor7th,
+        Self::Dominant7th,
+        Self::Diminished7th,
+        Self::HaldDiminished7th,
+        Self::Major6th,
+        Self::Minor6th,
+        Self::SixNine,
+        Self::Add9,
+        Self::MinorAdd9,
+        Self::Dominant9th,
+        Self::Major9th,
+        Self::Minor9th,
+        Self::Dominant11th,
+        Self::Minor11th,
+        Self::Dominant13th,
+        Self::Major13th,
+        Self::Minor13th,
+    ];
+    /// Intervals above the root, the root itself is not included.
+    pub fn get_intervals(&self) -> Vec<Interval> {
+        match self {
+            Self::Major => vec![Interval::Major3nd, Interval::Perfect5th],
+            Self::Minor => vec![Interval::Minor3nd, Interval::Perfect5th],
+            Self::Diminished => vec![Interval::Minor3nd, Interval::Diminished5th],
+            Self::Augmented => vec![Interval::Major3nd, Interval::Augmented5th],
+            Self::Suspended2th => vec![Interval::Major2nd, Interval::Perfect5th],
+            Self::Suspended4th => vec![Interval::Perfect4th, Interval::Perfect5th],
+            Self::Major7th => vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major7th],
+            Self::Minor7th => vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Minor7th],
+            Self::Dominant7th => vec![Interval::Major3nd, Interval::Perfect5th, Interval::Minor7th],
+            Self::Diminished7th => vec![
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Diminished7th,
+            ],
+            Self::HaldDiminished7th => vec![
+                Interval::Minor3nd,
+                Interval::Diminished5th,
+                Interval::Minor7th,
+            ],
+            Self::Major6th => vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major6th],
+            Self::Minor6th => vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Major6th],
+            Self::SixNine => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major6th,
+                Interval::Major9th,
+            ],
+            Self::Add9 => vec![Interval::Major3nd, Interval::Perfect5th, Interval::Major9th],
+            Self::MinorAdd9 => vec![Interval::Minor3nd, Interval::Perfect5th, Interval::Major9th],
+            Self::Dominant9th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+            Self::Major9th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+            ],
+            Self::Minor9th => vec![
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+            ],
+            Self::Dominant11th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+            ],
+            Self::Minor11th => vec![
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+            ],
+            Self::Dominant13th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+            Self::Major13th => vec![
+                Interval::Major3nd,
+                Interval::Perfect5th,
+                Interval::Major7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+            Self::Minor13th => vec![
+                Interval::Minor3nd,
+                Interval::Perfect5th,
+                Interval::Minor7th,
+                Interval::Major9th,
+                Interval::Perfect11th,
+                Interval::Major13th,
+            ],
+        }
+    }
+    /// Conventional suffix of the chord symbol, e.g. `m7b5`.
+    pub fn to_suffix(&self) -> String {
+        match self {
+            Self::Major => "",
+            Self::Minor => "m",
+            Self::Diminished => "dim",
+            Self::Augmented => "aug",
+            Self::Suspended2th => "sus2",
+            Self::Suspended4th => "sus4",
+            Self::Major7th => "maj7",
+            Self::Minor7th => "m7",
+            Self::Dominant7th => "7",
+            Self::Diminished7th => "dim7",
+            Self::HaldDiminished7th => "m7b5",
+            Self::Major6th => "6",
+            Self::Minor6th => "m6",
+            Self::SixNine => "6/9",
+            Self::Add9 => "add9",
+            Self::MinorAdd9 => "madd9",
+            Self::Dominant9th => "9",
+            Self::Major9th => "maj9",
+            Self::Minor9th => "m9",
+            Self::Dominant11th => "11",
+            Self::Minor11th => "m11",
+            Self::Dominant13th => "13",
+            Self::Major13th => "maj13",
+            Self::Minor13th => "m13",
+        }
+        .to_owned()
+    }
+    /// Other suffixes accepted when parsing, e.g. `min` and `-` for `m`.
+    pub fn get_aliases(&self) -> Vec<&'static str> {
+        match self {
+            Self::Major => vec!["M", "maj"],
+            Self::Minor => vec!["min", "-"],
+            Self::Diminished => vec!["o"],
+            Self::Augmented => vec!["+"],
+            Self::Suspended4th => vec!["sus"],
+            Self::Major7th => vec!["M7", "Δ"],
+            Self::Minor7th => vec!["-7", "min7"],
+            Self::Diminished7th => vec!["o7"],
+            Self::HaldDiminished7th => vec!["ø"],
+            Self::SixNine => vec!["69"],
+            Self::Add9 => vec!["add2"],
+            _ => vec![],
+        }
+    }
+    pub fn from_suffix(suffix: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|x| x.to_suffix() == suffix || x.get_aliases().contains(&suffix))
+    }
+    pub fn get_semitones(&self) -> Vec<Semitones> {
+        self.get_intervals().into_iter().map(Semitones::from).collect()
+    }
+    /// Match by semitones, so `Tritone` and `Diminished5th` are treated the same.
+    pub fn from_intervals(intervals: &Intervals) -> Option<Self> {
+        let mut semitones: Vec<Semitones> = intervals
+            .get_intervals()
+            .into_iter()
+            .map(Semitones::from)
+            .collect();
+        semitones.sort();
+        Self::ALL.into_iter().find(|x| x.get_semitones() == semitones)
+    }
+}
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChordSymbol {
     pub pitch: Pitch,
     pub quality: ChordQuality,
@@ -70,4 +271,193 @@ impl ChordSymbol {
             syllable,
         }
     }
+    /// Symbol of the chord in the key, `None` if the intervals don't match any quality.
+    pub fn from_chord(scale: &Scale, key: &Key, chord: &Chord) -> Option<Self> {
+        let quality = ChordQuality::from_intervals(&chord.intervals)?;
+        let pitch = scale.calc_pitch(key, &chord.root);
+        let inversion = match chord.bass {
+            None => ChordInversion::RootPosition,
+            Some(bass) => {
+                let bass_semitones = Semitones::from(bass);
+                match quality
+                    .get_semitones()
+                    .iter()
+                    .position(|x| *x == bass_semitones)
+                {
+                    Some(0) => ChordInversion::FirstInversion,
+                    Some(1) => ChordInversion::SecondInversion,
+                    Some(2) => ChordInversion::ThirdInversion,
+                    _ => ChordInversion::SlashBass(
+                        scale.calc_pitch(key, &bass.syllable_on_root(&chord.root)),
+                    ),
+                }
+            }
+        };
+        Some(Self::new(pitch, quality, inversion, Some(chord.root)))
+    }
+    pub fn get_bass_pitch(&self) -> Option<Pitch> {
+        let intervals = self.quality.get_intervals();
+        let interval = match self.inversion {
+            ChordInversion::RootPosition => return None,
+            ChordInversion::FirstInversion => intervals.first(),
+            ChordInversion::SecondInversion => intervals.get(1),
+            ChordInversion::ThirdInversion => intervals.get(2),
+            ChordInversion::SlashBass(pitch) => return Some(pitch),
+        };
+        interval.map(|x| Self::calc_interval_pitch(self.pitch, *x))
+    }
+    pub fn to_chord(&self, scale: &Scale, key: &Key) -> Chord {
+        let root = self
+            .syllable
+            .unwrap_or_else(|| scale.calc_syllable(key, &self.pitch));
+        let bass = self
+            .get_bass_pitch()
+            .map(|x| Self::calc_bass_interval(&self.quality.get_intervals(), root, scale.calc_syllable(key, &x)));
+        Chord::new(root, self.quality.get_intervals().into(), bass)
+    }
+    pub fn to_text(&self) -> String {
+        let bass = self
+            .get_bass_pitch()
+            .map(|x| format!("/{}", x))
+            .unwrap_or_default();
+        format!("{}{}{}", self.pitch, self.quality.to_suffix(), bass)
+    }
+}
+
+impl ChordSymbol {
+    fn calc_bass_interval(intervals: &[Interval], root: Syllable, bass: Syllable) -> Interval {
+        intervals
+            .iter()
+            .find(|x| x.is_matched(root, bass))
+            .copied()
+            .unwrap_or_else(|| Interval::from((root, bass)))
+    }
+    /// Pitch of the interval above the root, spelled by the letter steps of the interval.
+    fn calc_interval_pitch(root: Pitch, interval: Interval) -> Pitch {
+        const NAMES: [PitchName; 7] = [
+            PitchName::C,
+            PitchName::D,
+            PitchName::E,
+            PitchName::F,
+            PitchName::G,
+            PitchName::A,
+            PitchName::B,
+        ];
+        let (degree, _) = Syllable::from(interval).to_degree();
+        let index = NAMES.iter().position(|x| *x == root.name).unwrap_or(0);
+        let name = NAMES[(index + degree as usize) % NAMES.len()];
+        let semitones = Semitones::from(root) + Semitones::from(interval) - Semitones::from(name);
+        let sign = (semitones.0 + 6).rem_euclid(12) - 6;
+        Pitch::new(name, PitchSign::from(Semitones(sign)))
+    }
+    /// Parse the leading pitch of the text, e.g. `Bb` of `Bbmaj7`, returns the rest.
+    fn parse_pitch(text: &str) -> Option<(Pitch, &str)> {
+        let letter = text.chars().next()?;
+        if !('A'..='G').contains(&letter) {
+            return None;
+        }
+        let rest = &text[1..];
+        for sign in ["##", "bb", "#", "b", ""] {
+            if let Some(after_sign) = rest.strip_prefix(sign) {
+                let pitch = Pitch::from_text(&text[..1 + sign.len()]);
+                return Some((pitch, after_sign));
+            }
+        }
+        None
+    }
+    /// Parse conventional symbols like `Am7/G`, `Dsus4`, `Bbmaj7`, `F#m7b5`, `C6/9`,
+    /// the suffixes are matched against [`ChordQuality::to_suffix()`] and its aliases.
+    ///
+    /// A bass other than the root is kept as [`ChordInversion::SlashBass`].
+    pub fn from_text(text: &str) -> Option<Self> {
+        let text = text.trim().replace('♯', "#").replace('♭', "b");
+        let (pitch, rest) = Self::parse_pitch(&text)?;
+        let (suffix, bass) = match rest.rfind('/') {
+            Some(index) if Self::parse_pitch(&rest[index + 1..]).is_some() => {
+                let (bass, bass_rest) = Self::parse_pitch(&rest[index + 1..])?;
+                if !bass_rest.is_empty() {
+                    return None;
+                }
+                (&rest[..index], Some(bass))
+            }
+            _ => (rest, None),
+        };
+        let quality = ChordQuality::from_suffix(suffix)?;
+        let inversion = match bass {
+            Some(bass) if Semitones::from(bass).0.rem_euclid(12) != Semitones::from(pitch).0.rem_euclid(12) => {
+                ChordInversion::SlashBass(bass)
+            }
+            _ => ChordInversion::RootPosition,
+        };
+        Some(Self::new(pitch, quality, inversion, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(text: &str) -> Option<String> {
+        ChordSymbol::from_text(text).map(|x| x.to_text())
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        for text in ["C", "Am7/G", "Dsus4", "Bbmaj7", "F#m7b5", "C6/9", "Ebdim7", "G7/B", "Dbadd9"] {
+            assert_eq!(render(text).as_deref(), Some(text));
+        }
+    }
+
+    #[test]
+    fn test_aliases() {
+        assert_eq!(render("Amin7").as_deref(), Some("Am7"));
+        assert_eq!(render("CM7").as_deref(), Some("Cmaj7"));
+        assert_eq!(render("F#-").as_deref(), Some("F#m"));
+        assert_eq!(render("G69").as_deref(), Some("G6/9"));
+        assert_eq!(render("D♭ø").as_deref(), Some("Dbm7b5"));
+        assert_eq!(render("C/C").as_deref(), Some("C"));
+    }
+
+    #[test]
+    fn test_invalid_text() {
+        for text in ["", "H7", "Cxyz", "Am7/G#x", "am7"] {
+            assert_eq!(ChordSymbol::from_text(text), None);
+        }
+    }
+
+    #[test]
+    fn test_chord_round_trip() {
+        let (scale, key) = (Scale::Major, Key::C);
+        for quality in ChordQuality::ALL {
+            for root in [Syllable::Do, Syllable::Re, Syllable::Fa, Syllable::La] {
+                let chord = Chord::new(root, quality.get_intervals().into(), None);
+                let text = ChordSymbol::from_chord(&scale, &key, &chord).unwrap().to_text();
+                let parsed = ChordSymbol::from_text(&text).unwrap().to_chord(&scale, &key);
+                assert_eq!(parsed, chord, "{}", text);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inversions() {
+        let (scale, key) = (Scale::Major, Key::C);
+        let c = ChordQuality::Major.get_intervals();
+        let first = Chord::new(Syllable::Do, c.clone().into(), Some(Interval::Major3nd));
+        let symbol = ChordSymbol::from_chord(&scale, &key, &first).unwrap();
+        assert_eq!(symbol.inversion, ChordInversion::FirstInversion);
+        assert_eq!(symbol.to_text(), "C/E");
+        let c7 = ChordQuality::Dominant7th.get_intervals();
+        let third = Chord::new(Syllable::Do, c7.into(), Some(Interval::Minor7th));
+        assert_eq!(ChordSymbol::from_chord(&scale, &key, &third).unwrap().to_text(), "C7/Bb");
+        for text in ["C/E", "C7/Bb", "Am/G"] {
+            let chord = ChordSymbol::from_text(text).unwrap().to_chord(&scale, &key);
+            assert_eq!(ChordSymbol::from_chord(&scale, &key, &chord).unwrap().to_text(), text);
+        }
+    }
+
+    #[test]
+    fn test_unknown_intervals() {
+        let chord = Chord::new(Syllable::Do, vec![Interval::Minor2nd].into(), None);
+        assert_eq!(ChordSymbol::from_chord(&Scale::Major, &Key::C, &chord), None);
+    }
 }
@@ -2,14 +2,17 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use crate::prelude::{Chord, Duration, Entry, EntryPassMode, Tone};
+use crate::prelude::{Chord, Duration, Entry, EntryPassMode, Ornament, Tone};
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum CoreEntry {
     Tie,
     Rest(Duration),
     Tone(Tone, Duration),
     Chord(Chord, Duration),
+    /// Attached to the following tone or pick, takes no time in layout.
+    Ornament(Ornament),
 }
 impl Display for CoreEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -18,6 +21,7 @@ impl Display for CoreEntry {
             CoreEntry::Rest(duration) => write!(f, "Rest({})", duration),
             CoreEntry::Tone(tone, duration) => write!(f, "Tone({}, {})", tone, duration),
             CoreEntry::Chord(chord, duration) => write!(f, "Chord({}, {})", chord, duration),
+            CoreEntry::Ornament(ornament) => write!(f, "Ornament({})", ornament),
         }
     }
 }
@@ -28,6 +32,7 @@ impl CoreEntry {
             CoreEntry::Rest(duration) => *duration,
             CoreEntry::Tone(_, duration) => *duration,
             CoreEntry::Chord(_, duration) => *duration,
+            CoreEntry::Ornament(_) => Duration::Zero,
         }
     }
     pub fn pass_mode(&self) -> EntryPassMode {
@@ -68,6 +73,11 @@ impl CoreEntry {
     pub fn is_chord(&self) -> bool {
         matches!(self, Self::Chord(..))
     }
+
+    /// Returns `true` if the entry is [`Ornament`].
+    pub fn is_ornament(&self) -> bool {
+        matches!(self, Self::Ornament(..))
+    }
 }
 
 impl CoreEntry {
@@ -94,6 +104,14 @@ impl CoreEntry {
             None
         }
     }
+
+    pub fn as_ornament(&self) -> Option<&Ornament> {
+        if let Self::Ornament(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 
 impl From<()> for CoreEntry {
@@ -119,3 +137,9 @@ impl From<(Chord, Duration)> for CoreEntry {
         Self::Chord(v.0, v.1)
     }
 }
+
+impl From<Ornament> for CoreEntry {
+    fn from(v: Ornament) -> Self {
+        Self::Ornament(v)
+    }
+}
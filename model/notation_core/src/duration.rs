@@ -1,25 +1,22 @@
 use std::cmp::Ordering;
 use std::fmt::Display;
-use std::ops::{Add, Sub};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Sub};
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Unit {
     Whole,
     Half,
+    #[default]
     Quarter,
     Eighth,
     Sixteenth,
     ThirtySecondth,
 }
 
-impl Default for Unit {
-    fn default() -> Self {
-        Self::Quarter
-    }
-}
-
 impl Display for Unit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", match self {
@@ -79,26 +76,93 @@ impl Unit {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Debug, Default)]
-pub struct Units(pub f32);
+/// Exact position or length in whole notes, kept as a reduced fraction so
+/// tuplets add up without drifting, floats are only used for drawing and playback.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Units {
+    numer: i64,
+    denom: i64,
+}
+
+const fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+    a
+}
 
 impl Units {
-    pub const _MIN_ACCURACY: f32 = 0.00001;
-    pub const MIN_ACCURACY: Units = Units(Self::_MIN_ACCURACY);
-    /*
-     * This is quite messy here, the pasition passed checking needs some cleanup,
-     * mainly for control the guitar view timing, and seeking next note
-     */
-    pub const HALF_MIN_ACCURACY: Units = Units(Self::_MIN_ACCURACY * 0.5);
+    pub const ZERO: Units = Units::new(0, 1);
+    /// Grid used when converting from floats, divisible by all note units and tuplets.
+    pub const RESOLUTION: i64 = 1_209_600;
 
-    pub fn is_bigger_than(&self, other: &Units) -> bool {
-        self.0 > other.0 + Self::_MIN_ACCURACY
+    pub const fn new(numer: i64, denom: i64) -> Self {
+        assert!(denom != 0, "Units with zero denominator");
+        let sign = if denom < 0 { -1 } else { 1 };
+        let div = gcd(numer, denom);
+        let div = if div == 0 { 1 } else { div };
+        Self {
+            numer: sign * numer / div,
+            denom: sign * denom / div,
+        }
+    }
+    /// Reduces the wide intermediate result of an operation before narrowing it back.
+    fn from_i128(numer: i128, denom: i128) -> Self {
+        assert!(denom != 0, "Units with zero denominator");
+        let (mut a, mut b) = (numer.abs(), denom.abs());
+        while b != 0 {
+            let t = a % b;
+            a = b;
+            b = t;
+        }
+        let div = a.max(1) * denom.signum();
+        Self {
+            numer: i64::try_from(numer / div).expect("Units numerator overflow"),
+            denom: i64::try_from(denom / div).expect("Units denominator overflow"),
+        }
+    }
+    fn mul_ratio(self, numer: i128, denom: i128) -> Self {
+        Self::from_i128(self.numer as i128 * numer, self.denom as i128 * denom)
+    }
+    pub fn numer(&self) -> i64 {
+        self.numer
+    }
+    pub fn denom(&self) -> i64 {
+        self.denom
+    }
+    pub fn is_zero(&self) -> bool {
+        self.numer == 0
+    }
+    /// Nearest value on the [`RESOLUTION`](Self::RESOLUTION) grid, e.g. for playback time.
+    pub fn from_f32(v: f32) -> Self {
+        let numer = (v as f64 * Self::RESOLUTION as f64).round() as i64;
+        Self::new(numer, Self::RESOLUTION)
+    }
+    pub fn to_f32(&self) -> f32 {
+        (self.numer as f64 / self.denom as f64) as f32
+    }
+    /// How many whole `other` fit in this, rounded down.
+    pub fn div_floor(&self, other: &Units) -> i64 {
+        let numer = self.numer as i128 * other.denom as i128;
+        let denom = self.denom as i128 * other.numer as i128;
+        let (numer, denom) = if denom < 0 { (-numer, -denom) } else { (numer, denom) };
+        numer.div_euclid(denom) as i64
+    }
+}
+
+impl Default for Units {
+    fn default() -> Self {
+        Self::ZERO
     }
 }
 
 impl Display for Units {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<Units>({})", self.0)
+        write!(f, "<Units>({}/{})", self.numer, self.denom)
     }
 }
 
@@ -106,7 +170,10 @@ impl Add for Units {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        Units(self.0 + rhs.0)
+        Units::from_i128(
+            self.numer as i128 * rhs.denom as i128 + rhs.numer as i128 * self.denom as i128,
+            self.denom as i128 * rhs.denom as i128,
+        )
     }
 }
 
@@ -114,24 +181,66 @@ impl Sub for Units {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Units(self.0 - rhs.0)
+        Units::from_i128(
+            self.numer as i128 * rhs.denom as i128 - rhs.numer as i128 * self.denom as i128,
+            self.denom as i128 * rhs.denom as i128,
+        )
     }
 }
 
-impl Units {
-    pub fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.0 == other.0 {
-            Ordering::Equal
-        } else if self.0 < other.0 {
-            Ordering::Less
-        } else {
-            Ordering::Greater
-        }
+impl Mul<i64> for Units {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        self.mul_ratio(rhs as i128, 1)
+    }
+}
+
+impl Div<i64> for Units {
+    type Output = Self;
+
+    fn div(self, rhs: i64) -> Self::Output {
+        self.mul_ratio(1, rhs as i128)
+    }
+}
+
+impl Div for Units {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.mul_ratio(rhs.denom as i128, rhs.numer as i128)
+    }
+}
+
+impl AddAssign for Units {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sum for Units {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Units::ZERO, |a, b| a + b)
+    }
+}
+
+impl Ord for Units {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.numer as i128 * other.denom as i128;
+        let b = other.numer as i128 * self.denom as i128;
+        a.cmp(&b)
+    }
+}
+
+impl PartialOrd for Units {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
 // https://hellomusictheory.com/learn/tuplets/
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Duration {
     Zero,
     Simple(Unit),
@@ -250,35 +359,71 @@ impl From<Unit> for Duration {
     }
 }
 
-impl From<f32> for Units {
-    fn from(v: f32) -> Self {
-        Self(v)
-    }
-}
-
 impl From<Unit> for Units {
     fn from(v: Unit) -> Self {
-        match v {
-            Unit::Whole => 1.0,
-            Unit::Half => 1.0 / 2.0,
-            Unit::Quarter => 1.0 / 4.0,
-            Unit::Eighth => 1.0 / 8.0,
-            Unit::Sixteenth => 1.0 / 16.0,
-            Unit::ThirtySecondth => 1.0 / 32.0,
-        }
-        .into()
+        let denom = match v {
+            Unit::Whole => 1,
+            Unit::Half => 2,
+            Unit::Quarter => 4,
+            Unit::Eighth => 8,
+            Unit::Sixteenth => 16,
+            Unit::ThirtySecondth => 32,
+        };
+        Units::new(1, denom)
     }
 }
 
 impl From<Duration> for Units {
     fn from(v: Duration) -> Self {
         match v {
-            Duration::Zero => 0.0,
-            Duration::Simple(v) => Units::from(v).0,
-            Duration::Dotted(v) => Units::from(v).0 * 1.5,
-            Duration::Triplet(v) => Units::from(v).0 * 2.0 / 3.0,
-            Duration::DottedTriplet(v) => Units::from(v).0 * 4.0 / 3.0,
+            Duration::Zero => Units::ZERO,
+            Duration::Simple(v) => Units::from(v),
+            Duration::Dotted(v) => Units::from(v) * 3 / 2,
+            Duration::Triplet(v) => Units::from(v) * 2 / 3,
+            Duration::DottedTriplet(v) => Units::from(v) * 4 / 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Signature;
+
+    #[test]
+    fn test_triplet_sum() {
+        let bar_units = Units::from(Signature::_4_4);
+        let triplets: Units = (0..12).map(|_| Units::from(Duration::T_1_8)).sum();
+        assert_eq!(triplets, bar_units);
+        let mixed: Units = [Duration::T_1_4, Duration::T_1_4, Duration::T_1_4, Duration::_1_2]
+            .into_iter()
+            .map(Units::from)
+            .sum();
+        assert_eq!(mixed, bar_units);
+        assert_eq!(Units::from(Duration::DottedTriplet(Unit::Quarter)) * 3, bar_units);
+    }
+
+    #[test]
+    fn test_quintuplet_sum() {
+        let bar_units = Units::from(Signature::_4_4);
+        let quintuplet = Units::from(Unit::Quarter) / 5;
+        let sum: Units = (0..20).map(|_| quintuplet).sum();
+        assert_eq!(sum, bar_units);
+        let mut pos = Units::ZERO;
+        for _ in 0..19 {
+            pos += quintuplet;
         }
-        .into()
+        assert!(bar_units > pos);
+        assert_eq!(pos + quintuplet, bar_units);
+    }
+
+    #[test]
+    fn test_large_denominators() {
+        let a = Units::new(1, 3_000_000_007);
+        let b = Units::new(1, 3_000_000_019);
+        let sum = a + b;
+        assert_eq!(sum - b, a);
+        assert_eq!((a * 3_000_000_007) / b, Units::new(3_000_000_019, 1));
+        assert_eq!(a / 2 * 2, a);
     }
 }
@@ -1,6 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::prelude::{Duration, Units};
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum EntryPassMode {
     Immediate,
     Delayed,
@@ -5,6 +5,7 @@ use crate::prelude::{PitchName, Semitones, Syllable};
 
 // https://hellomusictheory.com/learn/music-scales-beginners-guide/
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Key {
     Natural(PitchName),
     Sharp(PitchName),
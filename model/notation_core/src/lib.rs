@@ -9,10 +9,13 @@ pub mod key;
 pub mod meta_entry;
 pub mod note;
 pub mod octave;
+pub mod ornament;
 pub mod pitch;
+pub mod roman_numeral;
 pub mod semitones;
 pub mod scale;
 pub mod signature;
+pub mod spelling;
 pub mod syllable;
 pub mod tempo;
 pub mod tone;
@@ -41,8 +44,12 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::octave::Octave;
     #[doc(hidden)]
+    pub use crate::ornament::{Ornament, OrnamentStep};
+    #[doc(hidden)]
     pub use crate::pitch::{Pitch, PitchName, PitchSign};
     #[doc(hidden)]
+    pub use crate::roman_numeral::{CadenceKind, HarmonicFunction, RomanNumeral, ScaleDegree};
+    #[doc(hidden)]
     pub use crate::semitones::Semitones;
     #[doc(hidden)]
     pub use crate::scale::Scale;
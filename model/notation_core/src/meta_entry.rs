@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::{Entry, Key, Scale, Signature, Tempo};
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum MetaEntry {
     Signature(Signature),
     Tempo(Tempo),
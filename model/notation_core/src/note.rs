@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::{Octave, Pitch, Syllable, Semitones};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Note {
     pub octave: Octave,
     pub pitch: Pitch,
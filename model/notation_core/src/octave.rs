@@ -6,6 +6,7 @@ use crate::prelude::Semitones;
 
 // https://en.wikipedia.org/wiki/Scientific_pitch_notation
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Octave {
     N1,
     P0,
@@ -0,0 +1,220 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Semitones, Unit, Units};
+
+/// Decoration of the following tone or pick, the intervals are in semitones
+/// from the decorated note, positive for upper neighbors and negative for lower ones.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Ornament {
+    /// Short note just before the main one.
+    Grace(Semitones),
+    /// Two short notes just before the main one.
+    DoubleGrace(Semitones, Semitones),
+    /// Main note, its neighbor, then back to the main note.
+    Mordent(Semitones),
+    /// Main note alternating with its neighbor for the whole duration.
+    Trill(Semitones),
+}
+
+/// One note played for an ornament, the position is from the start of the main note.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct OrnamentStep {
+    pub offset: Semitones,
+    pub pos: Units,
+    pub units: Units,
+}
+
+impl Display for Ornament {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ornament::Grace(a) => write!(f, "Grace({})", a),
+            Ornament::DoubleGrace(a, b) => write!(f, "DoubleGrace({}, {})", a, b),
+            Ornament::Mordent(a) => write!(f, "Mordent({})", a),
+            Ornament::Trill(a) => write!(f, "Trill({})", a),
+        }
+    }
+}
+
+impl OrnamentStep {
+    pub fn new(offset: Semitones, pos: Units, units: Units) -> Self {
+        Self { offset, pos, units }
+    }
+}
+
+impl Ornament {
+    /// Length of each ornament note in playback.
+    pub const STEP: Unit = Unit::ThirtySecondth;
+
+    /// Returns `true` if the ornament notes are drawn before the main note.
+    pub fn is_grace(&self) -> bool {
+        matches!(self, Self::Grace(..) | Self::DoubleGrace(..))
+    }
+    /// Neighbor notes to draw, in playing order.
+    pub fn get_offsets(&self) -> Vec<Semitones> {
+        match self {
+            Ornament::Grace(a) => vec![*a],
+            Ornament::DoubleGrace(a, b) => vec![*a, *b],
+            Ornament::Mordent(a) => vec![*a],
+            Ornament::Trill(a) => vec![*a, *a],
+        }
+    }
+    /// Splits a main note of `units` into the notes to play, the ornament notes
+    /// steal their time from the start of the main note, which always comes last.
+    pub fn calc_steps(&self, units: Units) -> Vec<OrnamentStep> {
+        let main = Semitones(0);
+        let sequence = match self {
+            Ornament::Grace(a) => vec![*a],
+            Ornament::DoubleGrace(a, b) => vec![*a, *b],
+            Ornament::Mordent(a) => vec![main, *a],
+            Ornament::Trill(a) => {
+                let count = units.div_floor(&Units::from(Self::STEP)).max(0) as usize;
+                let pairs = (count.max(3) - 1) / 2;
+                (0..pairs * 2)
+                    .map(|i| if i % 2 == 0 { main } else { *a })
+                    .collect()
+            }
+        };
+        let step = Units::from(Self::STEP).min(units / (sequence.len() + 1) as i64);
+        let mut steps: Vec<OrnamentStep> = sequence
+            .iter()
+            .enumerate()
+            .map(|(i, offset)| OrnamentStep::new(*offset, step * i as i64, step))
+            .collect();
+        let main_pos = step * sequence.len() as i64;
+        steps.push(OrnamentStep::new(main, main_pos, units - main_pos));
+        steps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quarter() -> Units {
+        Units::from(Unit::Quarter)
+    }
+
+    fn sixteenth() -> Units {
+        Units::from(Unit::Sixteenth)
+    }
+
+    fn step() -> Units {
+        Units::from(Ornament::STEP)
+    }
+
+    fn steps(ornament: Ornament, units: Units) -> Vec<(i8, Units, Units)> {
+        ornament
+            .calc_steps(units)
+            .iter()
+            .map(|x| (x.offset.0, x.pos, x.units))
+            .collect()
+    }
+
+    /// Steps fill the main note without gaps, and the main note comes last.
+    fn assert_fills(ornament: Ornament, units: Units) {
+        let steps = ornament.calc_steps(units);
+        let mut pos = Units::ZERO;
+        for x in steps.iter() {
+            assert_eq!(x.pos, pos);
+            assert!(x.units >= Units::ZERO);
+            pos += x.units;
+        }
+        assert_eq!(pos, units);
+        assert_eq!(steps.last().unwrap().offset, Semitones(0));
+    }
+
+    #[test]
+    fn test_grace() {
+        let q = quarter();
+        assert_eq!(
+            steps(Ornament::Grace(Semitones(2)), q),
+            vec![(2, Units::ZERO, step()), (0, step(), q - step())]
+        );
+        let s = sixteenth();
+        assert_eq!(
+            steps(Ornament::Grace(Semitones(-1)), s),
+            vec![(-1, Units::ZERO, step()), (0, step(), s - step())]
+        );
+        assert_fills(Ornament::Grace(Semitones(2)), q);
+    }
+
+    #[test]
+    fn test_double_grace() {
+        let q = quarter();
+        assert_eq!(
+            steps(Ornament::DoubleGrace(Semitones(-1), Semitones(2)), q),
+            vec![
+                (-1, Units::ZERO, step()),
+                (2, step(), step()),
+                (0, step() * 2, q - step() * 2),
+            ]
+        );
+        // Three notes don't fit a sixteenth with thirty-second steps, so they share it evenly.
+        let s = sixteenth();
+        let third = s / 3;
+        assert!(third < step());
+        assert_eq!(
+            steps(Ornament::DoubleGrace(Semitones(-1), Semitones(2)), s),
+            vec![(-1, Units::ZERO, third), (2, third, third), (0, third * 2, s - third * 2)]
+        );
+        assert_fills(Ornament::DoubleGrace(Semitones(-1), Semitones(2)), s);
+    }
+
+    #[test]
+    fn test_mordent() {
+        let q = quarter();
+        assert_eq!(
+            steps(Ornament::Mordent(Semitones(-2)), q),
+            vec![
+                (0, Units::ZERO, step()),
+                (-2, step(), step()),
+                (0, step() * 2, q - step() * 2),
+            ]
+        );
+        let s = sixteenth();
+        let third = s / 3;
+        assert_eq!(
+            steps(Ornament::Mordent(Semitones(-2)), s),
+            vec![(0, Units::ZERO, third), (-2, third, third), (0, third * 2, s - third * 2)]
+        );
+    }
+
+    #[test]
+    fn test_trill() {
+        // Eight thirty-seconds in a quarter, three pairs leave two steps for the main note.
+        let q = quarter();
+        let trill = steps(Ornament::Trill(Semitones(2)), q);
+        let offsets: Vec<i8> = trill.iter().map(|x| x.0).collect();
+        assert_eq!(offsets, vec![0, 2, 0, 2, 0, 2, 0]);
+        assert!(trill[..6].iter().all(|x| x.2 == step()));
+        assert_eq!(trill[6], (0, step() * 6, step() * 2));
+        assert_fills(Ornament::Trill(Semitones(2)), q);
+        // At least one pair, clamped to share a sixteenth evenly.
+        let s = sixteenth();
+        let third = s / 3;
+        assert_eq!(
+            steps(Ornament::Trill(Semitones(1)), s),
+            vec![(0, Units::ZERO, third), (1, third, third), (0, third * 2, s - third * 2)]
+        );
+        assert_fills(Ornament::Trill(Semitones(1)), s);
+    }
+
+    #[test]
+    fn test_zero_length_main_note() {
+        let ornaments = vec![
+            Ornament::Grace(Semitones(2)),
+            Ornament::DoubleGrace(Semitones(-1), Semitones(2)),
+            Ornament::Mordent(Semitones(-2)),
+            Ornament::Trill(Semitones(2)),
+        ];
+        for ornament in ornaments {
+            let steps = ornament.calc_steps(Units::ZERO);
+            assert!(steps.len() >= 2);
+            assert!(steps.iter().all(|x| x.pos == Units::ZERO && x.units == Units::ZERO));
+            assert_fills(ornament, Units::ZERO);
+        }
+    }
+}
@@ -7,6 +7,7 @@ use crate::prelude::{Key, Semitones};
 // https://hellomusictheory.com/learn/
 // http://openmusictheory.com/pitches.html
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum PitchName {
     C,
     D,
@@ -24,6 +25,7 @@ impl Display for PitchName {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum PitchSign {
     DoubleFlat,
     Flat,
@@ -52,6 +54,7 @@ impl Display for PitchSign {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Pitch {
     pub name: PitchName,
     pub sign: PitchSign,
@@ -0,0 +1,360 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+use crate::prelude::{Chord, ChordQuality, Interval, Key, PitchSign, Scale, Semitones, Syllable};
+
+// https://en.wikipedia.org/wiki/Roman_numeral_analysis
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum HarmonicFunction {
+    Tonic,
+    Subdominant,
+    Dominant,
+    Chromatic,
+}
+impl Display for HarmonicFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl HarmonicFunction {
+    pub fn to_text(&self) -> String {
+        match self {
+            HarmonicFunction::Tonic => "T",
+            HarmonicFunction::Subdominant => "S",
+            HarmonicFunction::Dominant => "D",
+            HarmonicFunction::Chromatic => "",
+        }
+        .to_owned()
+    }
+}
+
+// https://en.wikipedia.org/wiki/Cadence
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CadenceKind {
+    Authentic,
+    Plagal,
+    Half,
+    Deceptive,
+}
+impl Display for CadenceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Degree of a chord root counted from the tonic, 1 to 7, with the accidental
+/// against the scale, e.g. bVII in major.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ScaleDegree {
+    pub degree: u8,
+    pub sign: PitchSign,
+}
+impl Display for ScaleDegree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.sign, self.degree)
+    }
+}
+impl ScaleDegree {
+    pub fn new(degree: u8, sign: PitchSign) -> Self {
+        Self { degree, sign }
+    }
+    pub fn is_diatonic(&self) -> bool {
+        self.sign == PitchSign::Natural
+    }
+    pub fn to_numeral(&self, lower: bool) -> String {
+        let numeral = match self.degree {
+            1 => "I",
+            2 => "II",
+            3 => "III",
+            4 => "IV",
+            5 => "V",
+            6 => "VI",
+            _ => "VII",
+        };
+        let sign = match self.sign {
+            PitchSign::Natural => "".to_owned(),
+            _ => self.sign.to_string(),
+        };
+        if lower {
+            format!("{}{}", sign, numeral.to_lowercase())
+        } else {
+            format!("{}{}", sign, numeral)
+        }
+    }
+    /// Syllables for degrees 1 to 7, scales without 7 notes use major or minor.
+    fn get_scale_syllables(scale: &Scale) -> Vec<Syllable> {
+        let syllables = scale.get_syllables();
+        if syllables.len() == 7 {
+            syllables
+        } else if scale.calc_root_syllable() == Syllable::La {
+            Scale::Minor.get_syllables()
+        } else {
+            Scale::Major.get_syllables()
+        }
+    }
+    pub fn from_syllable(scale: &Scale, key: &Key, syllable: &Syllable) -> Self {
+        let syllables = Self::get_scale_syllables(scale);
+        let mut spelled = scale.calc_spelled_syllable(key, syllable);
+        // Borrowed roots are usually lowered, e.g. bII rather than #I, except #IV.
+        if spelled.to_degree().1 == PitchSign::Sharp
+            && spelled != Syllable::Fi
+            && !syllables.contains(&spelled)
+        {
+            spelled = spelled.to_enharmonic();
+        }
+        let (tonic_degree, _) = syllables[0].to_degree();
+        let (root_degree, _) = spelled.to_degree();
+        let index = (root_degree - tonic_degree).rem_euclid(7) as usize;
+        let expected = syllables
+            .iter()
+            .find(|x| (x.to_degree().0 - tonic_degree).rem_euclid(7) as usize == index)
+            .copied()
+            .unwrap_or(syllables[index]);
+        let mut offset = (Semitones::from(*syllable) - Semitones::from(expected)).0.rem_euclid(12);
+        if offset > 6 {
+            offset -= 12;
+        }
+        let sign = if offset.abs() > 2 {
+            PitchSign::Natural
+        } else {
+            PitchSign::from(Semitones(offset))
+        };
+        Self::new(index as u8 + 1, sign)
+    }
+    /// Whether the diatonic triad on the degree has a minor third.
+    pub fn is_minor_triad(&self, scale: &Scale) -> bool {
+        let syllables = Self::get_scale_syllables(scale);
+        let index = self.degree as usize - 1;
+        let root = Semitones::from(syllables[index]);
+        let third = Semitones::from(syllables[(index + 2) % syllables.len()]);
+        (third - root).0.rem_euclid(12) == 3
+    }
+    pub fn calc_function(&self) -> HarmonicFunction {
+        if !self.is_diatonic() {
+            return HarmonicFunction::Chromatic;
+        }
+        match self.degree {
+            1 | 3 | 6 => HarmonicFunction::Tonic,
+            2 | 4 => HarmonicFunction::Subdominant,
+            _ => HarmonicFunction::Dominant,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RomanNumeral {
+    pub degree: ScaleDegree,
+    pub quality: Option<ChordQuality>,
+    pub minor_third: bool,
+    /// Target of a secondary dominant, e.g. V for V7/V, with whether its triad is minor.
+    pub secondary: Option<(ScaleDegree, bool)>,
+}
+impl Display for RomanNumeral {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_text())
+    }
+}
+impl RomanNumeral {
+    pub fn new(
+        degree: ScaleDegree,
+        quality: Option<ChordQuality>,
+        minor_third: bool,
+        secondary: Option<(ScaleDegree, bool)>,
+    ) -> Self {
+        Self {
+            degree,
+            quality,
+            minor_third,
+            secondary,
+        }
+    }
+    /// Label the chord in the scale, the next chord is used to find secondary dominants.
+    pub fn from_chord(scale: &Scale, key: &Key, chord: &Chord, next: Option<&Chord>) -> Self {
+        let quality = ChordQuality::from_intervals(&chord.intervals);
+        let intervals = chord.intervals.get_intervals();
+        let minor_third =
+            intervals.contains(&Interval::Minor3nd) && !intervals.contains(&Interval::Major3nd);
+        let secondary = next.and_then(|next| Self::calc_secondary_target(scale, key, chord, quality, next));
+        match secondary {
+            Some(target) => {
+                let degree = ScaleDegree::new(5, PitchSign::Natural);
+                Self::new(degree, quality, minor_third, Some(target))
+            }
+            None => {
+                let degree = ScaleDegree::from_syllable(scale, key, &chord.root);
+                Self::new(degree, quality, minor_third, None)
+            }
+        }
+    }
+    /// A non-diatonic major or dominant 7th chord resolving down a fifth, to a
+    /// degree other than the tonic.
+    fn calc_secondary_target(
+        scale: &Scale,
+        key: &Key,
+        chord: &Chord,
+        quality: Option<ChordQuality>,
+        next: &Chord,
+    ) -> Option<(ScaleDegree, bool)> {
+        match quality {
+            Some(ChordQuality::Major) | Some(ChordQuality::Dominant7th) | Some(ChordQuality::Dominant9th) => {}
+            _ => return None,
+        }
+        let resolving = (Semitones::from(next.root) - Semitones::from(chord.root)).0.rem_euclid(12) == 5;
+        if !resolving || Self::is_diatonic_chord(scale, chord) {
+            return None;
+        }
+        let target = ScaleDegree::from_syllable(scale, key, &next.root);
+        if target.degree == 1 || !target.is_diatonic() {
+            return None;
+        }
+        Some((target, target.is_minor_triad(scale)))
+    }
+    fn is_diatonic_chord(scale: &Scale, chord: &Chord) -> bool {
+        let pitch_class = |x: Syllable| Semitones::from(x).0.rem_euclid(12);
+        let scale_classes: Vec<i8> = ScaleDegree::get_scale_syllables(scale)
+            .into_iter()
+            .map(pitch_class)
+            .collect();
+        std::iter::once(chord.root)
+            .chain(chord.intervals.get_intervals().iter().map(|x| x.syllable_on_root(&chord.root)))
+            .all(|x| scale_classes.contains(&pitch_class(x)))
+    }
+    pub fn calc_function(&self) -> HarmonicFunction {
+        if self.secondary.is_some() {
+            HarmonicFunction::Dominant
+        } else {
+            self.degree.calc_function()
+        }
+    }
+    pub fn to_suffix(&self) -> String {
+        match self.quality {
+            Some(ChordQuality::Diminished) => "°",
+            Some(ChordQuality::Augmented) => "+",
+            Some(ChordQuality::Suspended2th) => "sus2",
+            Some(ChordQuality::Suspended4th) => "sus4",
+            Some(ChordQuality::Major7th) => "maj7",
+            Some(ChordQuality::Minor7th) | Some(ChordQuality::Dominant7th) => "7",
+            Some(ChordQuality::Diminished7th) => "°7",
+            Some(ChordQuality::HaldDiminished7th) => "ø7",
+            Some(ChordQuality::Major6th) | Some(ChordQuality::Minor6th) => "6",
+            Some(ChordQuality::SixNine) => "6/9",
+            Some(ChordQuality::Add9) | Some(ChordQuality::MinorAdd9) => "add9",
+            Some(ChordQuality::Dominant9th) | Some(ChordQuality::Minor9th) => "9",
+            Some(ChordQuality::Major9th) => "maj9",
+            Some(ChordQuality::Dominant11th) | Some(ChordQuality::Minor11th) => "11",
+            Some(ChordQuality::Dominant13th) | Some(ChordQuality::Minor13th) => "13",
+            Some(ChordQuality::Major13th) => "maj13",
+            _ => "",
+        }
+        .to_owned()
+    }
+    pub fn to_text(&self) -> String {
+        let numeral = self.degree.to_numeral(self.minor_third);
+        let secondary = match self.secondary {
+            Some((target, minor)) => format!("/{}", target.to_numeral(minor)),
+            None => "".to_owned(),
+        };
+        format!("{}{}{}", numeral, self.to_suffix(), secondary)
+    }
+}
+
+impl CadenceKind {
+    pub fn to_text(&self) -> String {
+        match self {
+            CadenceKind::Authentic => "AC",
+            CadenceKind::Plagal => "PC",
+            CadenceKind::Half => "HC",
+            CadenceKind::Deceptive => "DC",
+        }
+        .to_owned()
+    }
+    /// Cadence formed by the last two chords of a phrase.
+    pub fn from_numerals(prev: &RomanNumeral, last: &RomanNumeral) -> Option<Self> {
+        if prev.secondary.is_some() || last.secondary.is_some() {
+            return None;
+        }
+        let (prev, last) = (prev.degree, last.degree);
+        if !prev.is_diatonic() || !last.is_diatonic() {
+            return None;
+        }
+        match (prev.degree, last.degree) {
+            (5, 1) | (7, 1) => Some(CadenceKind::Authentic),
+            (4, 1) => Some(CadenceKind::Plagal),
+            (5, 6) => Some(CadenceKind::Deceptive),
+            (_, 5) => Some(CadenceKind::Half),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::ChordSymbol;
+
+    fn chord(scale: Scale, key: Key, text: &str) -> Chord {
+        ChordSymbol::from_text(text).unwrap().to_chord(&scale, &key)
+    }
+
+    fn numeral(scale: Scale, key: Key, text: &str, next: Option<&str>) -> RomanNumeral {
+        let next = next.map(|x| chord(scale, key, x));
+        RomanNumeral::from_chord(&scale, &key, &chord(scale, key, text), next.as_ref())
+    }
+
+    fn label(scale: Scale, key: Key, text: &str, next: Option<&str>) -> String {
+        numeral(scale, key, text, next).to_text()
+    }
+
+    #[test]
+    fn test_major_numerals() {
+        assert_eq!(label(Scale::Major, Key::C, "C", None), "I");
+        assert_eq!(label(Scale::Major, Key::C, "Dm", None), "ii");
+        assert_eq!(label(Scale::Major, Key::C, "G7", Some("C")), "V7");
+        assert_eq!(label(Scale::Major, Key::C, "D7", Some("G")), "V7/V");
+        assert_eq!(label(Scale::Major, Key::C, "Bb", None), "bVII");
+        assert_eq!(label(Scale::Major, Key::C, "Bb", Some("Eb")), "bVII");
+        assert_eq!(label(Scale::Major, Key::E_FLAT, "Cm7", None), "vi7");
+    }
+
+    #[test]
+    fn test_minor_numerals() {
+        assert_eq!(label(Scale::Minor, Key::A, "Am", None), "i");
+        assert_eq!(label(Scale::Minor, Key::A, "F", None), "VI");
+        assert_eq!(label(Scale::Minor, Key::A, "C", None), "III");
+        assert_eq!(label(Scale::Minor, Key::A, "E7", Some("Am")), "V7");
+        assert_eq!(label(Scale::Minor, Key::A, "A7", Some("Dm")), "V7/iv");
+        assert_eq!(label(Scale::HarmonicMinor, Key::A, "E", None), "V");
+        assert_eq!(label(Scale::MinorPentatonic, Key::A, "G", None), "VII");
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(numeral(Scale::Major, Key::C, "Am", None).calc_function(), HarmonicFunction::Tonic);
+        assert_eq!(numeral(Scale::Major, Key::C, "F", None).calc_function(), HarmonicFunction::Subdominant);
+        assert_eq!(numeral(Scale::Major, Key::C, "G", None).calc_function(), HarmonicFunction::Dominant);
+        assert_eq!(numeral(Scale::Major, Key::C, "D7", Some("G")).calc_function(), HarmonicFunction::Dominant);
+        assert_eq!(numeral(Scale::Major, Key::C, "Bb", None).calc_function(), HarmonicFunction::Chromatic);
+    }
+
+    #[test]
+    fn test_cadences() {
+        let cadence = |prev: &str, last: &str| {
+            CadenceKind::from_numerals(
+                &numeral(Scale::Major, Key::C, prev, Some(last)),
+                &numeral(Scale::Major, Key::C, last, None),
+            )
+        };
+        assert_eq!(cadence("G7", "C"), Some(CadenceKind::Authentic));
+        assert_eq!(cadence("Bdim", "C"), Some(CadenceKind::Authentic));
+        assert_eq!(cadence("F", "C"), Some(CadenceKind::Plagal));
+        assert_eq!(cadence("C", "G"), Some(CadenceKind::Half));
+        assert_eq!(cadence("G", "Am"), Some(CadenceKind::Deceptive));
+        assert_eq!(cadence("D7", "G"), None);
+        assert_eq!(cadence("Bb", "C"), None);
+        assert_eq!(cadence("Am", "Dm"), None);
+    }
+}
@@ -6,6 +6,7 @@ use crate::tone::Tone;
 
 // https://hellomusictheory.com/learn/music-scales-beginners-guide/
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Scale {
     Ionian,
     Dorian,
@@ -14,6 +15,12 @@ pub enum Scale {
     Mixolydian,
     Aeolian,
     Locrian,
+    HarmonicMinor,
+    MelodicMinor,
+    MajorPentatonic,
+    MinorPentatonic,
+    Blues,
+    WholeTone,
 }
 impl Display for Scale {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -30,9 +37,15 @@ impl Scale {
     pub const Major: Scale = Scale::Ionian;
     #[allow(non_upper_case_globals)]
     pub const Minor: Scale = Scale::Aeolian;
-    pub const ALL: [ Scale; 7 ] = [
+    pub const MODES: [ Scale; 7 ] = [
         Scale::Ionian, Scale::Dorian, Scale::Phrygian, Scale::Lydian, Scale::Mixolydian, Scale::Aeolian, Scale::Locrian,
     ];
+    pub const ALL: [ Scale; 13 ] = [
+        Scale::Ionian, Scale::Dorian, Scale::Phrygian, Scale::Lydian, Scale::Mixolydian, Scale::Aeolian, Scale::Locrian,
+        Scale::HarmonicMinor, Scale::MelodicMinor,
+        Scale::MajorPentatonic, Scale::MinorPentatonic, Scale::Blues,
+        Scale::WholeTone,
+    ];
 
     pub fn to_ident(&self) -> String {
         format!("{}", self)
@@ -48,6 +61,12 @@ impl Scale {
             "Mixolydian" => Self::Mixolydian,
             "Aeolian" => Self::Aeolian,
             "Locrian" => Self::Locrian,
+            "HarmonicMinor" => Self::HarmonicMinor,
+            "MelodicMinor" => Self::MelodicMinor,
+            "MajorPentatonic" => Self::MajorPentatonic,
+            "MinorPentatonic" => Self::MinorPentatonic,
+            "Blues" => Self::Blues,
+            "WholeTone" => Self::WholeTone,
             _ => Self::default(),
         }
     }
@@ -116,6 +135,56 @@ impl Scale {
                 Syllable::So,
                 Syllable::La,
             ],
+            Scale::HarmonicMinor => vec![
+                Syllable::La,
+                Syllable::Ti,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fa,
+                Syllable::Si,
+            ],
+            // Ascending form, with raised 6th and 7th
+            Scale::MelodicMinor => vec![
+                Syllable::La,
+                Syllable::Ti,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fi,
+                Syllable::Si,
+            ],
+            Scale::MajorPentatonic => vec![
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::So,
+                Syllable::La,
+            ],
+            Scale::MinorPentatonic => vec![
+                Syllable::La,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::So,
+            ],
+            // Minor pentatonic with the blue note (b5 of the root)
+            Scale::Blues => vec![
+                Syllable::La,
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Me,
+                Syllable::Mi,
+                Syllable::So,
+            ],
+            Scale::WholeTone => vec![
+                Syllable::Do,
+                Syllable::Re,
+                Syllable::Mi,
+                Syllable::Fi,
+                Syllable::Si,
+                Syllable::Li,
+            ],
         }
     }
     pub fn calc_key_index(&self, key: Key) -> usize {
@@ -140,7 +209,7 @@ impl Scale {
     //https://www.hooktheory.com/cheat-sheet
     pub fn get_keys(&self) -> [Key; 12] {
         match self {
-            Scale::Ionian => [
+            Scale::Ionian | Scale::MajorPentatonic | Scale::WholeTone => [
                 Key::C,
                 Key::G,
                 Key::D,
@@ -210,7 +279,11 @@ impl Scale {
                 Key::F,
                 Key::C,
             ],
-            Scale::Aeolian => [
+            Scale::Aeolian
+            | Scale::HarmonicMinor
+            | Scale::MelodicMinor
+            | Scale::MinorPentatonic
+            | Scale::Blues => [
                 Key::A,
                 Key::E,
                 Key::B,
@@ -252,6 +325,12 @@ impl Scale {
             Scale::Mixolydian => 5,
             Scale::Aeolian => 3,
             Scale::Locrian => 1,
+            Scale::HarmonicMinor => 3,
+            Scale::MelodicMinor => 3,
+            Scale::MajorPentatonic => 0,
+            Scale::MinorPentatonic => 3,
+            Scale::Blues => 3,
+            Scale::WholeTone => 0,
         }
     }
     pub fn calc_do_semitones(&self, key: &Key) -> Semitones {
@@ -277,24 +356,14 @@ impl Scale {
         }
     }
     pub fn calc_syllable(&self, key: &Key, pitch: &Pitch) -> Syllable {
-        (Semitones::from(*pitch) - self.calc_do_semitones(key)).into()
+        let syllable = (Semitones::from(*pitch) - self.calc_do_semitones(key)).into();
+        self.calc_spelled_syllable(key, &syllable)
     }
+    /// Pitch of the syllable, spelled for the key, see [`calc_spelled_syllable()`].
+    ///
+    /// [`calc_spelled_syllable()`]: Self::calc_spelled_syllable
     pub fn calc_pitch(&self, key: &Key, syllable: &Syllable) -> Pitch {
-        let key_index = self.calc_key_index(key.clone());
-        if let Some(keys) = match syllable {
-            Syllable::Do => Some(Scale::Ionian.get_keys()),
-            Syllable::Re => Some(Scale::Dorian.get_keys()),
-            Syllable::Mi => Some(Scale::Phrygian.get_keys()),
-            Syllable::Fa => Some(Scale::Lydian.get_keys()),
-            Syllable::So => Some(Scale::Mixolydian.get_keys()),
-            Syllable::La => Some(Scale::Aeolian.get_keys()),
-            Syllable::Ti => Some(Scale::Locrian.get_keys()),
-            _ => None,
-        } {
-            keys[key_index].into()
-        } else {
-            (Semitones::from(*syllable) + self.calc_do_semitones(key)).into()
-        }
+        self.calc_syllable_pitch(key, &self.calc_spelled_syllable(key, syllable))
     }
     pub fn calc_note_from_pitch(&self, key: &Key, pitch: &Pitch, octave: &Octave) -> Note {
         let syllable = self.calc_syllable(key, pitch);
@@ -303,11 +372,13 @@ impl Scale {
     pub fn calc_note_from_syllable(&self, key: &Key, syllable: &Syllable, octave: &Octave) -> Note {
         let semitones = Semitones::from(*octave) + self.calc_do_semitones(key) + Semitones::from(*syllable);
         let pitch = self.calc_pitch(key, syllable);
-        Note::new(Octave::from(semitones), pitch, *syllable)
+        Note::new(pitch.calc_octave(semitones), pitch, *syllable)
     }
     pub fn calc_note_from_semitones(&self, key: &Key, semitones: Semitones) -> Note {
-        let (pitch, octave) = semitones.as_pitch_octave();
-        self.calc_note_from_pitch(key, &pitch, &octave)
+        let (pitch, _) = semitones.as_pitch_octave();
+        let syllable = self.calc_syllable(key, &pitch);
+        let pitch = self.calc_pitch(key, &syllable);
+        Note::new(pitch.calc_octave(semitones), pitch, syllable)
     }
     pub fn calc_click_note(&self, key: &Key, octave: &Octave, syllable: &Syllable) -> Note {
         let pitch = self.calc_pitch(key, syllable);
@@ -317,3 +388,60 @@ impl Scale {
         Tone::Single(self.calc_click_note(key, octave, syllable))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_intervals(scale: Scale) -> Vec<i8> {
+        let root = Semitones::from(scale.calc_root_syllable()).0;
+        scale
+            .get_syllables()
+            .iter()
+            .map(|x| (Semitones::from(*x).0 - root).rem_euclid(12))
+            .collect()
+    }
+
+    #[test]
+    fn test_syllable_sets() {
+        assert_eq!(root_intervals(Scale::HarmonicMinor), vec![0, 2, 3, 5, 7, 8, 11]);
+        assert_eq!(root_intervals(Scale::MelodicMinor), vec![0, 2, 3, 5, 7, 9, 11]);
+        assert_eq!(root_intervals(Scale::MajorPentatonic), vec![0, 2, 4, 7, 9]);
+        assert_eq!(root_intervals(Scale::MinorPentatonic), vec![0, 3, 5, 7, 10]);
+        assert_eq!(root_intervals(Scale::Blues), vec![0, 3, 5, 6, 7, 10]);
+        assert_eq!(root_intervals(Scale::WholeTone), vec![0, 2, 4, 6, 8, 10]);
+        for scale in Scale::MODES {
+            assert_eq!(scale.get_syllables().len(), 7);
+        }
+    }
+
+    #[test]
+    fn test_root_syllables() {
+        assert_eq!(Scale::HarmonicMinor.calc_root_syllable(), Syllable::La);
+        assert_eq!(Scale::MelodicMinor.calc_root_syllable(), Syllable::La);
+        assert_eq!(Scale::MajorPentatonic.calc_root_syllable(), Syllable::Do);
+        assert_eq!(Scale::MinorPentatonic.calc_root_syllable(), Syllable::La);
+        assert_eq!(Scale::Blues.calc_root_syllable(), Syllable::La);
+        assert_eq!(Scale::WholeTone.calc_root_syllable(), Syllable::Do);
+        for scale in Scale::ALL {
+            assert_eq!(scale.get_syllables()[0], scale.calc_root_syllable(), "{}", scale);
+        }
+    }
+
+    #[test]
+    fn test_do_semitones() {
+        // The minor-family scales share Aeolian's relative major, so A puts Do on C.
+        for scale in [
+            Scale::Aeolian,
+            Scale::HarmonicMinor,
+            Scale::MelodicMinor,
+            Scale::MinorPentatonic,
+            Scale::Blues,
+        ] {
+            assert_eq!(scale.calc_do_semitones(&Key::A), Semitones::from(Key::C) + Semitones(12));
+        }
+        for scale in [Scale::Ionian, Scale::MajorPentatonic, Scale::WholeTone] {
+            assert_eq!(scale.calc_do_semitones(&Key::C), Semitones::from(Key::C));
+        }
+    }
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::{Pitch, Octave};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Semitones(pub i8);
 
 impl Display for Semitones {
@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::{Unit, Units};
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Beats(pub f32);
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Signature {
     pub beat_unit: Unit,
     pub bar_beats: u8,
@@ -61,7 +63,7 @@ impl From<f32> for Beats {
 
 impl From<Signature> for Units {
     fn from(v: Signature) -> Self {
-        Self::from(Units::from(v.beat_unit).0 * (v.bar_beats as f32))
+        Units::from(v.beat_unit) * v.bar_beats as i64
     }
 }
 
@@ -73,6 +75,6 @@ impl From<Signature> for Beats {
 
 impl From<(Signature, Units)> for Beats {
     fn from((signature, units): (Signature, Units)) -> Self {
-        Self::from(units.0 / Units::from(signature.beat_unit).0)
+        Self::from((units / Units::from(signature.beat_unit)).to_f32())
     }
 }
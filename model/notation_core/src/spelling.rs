@@ -0,0 +1,283 @@
+use crate::prelude::{Chord, Interval, Key, Octave, Pitch, PitchName, PitchSign, Scale, Semitones, Syllable};
+
+// https://en.wikipedia.org/wiki/Enharmonic
+// Pitches are spelled by counting letters from the pitch of Do, so every natural
+// syllable gets its own letter, chromatic syllables are resolved by the scale,
+// the chord around them, or the key signature.
+
+impl PitchName {
+    pub const ALL: [PitchName; 7] = [
+        PitchName::C,
+        PitchName::D,
+        PitchName::E,
+        PitchName::F,
+        PitchName::G,
+        PitchName::A,
+        PitchName::B,
+    ];
+    pub fn to_index(&self) -> usize {
+        match self {
+            PitchName::C => 0,
+            PitchName::D => 1,
+            PitchName::E => 2,
+            PitchName::F => 3,
+            PitchName::G => 4,
+            PitchName::A => 5,
+            PitchName::B => 6,
+        }
+    }
+    /// Letter name the given steps away, e.g. 2 steps from C is E.
+    pub fn offset(&self, steps: i8) -> Self {
+        let index = (self.to_index() as i8 + steps).rem_euclid(7);
+        Self::ALL[index as usize]
+    }
+}
+
+impl Pitch {
+    /// Spell the pitch class of the semitones with the given letter name,
+    /// `None` if more than a double sharp or double flat is needed.
+    pub fn from_name_semitones(name: PitchName, semitones: Semitones) -> Option<Self> {
+        let mut sign = (semitones - Semitones::from(name)).0.rem_euclid(12);
+        if sign > 6 {
+            sign -= 12;
+        }
+        if sign.abs() > 2 {
+            return None;
+        }
+        Some(Self::new(name, PitchSign::from(Semitones(sign))))
+    }
+    /// Octave to write with this pitch for the note, B# and Cb cross the octave line.
+    pub fn calc_octave(&self, semitones: Semitones) -> Octave {
+        Octave::from(semitones - Semitones::from(*self))
+    }
+}
+
+impl Syllable {
+    /// Position of the letter counted from Do, with the accidental on it.
+    pub fn to_degree(&self) -> (i8, PitchSign) {
+        match self {
+            Syllable::Do => (0, PitchSign::Natural),
+            Syllable::Di => (0, PitchSign::Sharp),
+            Syllable::Ra => (1, PitchSign::Flat),
+            Syllable::Re => (1, PitchSign::Natural),
+            Syllable::Ri => (1, PitchSign::Sharp),
+            Syllable::Me => (2, PitchSign::Flat),
+            Syllable::Mi => (2, PitchSign::Natural),
+            Syllable::Fa => (3, PitchSign::Natural),
+            Syllable::Fi => (3, PitchSign::Sharp),
+            Syllable::Se => (4, PitchSign::Flat),
+            Syllable::So => (4, PitchSign::Natural),
+            Syllable::Si => (4, PitchSign::Sharp),
+            Syllable::Le => (5, PitchSign::Flat),
+            Syllable::La => (5, PitchSign::Natural),
+            Syllable::Li => (5, PitchSign::Sharp),
+            Syllable::Te => (6, PitchSign::Flat),
+            Syllable::Ti => (6, PitchSign::Natural),
+        }
+    }
+    pub fn is_natural(&self) -> bool {
+        self.to_degree().1 == PitchSign::Natural
+    }
+    /// The other name of a chromatic syllable, e.g. Li for Te, naturals are kept.
+    pub fn to_enharmonic(&self) -> Self {
+        match self {
+            Syllable::Di => Syllable::Ra,
+            Syllable::Ra => Syllable::Di,
+            Syllable::Ri => Syllable::Me,
+            Syllable::Me => Syllable::Ri,
+            Syllable::Fi => Syllable::Se,
+            Syllable::Se => Syllable::Fi,
+            Syllable::Si => Syllable::Le,
+            Syllable::Le => Syllable::Si,
+            Syllable::Li => Syllable::Te,
+            Syllable::Te => Syllable::Li,
+            _ => *self,
+        }
+    }
+}
+
+impl Scale {
+    /// Pitch of Do, spelled from the key, e.g. Bb for C Dorian (rather than A#).
+    pub fn calc_do_pitch(&self, key: &Key) -> Pitch {
+        let tonic = Pitch::from(*key);
+        let (root_degree, _) = self.calc_root_syllable().to_degree();
+        let semitones = self.calc_do_semitones(key);
+        Pitch::from_name_semitones(tonic.name.offset(-root_degree), semitones)
+            .unwrap_or_else(|| Pitch::from(semitones))
+    }
+    /// Whether chromatic notes should be written with flats, following the key signature.
+    ///
+    /// Keys without accidentals use the common C#, Eb, F#, Ab, Bb.
+    pub fn prefers_flat(&self, key: &Key, syllable: &Syllable) -> bool {
+        let do_pitch = self.calc_do_pitch(key);
+        match (do_pitch.name, do_pitch.sign) {
+            (_, PitchSign::Flat) | (_, PitchSign::DoubleFlat) => true,
+            (PitchName::F, PitchSign::Natural) => true,
+            (PitchName::C, PitchSign::Natural) => !matches!(syllable.to_degree(), (0, _) | (3, _)),
+            _ => false,
+        }
+    }
+    /// Pick the name of a chromatic syllable: the one in the scale if any, otherwise
+    /// the one with fewer accidentals on the pitch, e.g. G rather than F## in F# major,
+    /// then explicit flats are kept, and sharps follow the key signature.
+    pub fn calc_spelled_syllable(&self, key: &Key, syllable: &Syllable) -> Syllable {
+        if syllable.is_natural() {
+            return *syllable;
+        }
+        let syllables = self.get_syllables();
+        let enharmonic = syllable.to_enharmonic();
+        if syllables.contains(syllable) {
+            return *syllable;
+        } else if syllables.contains(&enharmonic) {
+            return enharmonic;
+        }
+        let accidentals = |x: &Syllable| Semitones::from(self.calc_syllable_pitch(key, x).sign).0.abs();
+        let (count, enharmonic_count) = (accidentals(syllable), accidentals(&enharmonic));
+        if count != enharmonic_count {
+            if count < enharmonic_count {
+                *syllable
+            } else {
+                enharmonic
+            }
+        } else if syllable.to_degree().1 == PitchSign::Flat {
+            *syllable
+        } else if self.prefers_flat(key, syllable) {
+            enharmonic
+        } else {
+            *syllable
+        }
+    }
+    /// Spell the syllable by its own letter, counted from the pitch of Do.
+    pub fn calc_syllable_pitch(&self, key: &Key, syllable: &Syllable) -> Pitch {
+        let do_pitch = self.calc_do_pitch(key);
+        let (degree, _) = syllable.to_degree();
+        let semitones = self.calc_do_semitones(key) + Semitones::from(*syllable);
+        Pitch::from_name_semitones(do_pitch.name.offset(degree), semitones)
+            .unwrap_or_else(|| Pitch::from(semitones))
+    }
+    /// Spell notes of the chord by their intervals from the root, e.g. the major 3rd
+    /// of E is G# even in C major, other notes are spelled with [`calc_pitch()`].
+    ///
+    /// [`calc_pitch()`]: Self::calc_pitch
+    pub fn calc_pitch_in_chord(&self, key: &Key, syllable: &Syllable, chord: &Chord) -> Pitch {
+        let root = self.calc_pitch(key, &chord.root);
+        let semitones = self.calc_do_semitones(key) + Semitones::from(*syllable);
+        let intervals = std::iter::once(Interval::Unison)
+            .chain(chord.intervals.get_intervals())
+            .chain(chord.bass);
+        for interval in intervals {
+            if interval.is_matched(chord.root, *syllable) {
+                let steps = interval.dot_count() as i8 - 1;
+                if let Some(pitch) = Pitch::from_name_semitones(root.name.offset(steps), semitones) {
+                    return pitch;
+                }
+            }
+        }
+        self.calc_pitch(key, syllable)
+    }
+    pub fn calc_pitch_with_chord(&self, key: &Key, syllable: &Syllable, chord: Option<&Chord>) -> Pitch {
+        match chord {
+            Some(chord) => self.calc_pitch_in_chord(key, syllable, chord),
+            None => self.calc_pitch(key, syllable),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Intervals;
+
+    const NATURALS: [Syllable; 7] = [
+        Syllable::Do,
+        Syllable::Re,
+        Syllable::Mi,
+        Syllable::Fa,
+        Syllable::So,
+        Syllable::La,
+        Syllable::Ti,
+    ];
+
+    fn pitches(scale: Scale, key: Key, syllables: &[Syllable]) -> Vec<Pitch> {
+        syllables.iter().map(|x| scale.calc_pitch(&key, x)).collect()
+    }
+
+    /// The lookup `calc_pitch()` used before spelling, only defined for the natural syllables.
+    fn lookup_pitch(scale: Scale, key: Key, syllable: Syllable) -> Pitch {
+        let mode = Scale::MODES[syllable.to_degree().0 as usize];
+        mode.get_keys()[scale.calc_key_index(key)].into()
+    }
+
+    #[test]
+    fn test_sharp_keys() {
+        assert_eq!(
+            pitches(Scale::Ionian, Key::F_SHARP, &NATURALS),
+            vec![
+                Pitch::F_SHARP,
+                Pitch::G_SHARP,
+                Pitch::A_SHARP,
+                Pitch::B,
+                Pitch::C_SHARP,
+                Pitch::D_SHARP,
+                Pitch::E_SHARP,
+            ]
+        );
+        // Fewer accidentals wins over the key signature.
+        assert_eq!(Scale::Ionian.calc_spelled_syllable(&Key::F_SHARP, &Syllable::Di), Syllable::Ra);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::F_SHARP, &Syllable::Di), Pitch::G);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::F_SHARP, &Syllable::Fi), Pitch::C);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::D, &Syllable::Fi), Pitch::G_SHARP);
+    }
+
+    #[test]
+    fn test_flat_keys() {
+        assert_eq!(
+            pitches(Scale::Ionian, Key::E_FLAT, &NATURALS),
+            vec![Pitch::E_FLAT, Pitch::F, Pitch::G, Pitch::A_FLAT, Pitch::B_FLAT, Pitch::C, Pitch::D]
+        );
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::E_FLAT, &Syllable::Li), Pitch::D_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::E_FLAT, &Syllable::Di), Pitch::E);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::F, &Syllable::Fi), Pitch::B);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::F, &Syllable::Ri), Pitch::A_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::F, &Syllable::Li), Pitch::E_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::C, &Syllable::Li), Pitch::B_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::C, &Syllable::Fi), Pitch::F_SHARP);
+    }
+
+    #[test]
+    fn test_minor_and_modal_scales() {
+        assert_eq!(Scale::Dorian.calc_do_pitch(&Key::C), Pitch::B_FLAT);
+        assert_eq!(Scale::Aeolian.calc_do_pitch(&Key::D_SHARP), Pitch::F_SHARP);
+        assert_eq!(Scale::Aeolian.calc_pitch(&Key::D_SHARP, &Syllable::Ti), Pitch::E_SHARP);
+        assert_eq!(Scale::HarmonicMinor.calc_pitch(&Key::A, &Syllable::Si), Pitch::G_SHARP);
+        assert_eq!(Scale::HarmonicMinor.calc_pitch(&Key::A, &Syllable::Le), Pitch::G_SHARP);
+        assert_eq!(Scale::MelodicMinor.calc_pitch(&Key::A, &Syllable::Se), Pitch::F_SHARP);
+        assert_eq!(Scale::HarmonicMinor.calc_syllable(&Key::C, &Pitch::B), Syllable::Si);
+        assert_eq!(Scale::Blues.calc_pitch(&Key::A, &Syllable::Ri), Pitch::E_FLAT);
+        assert_eq!(Scale::Mixolydian.calc_pitch(&Key::G, &Syllable::Fa), Pitch::F);
+    }
+
+    #[test]
+    fn test_chord_context() {
+        let e_major = Chord::new(Syllable::Mi, Intervals::Triad(Interval::Major3nd, Interval::Perfect5th), None);
+        assert_eq!(Scale::Ionian.calc_pitch(&Key::C, &Syllable::Si), Pitch::A_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch_in_chord(&Key::C, &Syllable::Si, &e_major), Pitch::G_SHARP);
+        assert_eq!(Scale::Ionian.calc_pitch_in_chord(&Key::C, &Syllable::Ti, &e_major), Pitch::B);
+        // Notes outside of the chord fall back to the key.
+        assert_eq!(Scale::Ionian.calc_pitch_in_chord(&Key::C, &Syllable::Li, &e_major), Pitch::B_FLAT);
+        assert_eq!(Scale::Ionian.calc_pitch_with_chord(&Key::C, &Syllable::Si, None), Pitch::A_FLAT);
+    }
+
+    #[test]
+    fn test_diatonic_unchanged() {
+        for scale in Scale::MODES {
+            for key in scale.get_keys() {
+                for syllable in NATURALS {
+                    let pitch = lookup_pitch(scale, key, syllable);
+                    assert_eq!(scale.calc_pitch(&key, &syllable), pitch, "{} {} {}", scale, key, syllable);
+                    assert_eq!(scale.calc_syllable(&key, &pitch), syllable, "{} {} {}", scale, key, syllable);
+                }
+            }
+        }
+    }
+}
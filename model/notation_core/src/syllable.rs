@@ -5,6 +5,7 @@ use crate::prelude::{PitchName, PitchSign, Semitones};
 
 // https://en.wikipedia.org/wiki/Solf%C3%A8ge
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Syllable {
     //Natural
     Do,
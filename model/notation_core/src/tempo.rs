@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 // https://hellomusictheory.com/learn/musical-term-for-fast/
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Tempo {
     Larghissimo,
     Adagissimo,
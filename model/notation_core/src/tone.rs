@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use super::note::Note;
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Tone {
     None,
     Single(Note),
@@ -0,0 +1,264 @@
+use crate::prelude::{Finger, HandShape4, HandShape6, Pick, PickNote};
+
+impl Finger {
+    /// Fingers used on the fretboard, the thumb is left out.
+    pub const FRETTING: [Finger; 4] = [Finger::Index, Finger::Middle, Finger::Ring, Finger::Pinky];
+    /// The common finger number in chord diagrams, thumb is 0.
+    pub fn to_number(&self) -> u8 {
+        match self {
+            Finger::Thumb => 0,
+            Finger::Index => 1,
+            Finger::Middle => 2,
+            Finger::Ring => 3,
+            Finger::Pinky => 4,
+        }
+    }
+    pub fn from_number(number: u8) -> Option<Self> {
+        match number {
+            0 => Some(Finger::Thumb),
+            1 => Some(Finger::Index),
+            2 => Some(Finger::Middle),
+            3 => Some(Finger::Ring),
+            4 => Some(Finger::Pinky),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_fingering {
+    ($hand_shape:ident, $strings:literal) => {
+        impl $hand_shape {
+            /// Put the barre on the lowest fret when the shape needs more than four fingers.
+            pub fn with_auto_barre(&self) -> Self {
+                if self.barre.is_some() {
+                    return *self;
+                }
+                let fretted: Vec<u8> = self.frets.iter().flatten().filter(|x| **x > 0).copied().collect();
+                if fretted.len() <= Finger::FRETTING.len() {
+                    return *self;
+                }
+                let lowest = *fretted.iter().min().unwrap();
+                let lowest_string = self.frets.iter().rposition(|x| x.is_some()).unwrap_or(0);
+                let covered = self.frets[..=lowest_string]
+                    .iter()
+                    .all(|x| x.map(|f| f >= lowest).unwrap_or(false));
+                if !covered {
+                    return *self;
+                }
+                Self::new_barre(
+                    lowest,
+                    self.frets.map(|x| x.map(|f| f - lowest)),
+                    self.fingers,
+                )
+            }
+            /// Fill the missing fingers, keeping the ones specified explicitly.
+            ///
+            /// Notes are assigned from the lowest fret, bass strings first, each finger
+            /// no closer to the index than its distance in frets, the barre takes the index.
+            pub fn calc_fingers(&self) -> [Option<Finger>; $strings] {
+                let mut fingers = self.fingers;
+                let mut used: Vec<Finger> = fingers.iter().flatten().copied().collect();
+                let barre = self.barre();
+                if barre > 0 {
+                    for (index, fret) in self.frets.iter().enumerate() {
+                        if *fret == Some(0) && fingers[index].is_none() {
+                            fingers[index] = Some(Finger::Index);
+                        }
+                    }
+                    used.push(Finger::Index);
+                }
+                let mut notes: Vec<(u8, usize)> = self
+                    .frets
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, fret)| fret.map(|f| f > 0).unwrap_or(false) && fingers[*index].is_none())
+                    .map(|(index, fret)| (fret.unwrap(), index))
+                    .collect();
+                notes.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+                let base = if barre > 0 {
+                    0
+                } else {
+                    notes.iter().map(|x| x.0).min().unwrap_or(0)
+                };
+                let mut next = 0;
+                for (fret, index) in notes {
+                    let distance = (fret - base) as usize;
+                    let mut slot = next.max(distance);
+                    while slot < Finger::FRETTING.len() && used.contains(&Finger::FRETTING[slot]) {
+                        slot += 1;
+                    }
+                    if slot >= Finger::FRETTING.len() {
+                        break;
+                    }
+                    fingers[index] = Some(Finger::FRETTING[slot]);
+                    used.push(Finger::FRETTING[slot]);
+                    next = slot + 1;
+                }
+                fingers
+            }
+            /// Shape with barre and fingers filled by [`with_auto_barre()`] and [`calc_fingers()`].
+            ///
+            /// [`with_auto_barre()`]: Self::with_auto_barre
+            /// [`calc_fingers()`]: Self::calc_fingers
+            pub fn with_auto_fingers(&self) -> Self {
+                let shape = self.with_auto_barre();
+                Self {
+                    fingers: shape.calc_fingers(),
+                    ..shape
+                }
+            }
+            /// Assign `fret_finger` for the notes in a passage played over this shape.
+            ///
+            /// Notes on the shape's frets use the shape's fingers, other notes follow the
+            /// hand position, which moves when a note is out of reach. Explicit fingers
+            /// are kept, and move the hand position too.
+            pub fn calc_pick_fingers(&self, picks: &[Pick]) -> Vec<Pick> {
+                let shape = self.with_auto_fingers();
+                let mut position = shape.hand_position();
+                picks
+                    .iter()
+                    .map(|pick| {
+                        let notes: Vec<PickNote> = pick
+                            .get_notes()
+                            .into_iter()
+                            .map(|note| shape.calc_pick_note_finger(note, &mut position))
+                            .collect();
+                        Pick::from(notes)
+                    })
+                    .collect()
+            }
+            /// Fret under the index finger, 1 for open shapes.
+            fn hand_position(&self) -> u8 {
+                let barre = self.barre();
+                if barre > 0 {
+                    return barre;
+                }
+                let mut position = None;
+                for (index, fret) in self.frets.iter().enumerate() {
+                    if let (Some(fret), Some(finger)) = (fret, self.fingers[index]) {
+                        if *fret > 0 && finger.to_number() > 0 {
+                            let p = (*fret + 1).saturating_sub(finger.to_number()).max(1);
+                            position = Some(position.map(|x: u8| x.min(p)).unwrap_or(p));
+                        }
+                    }
+                }
+                position.unwrap_or(1)
+            }
+            fn calc_pick_note_finger(&self, note: PickNote, position: &mut u8) -> PickNote {
+                let fret = match note.fret {
+                    Some(fret) => Some(fret),
+                    None => self.string_fret_with_barre(note.string),
+                };
+                let fret = match fret {
+                    Some(fret) if fret > 0 => fret,
+                    _ => return note,
+                };
+                if let Some(finger) = note.fret_finger {
+                    if finger.to_number() > 0 {
+                        *position = (fret + 1).saturating_sub(finger.to_number()).max(1);
+                    }
+                    return note;
+                }
+                let mut finger = None;
+                if self.string_fret_with_barre(note.string) == Some(fret) {
+                    finger = self.fingers.get(note.string as usize - 1).copied().flatten();
+                }
+                if finger.is_none() {
+                    if fret < *position {
+                        *position = fret;
+                    } else if fret >= *position + Finger::FRETTING.len() as u8 {
+                        *position = fret + 1 - Finger::FRETTING.len() as u8;
+                    }
+                    finger = Some(Finger::FRETTING[(fret - *position) as usize]);
+                }
+                PickNote {
+                    fret_finger: finger,
+                    ..note
+                }
+            }
+        }
+    };
+}
+
+impl_fingering!(HandShape6, 6);
+impl_fingering!(HandShape4, 4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const C: [Option<u8>; 6] = [Some(0), Some(1), Some(0), Some(2), Some(3), None];
+    const F: [Option<u8>; 6] = [Some(1), Some(1), Some(2), Some(3), Some(3), Some(1)];
+
+    #[test]
+    fn test_finger_numbers() {
+        for number in 0..=4 {
+            assert_eq!(Finger::from_number(number).unwrap().to_number(), number);
+        }
+        assert_eq!(Finger::from_number(5), None);
+    }
+
+    #[test]
+    fn test_auto_barre() {
+        let open = HandShape6::from(C);
+        assert_eq!(open.with_auto_barre(), open);
+        let f = HandShape6::from(F).with_auto_barre();
+        assert_eq!(f.barre, Some(1));
+        assert_eq!(f.frets, [Some(0), Some(0), Some(1), Some(2), Some(2), Some(0)]);
+        // muted bass strings are left out of the barre
+        let bass_muted = HandShape6::from([Some(1), Some(1), Some(2), Some(3), Some(3), None]);
+        assert_eq!(bass_muted.with_auto_barre().barre, Some(1));
+        // a muted string in the middle can't be covered by the barre
+        let middle_muted = HandShape6::from([Some(1), None, Some(2), Some(3), Some(3), Some(1)]);
+        assert_eq!(middle_muted.with_auto_barre(), middle_muted);
+        // not needed when four fingers are enough
+        let d = HandShape6::from([Some(2), Some(3), Some(2), Some(0), None, None]);
+        assert_eq!(d.with_auto_barre().barre, None);
+    }
+
+    #[test]
+    fn test_calc_fingers() {
+        use Finger::*;
+        let c = HandShape6::from(C).calc_fingers();
+        assert_eq!(c, [None, Some(Index), None, Some(Middle), Some(Ring), None]);
+        let f = HandShape6::from(F).with_auto_fingers();
+        assert_eq!(
+            f.fingers,
+            [Some(Index), Some(Index), Some(Middle), Some(Pinky), Some(Ring), Some(Index)]
+        );
+        let d = HandShape6::from([Some(2), Some(3), Some(2), Some(0), None, None]).calc_fingers();
+        assert_eq!(d, [Some(Middle), Some(Ring), Some(Index), None, None, None]);
+    }
+
+    #[test]
+    fn test_explicit_fingers_kept() {
+        use Finger::*;
+        let shape = HandShape6::new(C, [None, None, None, None, Some(Pinky), None]);
+        let fingers = shape.calc_fingers();
+        assert_eq!(fingers[4], Some(Pinky));
+        assert_eq!(fingers[1], Some(Index));
+        assert_eq!(fingers[3], Some(Middle));
+    }
+
+    #[test]
+    fn test_calc_pick_fingers() {
+        use Finger::*;
+        let picks = [
+            Pick::from(PickNote::new_string(5)),
+            Pick::from(PickNote::new_string_fret(1, 3)),
+            Pick::from(PickNote::new_string_fret(1, 7)),
+            Pick::from(PickNote::new(1, Some(5), Some(Index), None, None)),
+            Pick::from(PickNote::new_string_fret(2, 6)),
+            Pick::from(PickNote::new_string(3)),
+        ];
+        let fingers: Vec<Option<Finger>> = HandShape6::from(C)
+            .calc_pick_fingers(&picks)
+            .iter()
+            .map(|x| x.get_notes()[0].fret_finger)
+            .collect();
+        assert_eq!(
+            fingers,
+            vec![Some(Ring), Some(Ring), Some(Pinky), Some(Index), Some(Middle), None]
+        );
+    }
+}
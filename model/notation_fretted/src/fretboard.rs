@@ -5,14 +5,16 @@ use crate::pick::PickNote;
 use crate::prelude::Pick;
 
 use super::prelude::{HandShape4, HandShape6};
-use notation_core::prelude::{Note, Semitones, Tone, Scale, Key};
+use notation_core::prelude::{Chord, Note, Semitones, Tone, Scale, Key};
 
 macro_rules! impl_fretboard {
     ($type:ident, $strings:literal, $hand_shape:ident) => {
         #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         pub struct $type {
             pub total_fret_num: usize,
             #[serde(with = "serde_arrays")]
+            #[cfg_attr(feature = "schema", schemars(with = "Vec<Semitones>"))]
             pub string_notes: [Semitones; $strings],
             pub capo: u8,
         }
@@ -111,9 +113,92 @@ macro_rules! impl_fretboard {
                     .collect();
                 notes.into()
             }
+            /// Chords the shape can be named after, best match first.
+            pub fn calc_shape_chords(&self, scale: &Scale, key: &Key, shape: &$hand_shape) -> Vec<Chord> {
+                let notes: Vec<Note> = (1..=$strings)
+                    .filter_map(|string| self.shape_note(scale, key, shape, string as u8))
+                    .collect();
+                Chord::calc_candidates(&notes)
+            }
         }
     };
 }
 
 impl_fretboard!(Fretboard6, 6, HandShape6);
 impl_fretboard!(Fretboard4, 4, HandShape4);
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Interval, Intervals, Octave, Pitch, Syllable};
+
+    use super::*;
+
+    fn guitar() -> Fretboard6 {
+        let string_notes = [
+            (Pitch::E, Octave::P4).into(),
+            (Pitch::B, Octave::P3).into(),
+            (Pitch::G, Octave::P3).into(),
+            (Pitch::D, Octave::P3).into(),
+            (Pitch::A, Octave::P2).into(),
+            (Pitch::E, Octave::P2).into(),
+        ];
+        Fretboard6::new(22, string_notes, 0)
+    }
+
+    fn shape_chords(frets: [Option<u8>; 6]) -> Vec<Chord> {
+        guitar().calc_shape_chords(&Scale::Major, &Key::C, &HandShape6::from(frets))
+    }
+
+    #[test]
+    fn test_triads() {
+        let c = shape_chords([Some(0), Some(1), Some(0), Some(2), Some(3), None]);
+        assert_eq!(
+            c[0],
+            Chord::new(Syllable::Do, Intervals::Triad(Interval::Major3nd, Interval::Perfect5th), None)
+        );
+        let am = shape_chords([Some(0), Some(1), Some(2), Some(2), Some(0), None]);
+        assert_eq!(
+            am[0],
+            Chord::new(Syllable::La, Intervals::Triad(Interval::Minor3nd, Interval::Perfect5th), None)
+        );
+    }
+
+    #[test]
+    fn test_seventh() {
+        let e7 = shape_chords([Some(0), Some(0), Some(1), Some(0), Some(2), Some(0)]);
+        assert_eq!(
+            e7[0],
+            Chord::new(
+                Syllable::Mi,
+                Intervals::Tetrad(Interval::Major3nd, Interval::Perfect5th, Interval::Minor7th),
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn test_root_in_bass_ranked_first() {
+        let am7 = shape_chords([Some(0), Some(1), Some(0), Some(2), Some(0), None]);
+        assert_eq!(am7[0].root, Syllable::La);
+        assert_eq!(am7[0].bass, None);
+        let c6 = am7.iter().find(|x| x.root == Syllable::Do).unwrap();
+        assert_eq!(c6.bass, Some(Interval::Major6th));
+    }
+
+    #[test]
+    fn test_slash_bass() {
+        let c_g = shape_chords([Some(0), Some(1), Some(0), Some(2), Some(3), Some(3)]);
+        assert_eq!(c_g[0].root, Syllable::Do);
+        assert_eq!(c_g[0].bass, Some(Interval::Perfect5th));
+    }
+
+    #[test]
+    fn test_missing_fifth() {
+        let c = shape_chords([None, Some(1), None, Some(2), Some(3), None]);
+        assert_eq!(
+            c[0],
+            Chord::new(Syllable::Do, Intervals::Triad(Interval::Major3nd, Interval::Perfect5th), None)
+        );
+        assert!(shape_chords([None, None, None, None, Some(3), None]).is_empty());
+    }
+}
@@ -8,6 +8,7 @@ use notation_core::prelude::{Duration, Entry, EntryPassMode};
 macro_rules! impl_entry {
     ($type:ident, $strings:literal, $hand_shape:ident, $fretboard:ident) => {
         #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         pub enum $type {
             Pick(Pick, Duration),
             Strum(Strum, Duration),
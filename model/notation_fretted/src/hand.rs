@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Finger {
     Thumb,
     Index,
@@ -24,11 +25,14 @@ impl Display for Finger {
 macro_rules! impl_hand_shape {
     ($type:ident, $strings:literal) => {
         #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+        #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
         pub struct $type {
             pub barre: Option<u8>,
             #[serde(with = "serde_arrays")]
+            #[cfg_attr(feature = "schema", schemars(with = "Vec<Option<u8>>"))]
             pub frets: [Option<u8>; $strings],
             #[serde(with = "serde_arrays")]
+            #[cfg_attr(feature = "schema", schemars(with = "Vec<Option<Finger>>"))]
             pub fingers: [Option<Finger>; $strings],
         }
         impl Display for $type {
@@ -1,8 +1,11 @@
+pub mod fingering;
 pub mod fretboard;
 pub mod fretted_entry;
 pub mod hand;
+pub mod melody;
 pub mod pick;
 pub mod strum;
+pub mod voicing;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -12,7 +15,11 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::hand::{Finger, HandShape4, HandShape6};
     #[doc(hidden)]
+    pub use crate::melody::MelodyOptions;
+    #[doc(hidden)]
     pub use crate::pick::{Pick, PickNote};
     #[doc(hidden)]
     pub use crate::strum::Strum;
+    #[doc(hidden)]
+    pub use crate::voicing::{ChordVoicing4, ChordVoicing6, VoicingOptions};
 }
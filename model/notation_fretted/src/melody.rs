@@ -0,0 +1,248 @@
+use notation_core::prelude::{Note, Semitones};
+
+use super::prelude::{Fretboard4, Fretboard6, PickNote};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct MelodyOptions {
+    /// Preferred hand position, the fret under the index finger (relative to capo).
+    pub position: u8,
+    /// Highest fret to use (relative to capo).
+    pub max_fret: u8,
+}
+impl Default for MelodyOptions {
+    fn default() -> Self {
+        Self {
+            position: 1,
+            max_fret: 12,
+        }
+    }
+}
+
+/// Frets reachable without moving the hand.
+const HAND_SPAN: u8 = 3;
+const MOVE_COST: i32 = 4;
+const POSITION_COST: i32 = 2;
+const STRING_COST: i32 = 1;
+
+impl MelodyOptions {
+    fn position_cost(&self, fret: u8) -> i32 {
+        if fret == 0 {
+            0
+        } else if fret < self.position {
+            (self.position - fret) as i32 * POSITION_COST
+        } else if fret > self.position + HAND_SPAN {
+            (fret - self.position - HAND_SPAN) as i32 * POSITION_COST
+        } else {
+            0
+        }
+    }
+    fn move_cost(from: &PickNote, to: &PickNote) -> i32 {
+        let from_fret = from.fret.unwrap_or(0);
+        let to_fret = to.fret.unwrap_or(0);
+        let moving = if from_fret == 0 || to_fret == 0 {
+            0
+        } else {
+            ((from_fret as i32 - to_fret as i32).abs() - HAND_SPAN as i32).max(0)
+        };
+        moving * MOVE_COST + (from.string as i32 - to.string as i32).abs() * STRING_COST
+    }
+}
+
+macro_rules! impl_melody {
+    ($fretboard:ident, $strings:literal) => {
+        impl $fretboard {
+            /// All the string/fret places to play the note, frets are relative to capo.
+            pub fn calc_note_places(&self, note: &Note, max_fret: u8) -> Vec<PickNote> {
+                let max_fret = (max_fret as usize).min(self.fret_num().saturating_sub(1)) as i32;
+                let note = Semitones::from(*note).0 as i32;
+                let mut result = Vec::new();
+                for string in 1..=$strings {
+                    let open = self.string_notes[string - 1].0 as i32 + self.capo as i32;
+                    let fret = note - open;
+                    if fret >= 0 && fret <= max_fret {
+                        result.push(PickNote::new_string_fret(string as u8, fret as u8));
+                    }
+                }
+                result
+            }
+            /// Choose a place for each note, minimizing hand movement and staying close
+            /// to the preferred position, `None` for notes out of the fretboard's range.
+            pub fn calc_melody_picks(&self, notes: &[Note], options: &MelodyOptions) -> Vec<Option<PickNote>> {
+                let places: Vec<Vec<PickNote>> = notes
+                    .iter()
+                    .map(|x| self.calc_note_places(x, options.max_fret))
+                    .collect();
+                // costs[i][j]: best total cost with note i played at places[i][j],
+                // froms[i][j]: the choice of the previous playable note.
+                let mut costs: Vec<Vec<i32>> = Vec::new();
+                let mut froms: Vec<Vec<Option<usize>>> = Vec::new();
+                let mut prev: Option<usize> = None;
+                for (index, candidates) in places.iter().enumerate() {
+                    let mut step_costs = Vec::new();
+                    let mut step_froms = Vec::new();
+                    for place in candidates.iter() {
+                        let own = options.position_cost(place.fret.unwrap_or(0));
+                        let best = prev.and_then(|p| {
+                            places[p]
+                                .iter()
+                                .enumerate()
+                                .map(|(j, from)| (j, costs[p][j] + MelodyOptions::move_cost(from, place)))
+                                .min_by_key(|x| x.1)
+                        });
+                        match best {
+                            Some((j, cost)) => {
+                                step_costs.push(own + cost);
+                                step_froms.push(Some(j));
+                            }
+                            None => {
+                                step_costs.push(own);
+                                step_froms.push(None);
+                            }
+                        }
+                    }
+                    costs.push(step_costs);
+                    froms.push(step_froms);
+                    if !candidates.is_empty() {
+                        prev = Some(index);
+                    }
+                }
+                let mut result: Vec<Option<PickNote>> = vec![None; notes.len()];
+                let mut choice = prev.and_then(|p| {
+                    costs[p].iter().enumerate().min_by_key(|x| x.1).map(|x| x.0)
+                });
+                for index in (0..notes.len()).rev() {
+                    if places[index].is_empty() {
+                        continue;
+                    }
+                    if let Some(j) = choice {
+                        result[index] = Some(places[index][j]);
+                        choice = froms[index][j];
+                    }
+                }
+                result
+            }
+        }
+    };
+}
+
+impl_melody!(Fretboard6, 6);
+impl_melody!(Fretboard4, 4);
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Octave, Pitch, Syllable};
+
+    use super::*;
+
+    fn guitar(capo: u8) -> Fretboard6 {
+        let string_notes = [
+            (Pitch::E, Octave::P4).into(),
+            (Pitch::B, Octave::P3).into(),
+            (Pitch::G, Octave::P3).into(),
+            (Pitch::D, Octave::P3).into(),
+            (Pitch::A, Octave::P2).into(),
+            (Pitch::E, Octave::P2).into(),
+        ];
+        Fretboard6::new(22, string_notes, capo)
+    }
+
+    fn note(pitch: Pitch, octave: Octave) -> Note {
+        Note::new(octave, pitch, Syllable::Do)
+    }
+
+    fn places(picks: &[Option<PickNote>]) -> Vec<Option<(u8, u8)>> {
+        picks
+            .iter()
+            .map(|x| x.map(|y| (y.string, y.fret.unwrap())))
+            .collect()
+    }
+
+    #[test]
+    fn test_scale_run_in_one_position() {
+        let notes: Vec<Note> = [
+            (Pitch::C, Octave::P3),
+            (Pitch::D, Octave::P3),
+            (Pitch::E, Octave::P3),
+            (Pitch::F, Octave::P3),
+            (Pitch::G, Octave::P3),
+            (Pitch::A, Octave::P3),
+            (Pitch::B, Octave::P3),
+            (Pitch::C, Octave::P4),
+        ]
+        .iter()
+        .map(|(pitch, octave)| note(*pitch, *octave))
+        .collect();
+        let options = MelodyOptions {
+            position: 2,
+            ..Default::default()
+        };
+        let picks = guitar(0).calc_melody_picks(&notes, &options);
+        assert_eq!(
+            places(&picks),
+            vec![
+                Some((5, 3)),
+                Some((4, 0)),
+                Some((4, 2)),
+                Some((4, 3)),
+                Some((3, 0)),
+                Some((3, 2)),
+                Some((3, 4)),
+                Some((3, 5)),
+            ]
+        );
+        let frets: Vec<u8> = picks
+            .iter()
+            .map(|x| x.unwrap().fret.unwrap())
+            .filter(|x| *x > 0)
+            .collect();
+        let min = *frets.iter().min().unwrap();
+        let max = *frets.iter().max().unwrap();
+        assert!(max - min <= HAND_SPAN);
+        assert!(min >= options.position && max <= options.position + HAND_SPAN);
+    }
+
+    #[test]
+    fn test_capo() {
+        let f_sharp = note(Pitch::F_SHARP, Octave::P4);
+        let open = PickNote::new_string_fret(1, 0);
+        assert!(guitar(0).calc_note_places(&f_sharp, 12).contains(&PickNote::new_string_fret(1, 2)));
+        assert!(guitar(2).calc_note_places(&f_sharp, 12).contains(&open));
+        let picks = guitar(2).calc_melody_picks(&[f_sharp], &MelodyOptions::default());
+        assert_eq!(places(&picks), vec![Some((1, 0))]);
+        // max_fret is relative to the capo too
+        let high = [note(Pitch::F_SHARP, Octave::P5)];
+        let options = MelodyOptions {
+            position: 10,
+            max_fret: 12,
+        };
+        assert_eq!(places(&guitar(0).calc_melody_picks(&high, &options)), vec![None]);
+        assert_eq!(places(&guitar(2).calc_melody_picks(&high, &options)), vec![Some((1, 12))]);
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let notes = [
+            note(Pitch::E, Octave::P3),
+            note(Pitch::D, Octave::P2),
+            note(Pitch::E, Octave::P6),
+            note(Pitch::G, Octave::P3),
+        ];
+        let picks = guitar(0).calc_melody_picks(&notes, &MelodyOptions::default());
+        assert_eq!(places(&picks), vec![Some((4, 2)), None, None, Some((3, 0))]);
+    }
+
+    #[test]
+    fn test_position() {
+        let a = [note(Pitch::A, Octave::P3)];
+        let pick = |position| {
+            let options = MelodyOptions {
+                position,
+                ..Default::default()
+            };
+            places(&guitar(0).calc_melody_picks(&a, &options))[0]
+        };
+        assert_eq!(pick(1), Some((3, 2)));
+        assert_eq!(pick(7), Some((4, 7)));
+        assert_eq!(pick(10), Some((5, 12)));
+    }
+}
@@ -6,6 +6,7 @@ use crate::prelude::Finger;
 use crate::strum::StrumDirection;
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PickNote {
     pub string: u8,
     pub fret: Option<u8>,
@@ -59,6 +60,7 @@ impl PickNote {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum Pick {
     None,
     Single(PickNote),
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StrumDirection {
     Down,
     Up,
@@ -16,6 +17,7 @@ impl Display for StrumDirection {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum StrumStrings {
     All,
     Between(u8, u8),
@@ -30,6 +32,7 @@ impl Display for StrumStrings {
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Strum {
     pub strings: StrumStrings,
     pub direction: Option<StrumDirection>,
@@ -0,0 +1,372 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use notation_core::prelude::{Chord, Interval, Key, Scale, Semitones};
+
+use super::prelude::{Fretboard4, Fretboard6, HandShape4, HandShape6};
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct VoicingOptions {
+    /// Max distance between the lowest and highest fretted (non-open) notes.
+    pub max_span: u8,
+    /// Highest fret to start a voicing from.
+    pub max_fret: u8,
+    /// Least number of sounding strings.
+    pub min_strings: usize,
+    /// Allow leaving out the perfect fifth when not all strings can cover it.
+    pub allow_omit_fifth: bool,
+}
+impl Default for VoicingOptions {
+    fn default() -> Self {
+        Self {
+            max_span: 3,
+            max_fret: 12,
+            min_strings: 4,
+            allow_omit_fifth: true,
+        }
+    }
+}
+
+/// Fingers available for fretting, the thumb is not used.
+const FRETTING_FINGERS: usize = 4;
+
+/// What the voicings are searched for, shared by the steps of the search.
+struct VoicingSearch<'a> {
+    scale: &'a Scale,
+    key: &'a Key,
+    chord: &'a Chord,
+    options: &'a VoicingOptions,
+}
+
+macro_rules! impl_chord_voicing {
+    ($type:ident, $fretboard:ident, $hand_shape:ident, $strings:literal) => {
+        #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+        pub struct $type {
+            pub shape: $hand_shape,
+            pub span: u8,
+            pub lowest_fret: u8,
+            pub open_strings: usize,
+            pub muted_strings: usize,
+            pub barre: bool,
+            pub root_in_bass: bool,
+        }
+        impl Display for $type {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "<{}>({} score: {})", stringify!($type), self.shape, self.score())
+            }
+        }
+        impl $type {
+            /// Lower is better: compact, low on the neck, open strings, root in bass, no barre.
+            pub fn score(&self) -> i32 {
+                let mut score = self.span as i32 * 2 + self.lowest_fret as i32
+                    + self.muted_strings as i32 * 2
+                    - self.open_strings as i32;
+                if self.barre {
+                    score += 3;
+                }
+                if !self.root_in_bass {
+                    score += 6;
+                }
+                score
+            }
+        }
+
+        impl $fretboard {
+            /// Enumerate playable shapes for the chord, sorted by [`score()`] (best first).
+            ///
+            /// [`score()`]: $type::score
+            pub fn calc_chord_voicings(
+                &self,
+                scale: &Scale,
+                key: &Key,
+                chord: &Chord,
+                options: &VoicingOptions,
+            ) -> Vec<$type> {
+                let search = VoicingSearch {
+                    scale,
+                    key,
+                    chord,
+                    options,
+                };
+                let mut result = Vec::new();
+                let mut visited = HashSet::new();
+                let max_fret = (options.max_fret as usize).min(self.fret_num().saturating_sub(1)) as u8;
+                for low in 0..=max_fret {
+                    let mut candidates: Vec<Vec<Option<u8>>> = Vec::new();
+                    for string in 1..=$strings {
+                        let mut frets = vec![None];
+                        for fret in Self::window_frets(low, options.max_span, max_fret) {
+                            if let Some(note) = self.fretted_note(scale, key, string as u8, fret) {
+                                if chord.calc_interval(note.syllable).is_some() {
+                                    frets.push(Some(fret));
+                                }
+                            }
+                        }
+                        candidates.push(frets);
+                    }
+                    let mut frets = [None; $strings];
+                    self.collect_voicings(&search, &candidates, 0, &mut frets, &mut visited, &mut result);
+                }
+                result.sort_by_key(|x| x.score());
+                result
+            }
+            fn window_frets(low: u8, max_span: u8, max_fret: u8) -> Vec<u8> {
+                let mut frets = vec![0];
+                let from = low.max(1);
+                let to = (low + max_span).min(max_fret);
+                for fret in from..=to {
+                    frets.push(fret);
+                }
+                frets
+            }
+            fn collect_voicings(
+                &self,
+                search: &VoicingSearch,
+                candidates: &[Vec<Option<u8>>],
+                index: usize,
+                frets: &mut [Option<u8>; $strings],
+                visited: &mut HashSet<[Option<u8>; $strings]>,
+                result: &mut Vec<$type>,
+            ) {
+                if index == $strings {
+                    if !visited.contains(frets) {
+                        visited.insert(*frets);
+                        if let Some(voicing) = self.check_voicing(search, frets) {
+                            result.push(voicing);
+                        }
+                    }
+                    return;
+                }
+                for fret in candidates[index].iter() {
+                    frets[index] = *fret;
+                    self.collect_voicings(search, candidates, index + 1, frets, visited, result);
+                }
+            }
+            /// frets are absolute, index 0 is string 1 (the highest one).
+            fn check_voicing(&self, search: &VoicingSearch, frets: &[Option<u8>; $strings]) -> Option<$type> {
+                let VoicingSearch {
+                    scale,
+                    key,
+                    chord,
+                    options,
+                } = *search;
+                // muted strings are only allowed on the bass side, so the shape can be strummed
+                let played = frets.iter().filter(|x| x.is_some()).count();
+                if played < options.min_strings.min($strings) {
+                    return None;
+                }
+                let lowest_played = frets.iter().rposition(|x| x.is_some())?;
+                if frets[..=lowest_played].iter().any(|x| x.is_none()) {
+                    return None;
+                }
+                let fretted: Vec<u8> = frets.iter().flatten().filter(|x| **x > 0).copied().collect();
+                let lowest_fret = fretted.iter().min().copied().unwrap_or(0);
+                let highest_fret = fretted.iter().max().copied().unwrap_or(0);
+                let span = highest_fret - lowest_fret;
+                if span > options.max_span {
+                    return None;
+                }
+                // barre on the lowest fret when it is used on several strings with no open string above
+                let lowest_fret_strings = fretted.iter().filter(|x| **x == lowest_fret).count();
+                let barre = lowest_fret > 0
+                    && lowest_fret_strings >= 2
+                    && frets[..=lowest_played].iter().all(|x| x.map(|f| f >= lowest_fret).unwrap_or(false));
+                let fingers = if barre {
+                    fretted.len() - lowest_fret_strings + 1
+                } else {
+                    fretted.len()
+                };
+                if fingers > FRETTING_FINGERS {
+                    return None;
+                }
+                let mut syllables = Vec::new();
+                for (index, fret) in frets.iter().enumerate() {
+                    if let Some(fret) = fret {
+                        let note = self.fretted_note(scale, key, (index + 1) as u8, *fret)?;
+                        syllables.push((Semitones::from(note), note.syllable));
+                    }
+                }
+                let bass = syllables.iter().min_by_key(|x| x.0).map(|x| x.1)?;
+                if let Some(bass_interval) = chord.bass {
+                    if !bass_interval.is_matched(chord.root, bass) {
+                        return None;
+                    }
+                }
+                let has = |interval: &Interval| syllables.iter().any(|x| interval.is_matched(chord.root, x.1));
+                if !has(&Interval::Unison) {
+                    return None;
+                }
+                for interval in chord.intervals.get_intervals().iter() {
+                    if !has(interval) {
+                        let omittable = options.allow_omit_fifth && *interval == Interval::Perfect5th;
+                        if !omittable {
+                            return None;
+                        }
+                    }
+                }
+                let shape = if barre {
+                    $hand_shape::new_barre(
+                        lowest_fret,
+                        frets.map(|x| x.map(|f| f - lowest_fret)),
+                        [None; $strings],
+                    )
+                } else {
+                    $hand_shape::from(*frets)
+                };
+                Some($type {
+                    shape,
+                    span,
+                    lowest_fret,
+                    open_strings: frets.iter().filter(|x| **x == Some(0)).count(),
+                    muted_strings: $strings - played,
+                    barre,
+                    root_in_bass: Interval::Unison.is_matched(chord.root, bass),
+                })
+            }
+        }
+    };
+}
+
+impl_chord_voicing!(ChordVoicing6, Fretboard6, HandShape6, 6);
+impl_chord_voicing!(ChordVoicing4, Fretboard4, HandShape4, 4);
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Intervals, Octave, Pitch, Syllable};
+
+    use super::*;
+
+    fn guitar() -> Fretboard6 {
+        let string_notes = [
+            (Pitch::E, Octave::P4).into(),
+            (Pitch::B, Octave::P3).into(),
+            (Pitch::G, Octave::P3).into(),
+            (Pitch::D, Octave::P3).into(),
+            (Pitch::A, Octave::P2).into(),
+            (Pitch::E, Octave::P2).into(),
+        ];
+        Fretboard6::new(22, string_notes, 0)
+    }
+
+    fn major(root: Syllable) -> Chord {
+        Chord::new(root, Intervals::Triad(Interval::Major3nd, Interval::Perfect5th), None)
+    }
+
+    fn voicings(chord: &Chord, options: &VoicingOptions) -> Vec<ChordVoicing6> {
+        guitar().calc_chord_voicings(&Scale::Major, &Key::C, chord, options)
+    }
+
+    fn frets(voicing: &ChordVoicing6) -> Vec<Option<u8>> {
+        (1..=6).map(|x| voicing.shape.string_fret_with_barre(x)).collect()
+    }
+
+    #[test]
+    fn test_open_shapes_ranked_first() {
+        let options = VoicingOptions::default();
+        let c = voicings(&major(Syllable::Do), &options);
+        assert_eq!(c[0].shape, HandShape6::from([Some(0), Some(1), Some(0), Some(2), Some(3), None]));
+        assert!(c[0].root_in_bass && !c[0].barre);
+        let g = voicings(&major(Syllable::So), &options);
+        assert_eq!(g[0].shape, HandShape6::from([Some(3), Some(0), Some(0), Some(0), Some(2), Some(3)]));
+        for list in [c, g] {
+            assert!(list.windows(2).all(|x| x[0].score() <= x[1].score()));
+        }
+    }
+
+    #[test]
+    fn test_barre_voicing() {
+        let options = VoicingOptions {
+            max_fret: 5,
+            ..VoicingOptions::default()
+        };
+        let f = voicings(&major(Syllable::Fa), &options);
+        let full = f.iter().find(|x| x.muted_strings == 0 && x.open_strings == 0).unwrap();
+        assert!(full.barre);
+        assert_eq!(full.shape.barre, Some(1));
+        assert_eq!(frets(full), vec![Some(1), Some(1), Some(2), Some(3), Some(3), Some(1)]);
+    }
+
+    #[test]
+    fn test_voicings_are_playable() {
+        let options = VoicingOptions::default();
+        let minor7 = Intervals::Tetrad(Interval::Minor3nd, Interval::Perfect5th, Interval::Minor7th);
+        let chords = [
+            major(Syllable::Do),
+            major(Syllable::Re),
+            Chord::new(Syllable::La, minor7, None),
+        ];
+        for chord in chords.iter() {
+            let list = voicings(chord, &options);
+            assert!(!list.is_empty(), "no voicing for {}", chord);
+            for voicing in list.iter() {
+                let frets = frets(voicing);
+                let played = frets.iter().filter(|x| x.is_some()).count();
+                assert!(played >= options.min_strings);
+                // muted strings only on the bass side
+                let lowest = frets.iter().rposition(|x| x.is_some()).unwrap();
+                assert!(frets[..=lowest].iter().all(|x| x.is_some()), "{}", voicing);
+                let fretted: Vec<u8> = frets.iter().flatten().filter(|x| **x > 0).copied().collect();
+                let span = fretted.iter().max().unwrap_or(&0) - fretted.iter().min().unwrap_or(&0);
+                assert!(span <= options.max_span, "{}", voicing);
+                let fingers = if voicing.barre {
+                    fretted.iter().filter(|x| **x != voicing.lowest_fret).count() + 1
+                } else {
+                    fretted.len()
+                };
+                assert!(fingers <= FRETTING_FINGERS, "{}", voicing);
+                let fretboard = guitar();
+                let syllables: Vec<Syllable> = frets
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, x)| {
+                        x.and_then(|f| fretboard.fretted_note(&Scale::Major, &Key::C, i as u8 + 1, f))
+                    })
+                    .map(|x| x.syllable)
+                    .collect();
+                for syllable in syllables.iter() {
+                    assert!(chord.calc_interval(*syllable).is_some(), "{}", voicing);
+                }
+                for interval in [Interval::Unison, chord.intervals.get_intervals()[0]] {
+                    assert!(syllables.iter().any(|x| interval.is_matched(chord.root, *x)), "{}", voicing);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_voicing_options() {
+        let c = major(Syllable::Do);
+        let strict = VoicingOptions {
+            allow_omit_fifth: false,
+            min_strings: 5,
+            ..VoicingOptions::default()
+        };
+        for voicing in voicings(&c, &strict).iter() {
+            assert!(6 - voicing.muted_strings >= 5);
+            let has_fifth = (1..=6)
+                .filter_map(|x| voicing.shape.string_fret_with_barre(x).map(|f| (x, f)))
+                .filter_map(|(x, f)| guitar().fretted_note(&Scale::Major, &Key::C, x, f))
+                .any(|x| Interval::Perfect5th.is_matched(c.root, x.syllable));
+            assert!(has_fifth, "{}", voicing);
+        }
+        let low = VoicingOptions {
+            max_fret: 3,
+            ..VoicingOptions::default()
+        };
+        assert!(voicings(&c, &low).iter().all(|x| x.lowest_fret <= 3));
+    }
+
+    #[test]
+    fn test_slash_chord_bass() {
+        let c_e = Chord::new(
+            Syllable::Do,
+            Intervals::Triad(Interval::Major3nd, Interval::Perfect5th),
+            Some(Interval::Major3nd),
+        );
+        let list = voicings(&c_e, &VoicingOptions::default());
+        assert!(!list.is_empty());
+        for voicing in list.iter() {
+            assert!(!voicing.root_in_bass);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use notation_core::prelude::{Octave, Pitch, Semitones};
+use notation_fretted::prelude::Fretboard4;
+
+use crate::tuning::TuningUtil;
+
+pub const BASS_STRING_NUM: usize = 4;
+
+pub const BASS_FRET_NUM_STANDARD: usize = 20;
+pub const BASS_FRET_NUM_EXTENDED: usize = 24;
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum BassTuning {
+    #[default]
+    Standard,
+    StandardFlat,
+    DropD,
+    Custom([Semitones; 4]),
+}
+
+impl From<BassTuning> for [Semitones; 4] {
+    fn from(v: BassTuning) -> Self {
+        match v {
+            BassTuning::Standard => [
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+                (Pitch::A, Octave::P1).into(),
+                (Pitch::E, Octave::P1).into(),
+            ],
+            BassTuning::StandardFlat => [
+                (Pitch::G_FLAT, Octave::P2).into(),
+                (Pitch::D_FLAT, Octave::P2).into(),
+                (Pitch::A_FLAT, Octave::P1).into(),
+                (Pitch::E_FLAT, Octave::P1).into(),
+            ],
+            BassTuning::DropD => [
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+                (Pitch::A, Octave::P1).into(),
+                (Pitch::D, Octave::P1).into(),
+            ],
+            BassTuning::Custom(string_notes) => string_notes,
+        }
+    }
+}
+
+impl BassTuning {
+    pub const PRESETS: [Self; 3] = [Self::Standard, Self::StandardFlat, Self::DropD];
+    pub fn to_ident(&self) -> String {
+        match self {
+            Self::Custom(string_notes) => TuningUtil::custom_ident(string_notes),
+            _ => format!("{:?}", self),
+        }
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "StandardFlat" => Self::StandardFlat,
+            "DropD" => Self::DropD,
+            _ => Self::Standard,
+        }
+    }
+}
+
+impl_tuning!(BassTuning, 4);
+
+pub struct BassUtil();
+
+impl BassUtil {
+    pub fn new_bass_fretboard(total_fret_num: usize, strings: [Semitones; 4]) -> Fretboard4 {
+        Fretboard4 {
+            total_fret_num,
+            string_notes: strings,
+            capo: 0,
+        }
+    }
+
+    pub fn new_standard_bass_fretboard(tuning: Option<BassTuning>) -> Fretboard4 {
+        Self::new_bass_fretboard(BASS_FRET_NUM_STANDARD, tuning.unwrap_or_default().into())
+    }
+
+    pub fn new_default_fretboard() -> Fretboard4 {
+        BassUtil::new_standard_bass_fretboard(None)
+    }
+}
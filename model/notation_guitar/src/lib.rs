@@ -1,9 +1,20 @@
-pub mod guitar;
+#[macro_use]
 pub mod tuning;
 
+pub mod bass;
+pub mod guitar;
+pub mod mandolin;
+pub mod ukulele;
+
 pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::bass::*;
     #[doc(hidden)]
     pub use crate::guitar::*;
     #[doc(hidden)]
-    pub use crate::tuning::GuitarTuning;
+    pub use crate::mandolin::*;
+    #[doc(hidden)]
+    pub use crate::tuning::{GuitarTuning, TuningUtil};
+    #[doc(hidden)]
+    pub use crate::ukulele::*;
 }
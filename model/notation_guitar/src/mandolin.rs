@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use notation_core::prelude::{Octave, Pitch, Semitones};
+use notation_fretted::prelude::Fretboard4;
+
+use crate::tuning::TuningUtil;
+
+/// Mandolin got 4 courses of paired strings, each course is treated as a single string.
+pub const MANDOLIN_STRING_NUM: usize = 4;
+
+pub const MANDOLIN_FRET_NUM: usize = 20;
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum MandolinTuning {
+    #[default]
+    Standard,
+    Custom([Semitones; 4]),
+}
+
+impl From<MandolinTuning> for [Semitones; 4] {
+    fn from(v: MandolinTuning) -> Self {
+        match v {
+            MandolinTuning::Standard => [
+                (Pitch::E, Octave::P5).into(),
+                (Pitch::A, Octave::P4).into(),
+                (Pitch::D, Octave::P4).into(),
+                (Pitch::G, Octave::P3).into(),
+            ],
+            MandolinTuning::Custom(string_notes) => string_notes,
+        }
+    }
+}
+
+impl MandolinTuning {
+    pub const PRESETS: [Self; 1] = [Self::Standard];
+    pub fn to_ident(&self) -> String {
+        match self {
+            Self::Custom(string_notes) => TuningUtil::custom_ident(string_notes),
+            _ => format!("{:?}", self),
+        }
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        Self::from_preset_ident(ident).unwrap_or_default()
+    }
+}
+
+impl_tuning!(MandolinTuning, 4);
+
+pub struct MandolinUtil();
+
+impl MandolinUtil {
+    pub fn new_mandolin_fretboard(total_fret_num: usize, strings: [Semitones; 4]) -> Fretboard4 {
+        Fretboard4 {
+            total_fret_num,
+            string_notes: strings,
+            capo: 0,
+        }
+    }
+
+    pub fn new_default_fretboard() -> Fretboard4 {
+        Self::new_mandolin_fretboard(MANDOLIN_FRET_NUM, MandolinTuning::default().into())
+    }
+}
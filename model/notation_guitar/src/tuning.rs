@@ -2,17 +2,96 @@ use serde::{Deserialize, Serialize};
 
 use notation_core::prelude::{Semitones, Pitch, Octave};
 
-#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct TuningUtil();
+
+impl TuningUtil {
+    pub fn custom_ident(string_notes: &[Semitones]) -> String {
+        format!(
+            "[{}]",
+            string_notes
+                .iter()
+                .rev()
+                .map(|x| {
+                    let (pitch, octave) = x.as_pitch_octave();
+                    format!("{}{}", pitch, Semitones::from(octave).0 / 12)
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+        )
+    }
+    /// Calculate notes of the open strings from pitches, which are from the lowest string to
+    /// the highest one, when octave is not given, the one closest to the same string in
+    /// standard tuning is used.
+    pub fn calc_string_notes<const N: usize>(
+        standard: [Semitones; N],
+        pitches: [(Pitch, Option<Octave>); N],
+    ) -> [Semitones; N] {
+        let mut string_notes = standard;
+        for (index, (pitch, octave)) in pitches.iter().enumerate() {
+            let string_index = N - 1 - index;
+            string_notes[string_index] = match octave {
+                Some(octave) => (*pitch, *octave).into(),
+                None => Self::closest_note(*pitch, standard[string_index]),
+            };
+        }
+        string_notes
+    }
+    fn closest_note(pitch: Pitch, target: Semitones) -> Semitones {
+        let pitch_val = Semitones::from(pitch).0;
+        let octave_val = target.0 - target.0.rem_euclid(12);
+        let mut note = octave_val + pitch_val;
+        if note - target.0 > 6 {
+            note -= 12;
+        } else if target.0 - note > 6 {
+            note += 12;
+        }
+        Semitones(note)
+    }
+}
+
+macro_rules! impl_tuning {
+    ($type:ident, $strings:literal) => {
+        impl $type {
+            /// Returns the preset matching the string notes, or `Custom` if none matches.
+            pub fn from_string_notes(string_notes: [Semitones; $strings]) -> Self {
+                for preset in Self::PRESETS.iter() {
+                    if <[Semitones; $strings]>::from(*preset) == string_notes {
+                        return *preset;
+                    }
+                }
+                Self::Custom(string_notes)
+            }
+            /// Create tuning from pitches of the open strings, from the lowest string to the highest one.
+            pub fn from_pitches(pitches: [(Pitch, Option<Octave>); $strings]) -> Self {
+                let standard: [Semitones; $strings] = Self::Standard.into();
+                Self::from_string_notes(crate::tuning::TuningUtil::calc_string_notes(
+                    standard, pitches,
+                ))
+            }
+            /// Returns the preset named by the ident, `None` if there is no such preset.
+            pub fn from_preset_ident(ident: &str) -> Option<Self> {
+                Self::PRESETS.iter().copied().find(|x| x.to_ident() == ident)
+            }
+            pub fn is_custom(&self) -> bool {
+                matches!(self, Self::Custom(_))
+            }
+        }
+    };
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum GuitarTuning {
+    #[default]
     Standard,
     StandardFlat,
     DropD,
-}
-
-impl Default for GuitarTuning {
-    fn default() -> Self {
-        Self::Standard
-    }
+    DropDFlat,
+    DropC,
+    OpenG,
+    OpenD,
+    Dadgad,
+    Custom([Semitones; 6]),
 }
 
 impl From<GuitarTuning> for [Semitones; 6] {
@@ -42,19 +121,120 @@ impl From<GuitarTuning> for [Semitones; 6] {
                 (Pitch::A, Octave::P2).into(),
                 (Pitch::D, Octave::P2).into(),
             ],
+            GuitarTuning::DropDFlat => [
+                (Pitch::E_FLAT, Octave::P4).into(),
+                (Pitch::B_FLAT, Octave::P3).into(),
+                (Pitch::G_FLAT, Octave::P3).into(),
+                (Pitch::D_FLAT, Octave::P3).into(),
+                (Pitch::A_FLAT, Octave::P2).into(),
+                (Pitch::D_FLAT, Octave::P2).into(),
+            ],
+            GuitarTuning::DropC => [
+                (Pitch::D, Octave::P4).into(),
+                (Pitch::A, Octave::P3).into(),
+                (Pitch::F, Octave::P3).into(),
+                (Pitch::C, Octave::P3).into(),
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::C, Octave::P2).into(),
+            ],
+            GuitarTuning::OpenG => [
+                (Pitch::D, Octave::P4).into(),
+                (Pitch::B, Octave::P3).into(),
+                (Pitch::G, Octave::P3).into(),
+                (Pitch::D, Octave::P3).into(),
+                (Pitch::G, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+            ],
+            GuitarTuning::OpenD => [
+                (Pitch::D, Octave::P4).into(),
+                (Pitch::A, Octave::P3).into(),
+                (Pitch::F_SHARP, Octave::P3).into(),
+                (Pitch::D, Octave::P3).into(),
+                (Pitch::A, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+            ],
+            GuitarTuning::Dadgad => [
+                (Pitch::D, Octave::P4).into(),
+                (Pitch::A, Octave::P3).into(),
+                (Pitch::G, Octave::P3).into(),
+                (Pitch::D, Octave::P3).into(),
+                (Pitch::A, Octave::P2).into(),
+                (Pitch::D, Octave::P2).into(),
+            ],
+            GuitarTuning::Custom(string_notes) => string_notes,
         }
     }
 }
 
 impl GuitarTuning {
+    pub const PRESETS: [Self; 8] = [
+        Self::Standard,
+        Self::StandardFlat,
+        Self::DropD,
+        Self::DropDFlat,
+        Self::DropC,
+        Self::OpenG,
+        Self::OpenD,
+        Self::Dadgad,
+    ];
     pub fn to_ident(&self) -> String {
-        format!("{:?}", self)
+        match self {
+            Self::Custom(string_notes) => TuningUtil::custom_ident(string_notes),
+            _ => format!("{:?}", self),
+        }
     }
     pub fn from_ident(ident: &str) -> Self {
         match ident {
             "StandardFlat" => Self::StandardFlat,
             "DropD" => Self::DropD,
+            "DropDFlat" => Self::DropDFlat,
+            "DropC" => Self::DropC,
+            "OpenG" => Self::OpenG,
+            "OpenD" => Self::OpenD,
+            "Dadgad" | "DADGAD" => Self::Dadgad,
             _ => Self::Standard,
         }
     }
 }
+
+impl_tuning!(GuitarTuning, 6);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closest_note() {
+        let e2: Semitones = (Pitch::E, Octave::P2).into();
+        let closest = |pitch| TuningUtil::closest_note(pitch, e2);
+        assert_eq!(closest(Pitch::E), e2);
+        assert_eq!(closest(Pitch::D), (Pitch::D, Octave::P2).into());
+        assert_eq!(closest(Pitch::A), (Pitch::A, Octave::P2).into());
+        assert_eq!(closest(Pitch::B), (Pitch::B, Octave::P1).into());
+        assert_eq!(closest(Pitch::A_SHARP), (Pitch::A_SHARP, Octave::P2).into());
+        assert_eq!(closest(Pitch::C), (Pitch::C, Octave::P2).into());
+        let b3: Semitones = (Pitch::B, Octave::P3).into();
+        assert_eq!(TuningUtil::closest_note(Pitch::C, b3), (Pitch::C, Octave::P4).into());
+        assert_eq!(TuningUtil::closest_note(Pitch::A, b3), (Pitch::A, Octave::P3).into());
+    }
+
+    #[test]
+    fn test_from_pitches() {
+        let standard = [
+            (Pitch::E, None),
+            (Pitch::A, None),
+            (Pitch::D, None),
+            (Pitch::G, None),
+            (Pitch::B, None),
+            (Pitch::E, None),
+        ];
+        assert_eq!(GuitarTuning::from_pitches(standard), GuitarTuning::Standard);
+        let mut drop_c = standard;
+        drop_c[0] = (Pitch::C, Some(Octave::P2));
+        let tuning = GuitarTuning::from_pitches(drop_c);
+        assert!(tuning.is_custom());
+        assert_eq!(<[Semitones; 6]>::from(tuning)[5], (Pitch::C, Octave::P2).into());
+        assert_eq!(GuitarTuning::from_preset_ident("DropC"), Some(GuitarTuning::DropC));
+        assert_eq!(GuitarTuning::from_preset_ident("OpenDD"), None);
+    }
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+use notation_core::prelude::{Octave, Pitch, Semitones};
+use notation_fretted::prelude::Fretboard4;
+
+use crate::tuning::TuningUtil;
+
+pub const UKULELE_STRING_NUM: usize = 4;
+
+pub const UKULELE_FRET_NUM_SOPRANO: usize = 12;
+pub const UKULELE_FRET_NUM_CONCERT: usize = 15;
+pub const UKULELE_FRET_NUM_TENOR: usize = 18;
+
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum UkuleleTuning {
+    #[default]
+    Standard,
+    LowG,
+    Baritone,
+    Custom([Semitones; 4]),
+}
+
+impl From<UkuleleTuning> for [Semitones; 4] {
+    fn from(v: UkuleleTuning) -> Self {
+        match v {
+            // re-entrant, the 4th string is higher than the 3rd one
+            UkuleleTuning::Standard => [
+                (Pitch::A, Octave::P4).into(),
+                (Pitch::E, Octave::P4).into(),
+                (Pitch::C, Octave::P4).into(),
+                (Pitch::G, Octave::P4).into(),
+            ],
+            UkuleleTuning::LowG => [
+                (Pitch::A, Octave::P4).into(),
+                (Pitch::E, Octave::P4).into(),
+                (Pitch::C, Octave::P4).into(),
+                (Pitch::G, Octave::P3).into(),
+            ],
+            UkuleleTuning::Baritone => [
+                (Pitch::E, Octave::P4).into(),
+                (Pitch::B, Octave::P3).into(),
+                (Pitch::G, Octave::P3).into(),
+                (Pitch::D, Octave::P3).into(),
+            ],
+            UkuleleTuning::Custom(string_notes) => string_notes,
+        }
+    }
+}
+
+impl UkuleleTuning {
+    pub const PRESETS: [Self; 3] = [Self::Standard, Self::LowG, Self::Baritone];
+    pub fn to_ident(&self) -> String {
+        match self {
+            Self::Custom(string_notes) => TuningUtil::custom_ident(string_notes),
+            _ => format!("{:?}", self),
+        }
+    }
+    pub fn from_ident(ident: &str) -> Self {
+        match ident {
+            "LowG" => Self::LowG,
+            "Baritone" => Self::Baritone,
+            _ => Self::Standard,
+        }
+    }
+}
+
+impl_tuning!(UkuleleTuning, 4);
+
+pub struct UkuleleUtil();
+
+impl UkuleleUtil {
+    pub fn new_ukulele_fretboard(total_fret_num: usize, strings: [Semitones; 4]) -> Fretboard4 {
+        Fretboard4 {
+            total_fret_num,
+            string_notes: strings,
+            capo: 0,
+        }
+    }
+
+    pub fn new_concert_ukulele_fretboard(tuning: Option<UkuleleTuning>) -> Fretboard4 {
+        Self::new_ukulele_fretboard(UKULELE_FRET_NUM_CONCERT, tuning.unwrap_or_default().into())
+    }
+
+    pub fn new_default_fretboard() -> Fretboard4 {
+        UkuleleUtil::new_concert_ukulele_fretboard(None)
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{CadenceKind, Chord, HarmonicFunction, RomanNumeral, Units};
+
+use crate::prelude::{LaneKind, Tab, TabBar};
+
+/// A chord in the tab labelled in the key, see [`Tab::calc_chord_analysis()`].
+#[derive(Clone, Debug)]
+pub struct ChordAnalysis {
+    pub bar_ordinal: usize,
+    pub in_bar_pos: Units,
+    pub chord: Chord,
+    pub numeral: RomanNumeral,
+    pub function: HarmonicFunction,
+    /// Set on the last chord of a section if it ends with a cadence.
+    pub cadence: Option<CadenceKind>,
+}
+impl Display for ChordAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<ChordAnalysis>({}:{} {} {}",
+            self.bar_ordinal, self.in_bar_pos.to_f32(), self.numeral, self.function,
+        )?;
+        if let Some(cadence) = self.cadence {
+            write!(f, " {}", cadence)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl TabBar {
+    /// Chords in the chord lane with their in bar positions.
+    pub fn get_chords_with_pos(&self) -> Vec<(Units, Chord)> {
+        let mut chords = Vec::new();
+        if let Some(lane) = self.get_lane_of_kind(LaneKind::Chord, None) {
+            for entry in lane.entries.iter() {
+                if let Some(chord) = entry.proto().as_core().and_then(|x| x.as_chord()) {
+                    chords.push((entry.props.in_bar_pos, *chord));
+                }
+            }
+        }
+        chords
+    }
+    fn is_section_end(&self, next: Option<&Arc<TabBar>>) -> bool {
+        match next {
+            Some(next) => next.props.section_ordinal != self.props.section_ordinal,
+            None => true,
+        }
+    }
+}
+
+impl Tab {
+    /// Roman numeral and function for every chord in playing order, with cadences
+    /// at the end of sections.
+    pub fn calc_chord_analysis(&self) -> Vec<ChordAnalysis> {
+        let mut chords: Vec<(usize, Units, Chord, bool)> = Vec::new();
+        for (index, bar) in self.bars.iter().enumerate() {
+            let bar_chords = bar.get_chords_with_pos();
+            let section_end = bar.is_section_end(self.bars.get(index + 1));
+            let count = bar_chords.len();
+            for (chord_index, (in_bar_pos, chord)) in bar_chords.into_iter().enumerate() {
+                let last = section_end && chord_index + 1 == count;
+                chords.push((bar.props.bar_ordinal, in_bar_pos, chord, last));
+            }
+        }
+        let scale = self.meta.scale;
+        let key = self.meta.key;
+        let numerals: Vec<RomanNumeral> = chords
+            .iter()
+            .enumerate()
+            .map(|(index, (_, _, chord, _))| {
+                let next = Self::find_next_chord(&chords, index);
+                RomanNumeral::from_chord(&scale, &key, chord, next)
+            })
+            .collect();
+        chords
+            .iter()
+            .enumerate()
+            .map(|(index, (bar_ordinal, in_bar_pos, chord, last))| {
+                let numeral = numerals[index];
+                let cadence = if *last {
+                    Self::find_prev_chord(&chords, index)
+                        .and_then(|prev| CadenceKind::from_numerals(&numerals[prev], &numeral))
+                } else {
+                    None
+                };
+                ChordAnalysis {
+                    bar_ordinal: *bar_ordinal,
+                    in_bar_pos: *in_bar_pos,
+                    chord: *chord,
+                    numeral,
+                    function: numeral.calc_function(),
+                    cadence,
+                }
+            })
+            .collect()
+    }
+    /// Next different chord in the same section, repeated chords are skipped.
+    fn find_next_chord(chords: &[(usize, Units, Chord, bool)], index: usize) -> Option<&Chord> {
+        let current = &chords[index];
+        if current.3 {
+            return None;
+        }
+        for next in chords[index + 1..].iter() {
+            if next.2 != current.2 {
+                return Some(&next.2);
+            }
+            if next.3 {
+                break;
+            }
+        }
+        None
+    }
+    /// Index of the previous different chord in the same section.
+    fn find_prev_chord(chords: &[(usize, Units, Chord, bool)], index: usize) -> Option<usize> {
+        let current = &chords[index];
+        for prev in (0..index).rev() {
+            if chords[prev].3 {
+                break;
+            }
+            if chords[prev].2 != current.2 {
+                return Some(prev);
+            }
+        }
+        None
+    }
+    pub fn get_bar_chord_analysis(&self, bar_ordinal: usize) -> Vec<ChordAnalysis> {
+        self.calc_chord_analysis()
+            .into_iter()
+            .filter(|x| x.bar_ordinal == bar_ordinal)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{
+        Bar, BarLayer, ChordSymbol, CoreEntry, Duration, Form, Key, ProtoEntry, Scale, Section,
+        SectionKind, Slice, SliceBegin, SliceEnd, Tab as ProtoTab, Track as ProtoTrack, TrackKind,
+    };
+
+    use super::*;
+
+    fn chord(text: &str, duration: Duration) -> ProtoEntry {
+        let chord = ChordSymbol::from_text(text).unwrap().to_chord(&Scale::Major, &Key::C);
+        CoreEntry::Chord(chord, duration).into()
+    }
+
+    fn bar(index: usize, count: usize) -> Bar {
+        let slice = Slice::new(SliceBegin::Index(index), SliceEnd::Count(count), None);
+        Bar::new(vec![BarLayer::new("chords".into(), vec![slice])])
+    }
+
+    /// | C D7 | G7 C | in the verse, then | F | F C | in the chorus, in C major.
+    fn tab() -> Arc<Tab> {
+        let mut tab = ProtoTab::new_empty();
+        let entries = vec![
+            chord("C", Duration::_1_2),
+            chord("D7", Duration::_1_2),
+            chord("G7", Duration::_1_2),
+            chord("C", Duration::_1_2),
+            chord("F", Duration::_1),
+            chord("F", Duration::_1_2),
+            chord("C", Duration::_1_2),
+        ];
+        tab.tracks.push(ProtoTrack::new("chords".into(), TrackKind::Chord, entries));
+        tab.sections = vec![
+            Section::new("verse".into(), SectionKind::Verse, vec![bar(0, 2), bar(2, 2)]),
+            Section::new("chorus".into(), SectionKind::Chorus, vec![bar(4, 1), bar(5, 2)]),
+        ];
+        tab.form = Form::from(vec!["verse", "chorus"]);
+        let (tab, report) = Tab::try_parse_arc(tab, false, None, true).unwrap();
+        assert!(report.is_empty());
+        tab
+    }
+
+    #[test]
+    fn test_chord_analysis() {
+        let analysis = tab().calc_chord_analysis();
+        let labels: Vec<String> = analysis.iter().map(|x| x.numeral.to_text()).collect();
+        assert_eq!(labels, vec!["I", "V7/V", "V7", "I", "IV", "IV", "I"]);
+        let functions: Vec<HarmonicFunction> = analysis.iter().map(|x| x.function).collect();
+        assert_eq!(
+            functions,
+            vec![
+                HarmonicFunction::Tonic,
+                HarmonicFunction::Dominant,
+                HarmonicFunction::Dominant,
+                HarmonicFunction::Tonic,
+                HarmonicFunction::Subdominant,
+                HarmonicFunction::Subdominant,
+                HarmonicFunction::Tonic,
+            ]
+        );
+        assert_eq!(analysis[1].bar_ordinal, analysis[0].bar_ordinal);
+        assert_eq!(analysis[1].in_bar_pos, Units::new(1, 2));
+    }
+
+    #[test]
+    fn test_section_end_cadences() {
+        let analysis = tab().calc_chord_analysis();
+        let cadences: Vec<Option<CadenceKind>> = analysis.iter().map(|x| x.cadence).collect();
+        assert_eq!(
+            cadences,
+            vec![
+                None,
+                None,
+                None,
+                Some(CadenceKind::Authentic),
+                None,
+                None,
+                Some(CadenceKind::Plagal),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bar_chord_analysis() {
+        let tab = tab();
+        let last_bar = tab.bars.last().unwrap().props.bar_ordinal;
+        let bar = tab.get_bar_chord_analysis(last_bar);
+        assert_eq!(bar.len(), 2);
+        assert_eq!(bar[1].cadence, Some(CadenceKind::Plagal));
+    }
+}
@@ -1,5 +1,8 @@
 use fehler::throws;
 
+use notation_proto::prelude::DiagnosticLocation;
+
+use crate::parse::ParseReport;
 use crate::prelude::{ParseError, Slice, Track};
 
 use std::fmt::Display;
@@ -10,24 +13,27 @@ pub struct BarLayer {
     pub index: usize,
     pub track: Arc<Track>,
     pub slices: Vec<Slice>,
+    pub voice: usize,
 }
 impl Display for BarLayer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "<BarLayer>({} {} S:{})",
+            "<BarLayer>({} {} S:{} V:{})",
             self.index,
             self.track,
-            self.slices.len()
+            self.slices.len(),
+            self.voice
         )
     }
 }
 impl BarLayer {
-    pub fn new(index: usize, track: Arc<Track>, slices: Vec<Slice>) -> Self {
+    pub fn new(index: usize, track: Arc<Track>, slices: Vec<Slice>, voice: usize) -> Self {
         Self {
             index,
             track,
             slices,
+            voice,
         }
     }
 }
@@ -44,28 +50,56 @@ impl Display for Bar {
 impl BarLayer {
     #[throws(ParseError)]
     pub fn try_new(
-        index: usize,
+        location: DiagnosticLocation,
         proto: notation_proto::prelude::BarLayer,
-        tracks: &Vec<Arc<Track>>,
+        tracks: &[Arc<Track>],
+        report: &mut ParseReport,
     ) -> Self {
+        let location = DiagnosticLocation {
+            track: Some(proto.track.clone()),
+            ..location
+        };
         let track = tracks
             .iter()
             .find(|x| x.id == proto.track)
             .cloned()
-            .ok_or(ParseError::TrackNotFound(proto.track))?;
-        Self::new(index, track, proto.slices)
+            .ok_or_else(|| ParseError::TrackNotFound {
+                track: proto.track.clone(),
+                location: Box::new(location.clone()),
+            })?;
+        for (slice_index, slice) in proto.slices.iter().enumerate() {
+            if let Some(mark) = track.find_missing_mark(slice) {
+                let location = DiagnosticLocation {
+                    slice_index: Some(slice_index),
+                    ..location.clone()
+                };
+                report.add(ParseError::MarkNotFound {
+                    mark,
+                    location: Box::new(location),
+                })?;
+            }
+        }
+        Self::new(location.layer_index.unwrap_or(0), track, proto.slices, proto.voice)
     }
 }
 impl Bar {
     #[throws(ParseError)]
     pub fn try_new(
+        section_id: &str,
         index: usize,
         proto: notation_proto::prelude::Bar,
         tracks: &Vec<Arc<Track>>,
+        report: &mut ParseReport,
     ) -> Self {
         let mut layers = Vec::new();
         for (layer_index, layer) in proto.layers.into_iter().enumerate() {
-            layers.push(BarLayer::try_new(layer_index, layer, tracks).map(Arc::new)?);
+            let location = DiagnosticLocation {
+                section: Some(section_id.to_owned()),
+                bar_index: Some(index),
+                layer_index: Some(layer_index),
+                ..DiagnosticLocation::default()
+            };
+            layers.push(BarLayer::try_new(location, layer, tracks, report).map(Arc::new)?);
         }
         Self {
             index: index,
@@ -45,8 +45,9 @@ impl BarLane {
         index: usize,
         track: &Arc<Track>,
         slice: Slice,
+        voice: usize,
     ) -> Option<Arc<Self>> {
-        let model_entries = track.get_entries(&slice.begin, &slice.end);
+        let model_entries = track.get_entries(&slice);
         let kind = LaneKind::of_entries(&track.kind, &model_entries);
         if !kind.is_none() {
             Some(Arc::<Self>::new_cyclic(|weak_self| {
@@ -54,7 +55,7 @@ impl BarLane {
                     index,
                     track: track.props,
                 };
-                let entries = LaneEntry::new_entries(model_entries, weak_self, slice);
+                let entries = LaneEntry::new_entries(model_entries, weak_self, slice, voice);
                 Self {
                     bar,
                     kind,
@@ -100,8 +101,27 @@ impl BarLane {
             }
         })
     }
+    /// Voices of the entries, lanes merged from layers of several voices have more than one.
+    pub fn get_voices(&self) -> Vec<usize> {
+        let mut voices: Vec<usize> = self.entries.iter().map(|x| x.props.voice).collect();
+        voices.sort();
+        voices.dedup();
+        voices
+    }
+    pub fn is_multi_voice(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|x| x.props.voice != self.entries[0].props.voice)
+    }
+    pub fn get_voice_entries(&self, voice: usize) -> Vec<Arc<LaneEntry>> {
+        self.entries
+            .iter()
+            .filter(|x| x.props.voice == voice)
+            .cloned()
+            .collect()
+    }
     pub fn bar(&self) -> Option<Arc<TabBar>> {
-        self.bar.upgrade().map(|x| x.clone())
+        self.bar.upgrade()
     }
     pub fn tab(&self) -> Option<Arc<Tab>> {
         self.bar().and_then(|x| x.tab())
@@ -133,7 +153,7 @@ impl BarLane {
         predicate: &F,
     ) -> Option<T> {
         self.get_entry(&|x: &LaneEntry| {
-            if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+            if x.props.in_bar_pos > in_bar_pos {
                 predicate(x)
             } else {
                 None
@@ -141,3 +161,89 @@ impl BarLane {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{
+        Bar, BarLayer, CoreEntry, Duration, Form, Note, Octave, Pitch, ProtoEntry, Section,
+        SectionKind, SliceBegin, SliceEnd, Syllable, Tab as ProtoTab, Tone, Track as ProtoTrack,
+        TrackKind,
+    };
+
+    use super::*;
+
+    fn tone(pitch: Pitch, duration: Duration) -> ProtoEntry {
+        let note = Note::new(Octave::P4, pitch, Syllable::Do);
+        CoreEntry::Tone(Tone::from(note), duration).into()
+    }
+
+    fn layer(voice: usize, index: usize, count: usize) -> BarLayer {
+        let slice = Slice::new(SliceBegin::Index(index), SliceEnd::Count(count), None);
+        BarLayer::new_voice("vocal".into(), voice, vec![slice])
+    }
+
+    /// | C/2 D/2 | in the main voice, | _/4 E/4 F/2 | in voice 1, in one bar.
+    fn lane() -> (Arc<Tab>, Arc<BarLane>) {
+        let mut tab = ProtoTab::new_empty();
+        let entries = vec![
+            tone(Pitch::C, Duration::_1_2),
+            tone(Pitch::D, Duration::_1_2),
+            CoreEntry::Rest(Duration::_1_4).into(),
+            tone(Pitch::E, Duration::_1_4),
+            tone(Pitch::F, Duration::_1_2),
+        ];
+        tab.tracks.push(ProtoTrack::new("vocal".into(), TrackKind::Vocal, entries));
+        let bar = Bar::new(vec![layer(0, 0, 2), layer(1, 2, 3)]);
+        tab.sections = vec![Section::new("verse".into(), SectionKind::Verse, vec![bar])];
+        tab.form = Form::from(vec!["verse"]);
+        let (tab, report) = Tab::try_parse_arc(tab, false, None, true).unwrap();
+        assert!(report.is_empty());
+        let lane = tab.bars[0].get_lane_of_kind(LaneKind::Melody, None).unwrap();
+        (tab, lane)
+    }
+
+    fn describe(entries: &[Arc<LaneEntry>]) -> Vec<(usize, usize, Units)> {
+        entries
+            .iter()
+            .map(|x| (x.props.index, x.props.voice, x.props.in_bar_pos))
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_voices_in_time_order() {
+        let (_tab, lane) = lane();
+        let quarter = Units::from(Duration::_1_4);
+        let half = Units::from(Duration::_1_2);
+        assert_eq!(
+            describe(&lane.entries),
+            vec![
+                (0, 0, Units::ZERO),
+                (1, 1, Units::ZERO),
+                (2, 1, quarter),
+                (3, 0, half),
+                (4, 1, half),
+            ]
+        );
+        assert!(lane.is_multi_voice());
+        assert_eq!(lane.get_voices(), vec![0, 1]);
+        assert_eq!(describe(&lane.get_voice_entries(0)), vec![(0, 0, Units::ZERO), (3, 0, half)]);
+        let voice_1: Vec<usize> = lane.get_voice_entries(1).iter().map(|x| x.props.index).collect();
+        assert_eq!(voice_1, vec![1, 2, 4]);
+        assert!(lane.get_voice_entries(2).is_empty());
+    }
+
+    #[test]
+    fn test_prev_next_in_voice() {
+        let (_tab, lane) = lane();
+        let index_of = |x: Option<Arc<LaneEntry>>| x.map(|x| x.props.index);
+        assert_eq!(index_of(lane.entries[0].prev()), None);
+        assert_eq!(index_of(lane.entries[0].next()), Some(3));
+        assert_eq!(index_of(lane.entries[3].prev()), Some(0));
+        assert_eq!(index_of(lane.entries[3].next()), None);
+        assert_eq!(index_of(lane.entries[1].prev()), None);
+        assert_eq!(index_of(lane.entries[1].next()), Some(2));
+        assert_eq!(index_of(lane.entries[2].next()), Some(4));
+        assert_eq!(index_of(lane.entries[4].prev()), Some(2));
+        assert_eq!(index_of(lane.entries[4].next()), None);
+    }
+}
@@ -1,6 +1,10 @@
+use fehler::throws;
 use std::fmt::Display;
 use std::sync::Arc;
 
+use notation_proto::prelude::DiagnosticLocation;
+
+use crate::parse::{ParseError, ParseReport};
 use crate::section::Section;
 
 #[derive(Debug)]
@@ -13,18 +17,35 @@ impl Display for Form {
     }
 }
 impl Form {
-    pub fn new(add_ready_section: bool, proto: notation_proto::prelude::Form, tab_section: &Vec<Arc<Section>>) -> Self {
+    #[throws(ParseError)]
+    pub fn try_new(
+        add_ready_section: bool,
+        proto: notation_proto::prelude::Form,
+        tab_section: &[Arc<Section>],
+        report: &mut ParseReport,
+    ) -> Self {
         let mut sections = Vec::new();
-        let mut add_section =
-            |section_id: String| match tab_section.iter().find(|x| x.id == section_id).cloned() {
-                Some(section) => sections.push(section),
-                None => println!("Form::from(), bad section: {}", section_id),
-            };
+        let mut add_section = |form_index: Option<usize>, section_id: String| {
+            match tab_section.iter().find(|x| x.id == section_id).cloned() {
+                Some(section) => {
+                    sections.push(section);
+                    Ok(())
+                }
+                None => report.add(ParseError::SectionNotFound {
+                    location: Box::new(DiagnosticLocation {
+                        form_index,
+                        section: Some(section_id.clone()),
+                        ..DiagnosticLocation::default()
+                    }),
+                    section: section_id,
+                }),
+            }
+        };
         if add_ready_section {
-            add_section(notation_proto::prelude::Section::READY_ID.to_string());
+            add_section(None, notation_proto::prelude::Section::READY_ID.to_string())?;
         }
-        for section_id in proto.sections {
-            add_section(section_id);
+        for (form_index, section_id) in proto.sections.into_iter().enumerate() {
+            add_section(Some(form_index), section_id)?;
         }
         Self { sections }
     }
@@ -3,7 +3,7 @@ use std::sync::{Arc, Weak};
 
 use crate::prelude::{BarLane, BarLaneProps, ModelEntry, Tab, TabBar, TabBarProps, Track};
 use notation_proto::prelude::{
-    BarPosition, Duration, Entry, EntryPassMode, ProtoEntry, TrackKind, Units, Slice,
+    BarPosition, Duration, Entry, EntryPassMode, Ornament, ProtoEntry, TrackKind, Units, Slice,
 };
 
 #[derive(Clone, Debug, Default)]
@@ -11,6 +11,8 @@ pub struct LaneEntryProps {
     pub slice: Slice,
     pub slice_index: usize,
     pub index: usize,
+    /// Voice of the bar layer, entries of other voices in the lane have their own rhythm.
+    pub voice: usize,
     pub in_bar_pos: Units,
     pub tied_units: Units,
     pub duration: Duration,
@@ -33,6 +35,7 @@ impl LaneEntry {
         slice: Slice,
         slice_index: usize,
         index: usize,
+        voice: usize,
         model: Arc<ModelEntry>,
         in_bar_pos: Units,
     ) -> Self {
@@ -40,6 +43,7 @@ impl LaneEntry {
             slice,
             slice_index,
             index,
+            voice,
             in_bar_pos,
             tied_units: model.tied_units(),
             duration: model.duration(),
@@ -78,7 +82,7 @@ impl LaneEntry {
 }
 impl LaneEntry {
     pub fn lane(&self) -> Option<Arc<BarLane>> {
-        self.lane.upgrade().map(|x| x.clone())
+        self.lane.upgrade()
     }
     pub fn track(&self) -> Option<Arc<Track>> {
         self.lane().map(|x| x.track.clone())
@@ -103,25 +107,45 @@ impl LaneEntry {
     pub fn proto(&self) -> &ProtoEntry {
         self.model.proto.as_ref()
     }
+    /// Previous entry of the same voice.
     pub fn prev(&self) -> Option<Arc<LaneEntry>> {
-        if self.props.index == 0 {
-            None
-        } else if let Some(lane) = self.lane.upgrade() {
-            lane.entries.get(self.props.index - 1).map(|x| x.clone())
+        if let Some(lane) = self.lane.upgrade() {
+            lane.entries[..self.props.index.min(lane.entries.len())]
+                .iter()
+                .rev()
+                .find(|x| x.props.voice == self.props.voice)
+                .cloned()
         } else {
             None
         }
     }
+    /// Next entry of the same voice.
     pub fn next(&self) -> Option<Arc<LaneEntry>> {
         if let Some(lane) = self.lane.upgrade() {
-            lane.entries.get(self.props.index + 1).map(|x| x.clone())
+            lane.entries
+                .iter()
+                .skip(self.props.index + 1)
+                .find(|x| x.props.voice == self.props.voice)
+                .cloned()
         } else {
             None
         }
     }
+    /// Ornament right before this tone or pick, in the same voice.
+    pub fn get_ornament(&self) -> Option<Ornament> {
+        if !self.proto().is_ornament_target() {
+            return None;
+        }
+        self.prev().and_then(|x| x.proto().as_core_ornament().copied())
+    }
+    /// Tone or pick decorated by this ornament entry.
+    pub fn get_ornament_target(&self) -> Option<Arc<LaneEntry>> {
+        self.proto().as_core_ornament()?;
+        self.next().filter(|x| x.proto().is_ornament_target())
+    }
     pub fn prev_as_mark(&self) -> Option<String> {
         if let Some(entry) = self.prev() {
-            entry.model.proto.as_mark().map(|x| x.clone())
+            entry.model.proto.as_mark().cloned()
         } else {
             None
         }
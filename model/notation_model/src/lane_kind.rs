@@ -1,12 +1,16 @@
+use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-use notation_proto::prelude::{FrettedEntry6, ProtoEntry, TrackKind};
+use notation_proto::prelude::{
+    ExtensionRegistry, ExtensionType, FrettedEntry4, FrettedEntry6, ProtoEntry, TrackKind,
+};
 
 use crate::prelude::ModelEntry;
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
 pub enum LaneKind {
+    #[default]
     None,
     Meta,
     Chord,
@@ -16,19 +20,20 @@ pub enum LaneKind {
     Keyboard,
     Shapes,
     Strings,
+    Drums,
+    Extension,
 }
 impl Display for LaneKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
-impl Default for LaneKind {
-    fn default() -> Self {
-        Self::None
-    }
+lazy_static! {
+    static ref EXTENSION_LANES: RwLock<HashMap<String, LaneKind>> = RwLock::new(HashMap::new());
 }
+
 impl LaneKind {
-    pub const LEN: usize = 9;
+    pub const LEN: usize = 11;
     pub fn order(&self) -> usize {
         match self {
             LaneKind::None => 0,
@@ -40,9 +45,25 @@ impl LaneKind {
             LaneKind::Keyboard => 6,
             LaneKind::Shapes => 7,
             LaneKind::Strings => 8,
+            LaneKind::Drums => 9,
+            LaneKind::Extension => 10,
         }
     }
+    /// Register the extension type, with the lane its entries are shown in.
+    pub fn register_extension<T: ExtensionType>(lane_kind: LaneKind) {
+        ExtensionRegistry::register::<T>();
+        EXTENSION_LANES
+            .write()
+            .unwrap()
+            .insert(T::KIND.to_owned(), lane_kind);
+    }
+    pub fn of_extension(kind: &str) -> Option<Self> {
+        EXTENSION_LANES.read().unwrap().get(kind).copied()
+    }
     pub fn of_entry(track_kind: &TrackKind, entry: &ProtoEntry) -> Self {
+        if let Some(kind) = entry.as_extension().and_then(|x| Self::of_extension(&x.kind)) {
+            return kind;
+        }
         match track_kind {
             TrackKind::Unsupported => Self::None,
             TrackKind::Meta => Self::Meta,
@@ -60,18 +81,36 @@ impl LaneKind {
             },
             TrackKind::Synth => Self::Keyboard,
             TrackKind::Piano => Self::Keyboard,
-            TrackKind::Drums => Self::None,
-            TrackKind::Bass => Self::None,
+            TrackKind::Drums => match entry {
+                ProtoEntry::Drum(_) => Self::Drums,
+                _ => Self::None,
+            },
+            TrackKind::Bass | TrackKind::Ukulele | TrackKind::Mandolin => match entry {
+                ProtoEntry::Fretted4(entry) => match entry {
+                    FrettedEntry4::Pick(_, _) => Self::Strings,
+                    FrettedEntry4::Strum(_, _) => Self::Strings,
+                    FrettedEntry4::Shape(_, _) => Self::Shapes,
+                    FrettedEntry4::Fretboard(_) => Self::None,
+                },
+                _ => Self::None,
+            },
         }
     }
-    pub fn of_entries(track_kind: &TrackKind, entries: &Vec<Arc<ModelEntry>>) -> LaneKind {
+    /// Extension entries only decide the lane if there are no other entries for one.
+    pub fn of_entries(track_kind: &TrackKind, entries: &[Arc<ModelEntry>]) -> LaneKind {
+        let mut extension_kind = Self::None;
         for entry in entries.iter() {
             let kind = Self::of_entry(track_kind, &entry.proto);
             if kind != Self::None {
-                return kind;
+                if !entry.proto.is_extension() {
+                    return kind;
+                }
+                if extension_kind.is_none() {
+                    extension_kind = kind;
+                }
             }
         }
-        Self::None
+        extension_kind
     }
 
     /// Returns `true` if the lane kind is [`None`].
@@ -136,4 +175,92 @@ impl LaneKind {
     pub fn is_shapes(&self) -> bool {
         matches!(self, Self::Shapes)
     }
+
+    /// Returns `true` if the lane kind is [`Drums`].
+    ///
+    /// [`Drums`]: LaneKind::Drums
+    pub fn is_drums(&self) -> bool {
+        matches!(self, Self::Drums)
+    }
+
+    /// Returns `true` if the lane kind is [`Extension`].
+    ///
+    /// [`Extension`]: LaneKind::Extension
+    pub fn is_extension(&self) -> bool {
+        matches!(self, Self::Extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Weak;
+
+    use notation_proto::prelude::{
+        Duration, ExtensionEntry, Pick, PickNote, Unit, Units,
+    };
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+    struct Cue(String);
+    impl Display for Cue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    impl ExtensionType for Cue {
+        const KIND: &'static str = "notation_model::test::Cue";
+    }
+
+    fn cue() -> ProtoEntry {
+        ProtoEntry::Extension(ExtensionEntry::from_value(&Cue("intro".into())).unwrap())
+    }
+
+    fn unknown() -> ProtoEntry {
+        ProtoEntry::Extension(ExtensionEntry::new("notation_model::test::Unknown".into(), "()".into()))
+    }
+
+    fn pick() -> ProtoEntry {
+        let pick = Pick::from(PickNote::new_string_fret(1, 0));
+        FrettedEntry6::Pick(pick, Duration::Simple(Unit::Quarter)).into()
+    }
+
+    fn entries(protos: Vec<ProtoEntry>) -> Vec<Arc<ModelEntry>> {
+        protos
+            .into_iter()
+            .enumerate()
+            .map(|(index, x)| Arc::new(ModelEntry::new(Weak::new(), Arc::new(x), index, Units::ZERO)))
+            .collect()
+    }
+
+    #[test]
+    fn test_of_entry() {
+        LaneKind::register_extension::<Cue>(LaneKind::Lyrics);
+        assert!(ExtensionRegistry::is_registered(Cue::KIND));
+        assert_eq!(LaneKind::of_extension(Cue::KIND), Some(LaneKind::Lyrics));
+        assert_eq!(LaneKind::of_entry(&TrackKind::Guitar, &cue()), LaneKind::Lyrics);
+        assert_eq!(LaneKind::of_entry(&TrackKind::Vocal, &cue()), LaneKind::Lyrics);
+        // Unknown kinds follow the track kind like any other entry.
+        assert_eq!(LaneKind::of_extension("notation_model::test::Unknown"), None);
+        assert_eq!(LaneKind::of_entry(&TrackKind::Guitar, &unknown()), LaneKind::None);
+        assert_eq!(LaneKind::of_entry(&TrackKind::Vocal, &unknown()), LaneKind::Melody);
+    }
+
+    #[test]
+    fn test_of_entries() {
+        LaneKind::register_extension::<Cue>(LaneKind::Lyrics);
+        let guitar = TrackKind::Guitar;
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![cue()])), LaneKind::Lyrics);
+        assert_eq!(
+            LaneKind::of_entries(&guitar, &entries(vec![ProtoEntry::Mark("a".into()), cue()])),
+            LaneKind::Lyrics
+        );
+        // Extension entries only decide the lane when no other entry does.
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![cue(), pick()])), LaneKind::Strings);
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![pick(), cue()])), LaneKind::Strings);
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![unknown()])), LaneKind::None);
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![unknown(), cue()])), LaneKind::Lyrics);
+        assert_eq!(LaneKind::of_entries(&guitar, &entries(vec![])), LaneKind::None);
+    }
 }
@@ -1,5 +1,9 @@
+#[macro_use]
+extern crate lazy_static;
+
 pub use notation_proto;
 
+pub mod analysis;
 pub mod bar;
 pub mod bar_lane;
 pub mod form;
@@ -11,6 +15,7 @@ pub mod section;
 pub mod tab;
 pub mod tab_bar;
 pub mod tab_chord;
+pub mod timeline;
 pub mod track;
 pub mod util;
 
@@ -18,6 +23,8 @@ pub mod prelude {
     #[doc(hidden)]
     pub use notation_proto::prelude::*;
     #[doc(hidden)]
+    pub use crate::analysis::ChordAnalysis;
+    #[doc(hidden)]
     pub use crate::bar::{Bar, BarLayer};
     #[doc(hidden)]
     pub use crate::bar_lane::{BarLane, BarLaneProps};
@@ -30,7 +37,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::model_entry::{ModelEntry, ModelEntryProps};
     #[doc(hidden)]
-    pub use crate::parse::ParseError;
+    pub use crate::parse::{ParseError, ParseReport};
     #[doc(hidden)]
     pub use crate::section::Section;
     #[doc(hidden)]
@@ -40,6 +47,8 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::tab_chord::TabChord;
     #[doc(hidden)]
+    pub use crate::timeline::{Timeline, TimelineEvent};
+    #[doc(hidden)]
     pub use crate::track::{Track, TrackProps};
     #[doc(hidden)]
     pub use notation_proto::prelude::Bar as ProtoBar;
@@ -2,7 +2,8 @@ use std::sync::{Arc, Weak};
 
 use crate::prelude::{Tab, Track};
 use notation_proto::prelude::{
-    Duration, Entry, EntryPassMode, FrettedEntry4, FrettedEntry6, ProtoEntry, TrackKind, Units,
+    DrumEntry, Duration, Entry, EntryPassMode, FrettedEntry4, FrettedEntry6, Ornament, ProtoEntry,
+    TrackKind, Units,
 };
 
 #[derive(Copy, Clone, Debug)]
@@ -62,6 +63,9 @@ impl ModelEntry {
     pub fn as_fretted4(&self) -> Option<&FrettedEntry4> {
         self.proto.as_fretted4()
     }
+    pub fn as_drum(&self) -> Option<&DrumEntry> {
+        self.proto.as_drum()
+    }
     pub fn prev(&self) -> Option<Arc<ModelEntry>> {
         if self.props.index == 0 {
             None
@@ -85,6 +89,13 @@ impl ModelEntry {
             None
         }
     }
+    /// Ornament right before this tone or pick.
+    pub fn get_ornament(&self) -> Option<Ornament> {
+        if !self.proto.is_ornament_target() {
+            return None;
+        }
+        self.prev().and_then(|x| x.proto.as_core_ornament().copied())
+    }
     pub fn get_tied_prev(&self) -> Option<Arc<ModelEntry>> {
         if self.props.index <= 1 {
             return None;
@@ -1,24 +1,94 @@
 use fehler::throws;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
 
-use std::sync::{Arc, Weak};
+use std::sync::{Arc, RwLock, Weak};
 use thiserror::Error;
 
 use crate::prelude::{BarLane, Form, LaneEntry, ModelEntry, Section, Tab, TabBar, TabMeta, Track, Slice};
-use notation_proto::prelude::{Duration, Entry, ProtoEntry, Units};
+use notation_proto::prelude::{DiagnosticLocation, Duration, Entry, ProtoEntry, Units};
 
 #[derive(Error, Clone, Debug)]
 pub enum ParseError {
-    #[error("track not found")]
-    TrackNotFound(String),
-    #[error("section not found")]
-    SectionNotFound(String),
+    #[error("track not found: {track} ({location})")]
+    TrackNotFound {
+        track: String,
+        location: Box<DiagnosticLocation>,
+    },
+    #[error("section not found: {section} ({location})")]
+    SectionNotFound {
+        section: String,
+        location: Box<DiagnosticLocation>,
+    },
+    #[error("mark not found: {mark} ({location})")]
+    MarkNotFound {
+        mark: String,
+        location: Box<DiagnosticLocation>,
+    },
+    #[error("bars range out of bounds: {begin}-{end} in {bar_count} bars")]
+    BarsRangeOutOfBounds {
+        begin: usize,
+        end: usize,
+        bar_count: usize,
+    },
+}
+
+/// Problems found while parsing, that were skipped in the lenient mode.
+#[derive(Clone, Debug, Default)]
+pub struct ParseReport {
+    pub strict: bool,
+    pub problems: Vec<ParseError>,
+}
+impl Display for ParseReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<ParseReport>(P:{})", self.problems.len())
+    }
+}
+impl ParseReport {
+    pub fn new(strict: bool) -> Self {
+        Self {
+            strict,
+            problems: vec![],
+        }
+    }
+    /// Record the problem, in the strict mode it's returned as the error to stop parsing.
+    #[throws(ParseError)]
+    pub fn add(&mut self, problem: ParseError) {
+        if self.strict {
+            fehler::throw!(problem);
+        }
+        self.problems.push(problem);
+    }
+    pub fn is_empty(&self) -> bool {
+        self.problems.is_empty()
+    }
 }
 
 impl Tab {
+    /// Parse the proto tab, problems are collected in the report, bad sections are
+    /// skipped, missing marks leave empty lanes.
+    ///
+    /// In the strict mode, the first problem is returned as the error instead.
     #[throws(ParseError)]
-    pub fn try_parse_arc(proto: notation_proto::prelude::Tab, add_ready_section: bool, bars_range:Option<(usize, usize)>) -> Arc<Self> {
-        Arc::<Tab>::new_cyclic(|weak_self| {
+    pub fn try_parse_arc(
+        proto: notation_proto::prelude::Tab,
+        add_ready_section: bool,
+        bars_range: Option<(usize, usize)>,
+        strict: bool,
+    ) -> (Arc<Self>, ParseReport) {
+        let mut report = ParseReport::new(strict);
+        let tab = Self::parse_arc(proto, add_ready_section, bars_range, &mut report)?;
+        (tab, report)
+    }
+    #[throws(ParseError)]
+    fn parse_arc(
+        proto: notation_proto::prelude::Tab,
+        add_ready_section: bool,
+        bars_range: Option<(usize, usize)>,
+        report: &mut ParseReport,
+    ) -> Arc<Self> {
+        let mut error = None;
+        let tab = Arc::<Tab>::new_cyclic(|weak_self| {
             let uuid = proto.uuid;
             let meta = Arc::new(proto.meta);
             let tracks = proto
@@ -27,59 +97,23 @@ impl Tab {
                 .enumerate()
                 .map(|(index, track)| Track::new_arc(weak_self.clone(), index, track))
                 .collect();
-            let mut sections = Vec::new();
-            let mut add_section = |index: usize, section: notation_proto::prelude::Section| {
-                let section_id = section.id.clone();
-                match Section::try_new(weak_self.clone(), index, section, &tracks).map(Arc::new) {
-                    Ok(section) => sections.push(section),
-                    Err(err) => println!(
-                        "Tab::try_parse_arc(), bad section: {} {} -> {}",
-                        index, section_id, err
-                    ),
-                }
-            };
-            if add_ready_section {
-                add_section(0, notation_proto::prelude::Section::new_ready());
-            }
-            for (index, section) in proto.sections.into_iter().enumerate() {
-                add_section(index, section);
-            }
-            let form = Form::new(add_ready_section, proto.form, &sections);
-            let all_bars = Self::new_tab_bars(add_ready_section, weak_self, &meta, &form);
-            let bars = if let Some((begin, end)) = bars_range {
-                if begin < all_bars.len() && end < all_bars.len() && end >= begin {
-                    let ready_added = add_ready_section && begin > 0;
-                    let mut bars: Vec<Arc<TabBar>> = all_bars[begin..=end].iter()
-                        .enumerate()
-                        .map(|(index, bar)| {
-                            let bar_ordinal = if ready_added { index + 1 } else { index };
-                            let bar_number = if ready_added {
-                                begin + index
-                            } else {
-                                begin + index + 1
-                            };
-                            TabBar::new_arc(
-                                bar.tab.clone(),
-                                bar.section.clone(),
-                                bar.proto.clone(),
-                                bar.props.section_round,
-                                bar.props.section_ordinal,
-                                bar.props.bar_index,
-                                bar_ordinal,
-                                bar_number,
-                                bar.props.bar_units,
-                            )
-                        }).collect();
-                    if ready_added {
-                        bars.insert(0, all_bars[0].clone());
-                    }
-                    bars
-                } else {
-                    all_bars
-                }
-            } else {
-                all_bars
-            };
+            let parsed = Self::parse_sections(
+                weak_self,
+                &tracks,
+                proto.sections,
+                proto.form,
+                add_ready_section,
+                report,
+            )
+            .and_then(|(sections, form)| {
+                let all_bars = Self::new_tab_bars(add_ready_section, weak_self, &meta, &form);
+                let bars = Self::select_bars(all_bars, add_ready_section, bars_range, report)?;
+                Ok((sections, form, bars))
+            });
+            let (sections, form, bars) = parsed.unwrap_or_else(|err| {
+                error = Some(err);
+                (vec![], Form { sections: vec![] }, vec![])
+            });
             Self {
                 uuid,
                 meta,
@@ -87,8 +121,90 @@ impl Tab {
                 sections,
                 form,
                 bars,
+                guitar_chord_voicings: RwLock::new(BTreeMap::new()),
+            }
+        });
+        if let Some(err) = error {
+            fehler::throw!(err);
+        }
+        tab
+    }
+    /// Sections and form of the tab, stops at the first problem in the strict mode.
+    #[throws(ParseError)]
+    fn parse_sections(
+        weak_self: &Weak<Tab>,
+        tracks: &Vec<Arc<Track>>,
+        proto_sections: Vec<notation_proto::prelude::Section>,
+        proto_form: notation_proto::prelude::Form,
+        add_ready_section: bool,
+        report: &mut ParseReport,
+    ) -> (Vec<Arc<Section>>, Form) {
+        let mut sections = Vec::new();
+        let mut add_section = |index: usize, section: notation_proto::prelude::Section, report: &mut ParseReport| {
+            match Section::try_new(weak_self.clone(), index, section, tracks, report).map(Arc::new) {
+                Ok(section) => {
+                    sections.push(section);
+                    Ok(())
+                }
+                Err(err) => report.add(err),
             }
-        })
+        };
+        if add_ready_section {
+            add_section(0, notation_proto::prelude::Section::new_ready(), report)?;
+        }
+        for (index, section) in proto_sections.into_iter().enumerate() {
+            add_section(index, section, report)?;
+        }
+        let form = Form::try_new(add_ready_section, proto_form, &sections, report)?;
+        (sections, form)
+    }
+    /// Bars in the range if given, all bars are kept if the range is out of bounds.
+    #[throws(ParseError)]
+    fn select_bars(
+        all_bars: Vec<Arc<TabBar>>,
+        add_ready_section: bool,
+        bars_range: Option<(usize, usize)>,
+        report: &mut ParseReport,
+    ) -> Vec<Arc<TabBar>> {
+        if let Some((begin, end)) = bars_range {
+            if begin < all_bars.len() && end < all_bars.len() && end >= begin {
+                let ready_added = add_ready_section && begin > 0;
+                let mut bars: Vec<Arc<TabBar>> = all_bars[begin..=end].iter()
+                    .enumerate()
+                    .map(|(index, bar)| {
+                        let bar_ordinal = if ready_added { index + 1 } else { index };
+                        let bar_number = if ready_added {
+                            begin + index
+                        } else {
+                            begin + index + 1
+                        };
+                        TabBar::new_arc(
+                            bar.tab.clone(),
+                            bar.section.clone(),
+                            bar.proto.clone(),
+                            bar.props.section_round,
+                            bar.props.section_ordinal,
+                            bar.props.bar_index,
+                            bar_ordinal,
+                            bar_number,
+                            bar.props.bar_units,
+                        )
+                    }).collect();
+                if ready_added {
+                    bars.insert(0, all_bars[0].clone());
+                }
+                bars
+            } else {
+                report.add(ParseError::BarsRangeOutOfBounds {
+                    begin,
+                    end,
+                    bar_count: all_bars.len(),
+                })?;
+                all_bars
+            }
+        } else {
+            all_bars
+        }
     }
     fn new_tab_bars(add_ready_section: bool, weak_self: &Weak<Tab>, meta: &TabMeta, form: &Form) -> Vec<Arc<TabBar>> {
         let mut section_rounds: HashMap<String, usize> = HashMap::new();
@@ -161,7 +277,7 @@ impl Section {
 }
 impl ModelEntry {
     pub fn calc_tied_units(entries: &Vec<ProtoEntry>, index: usize) -> Units {
-        let mut units = Units(0.0);
+        let mut units = Units::ZERO;
         if let Some(entry) = entries.get(index) {
             units = units + Units::from(entry.duration());
             if let Some(next_entry) = entries.get(index + 1) {
@@ -191,16 +307,93 @@ impl ModelEntry {
     }
 }
 impl LaneEntry {
-    pub fn new_entries(v: Vec<Arc<ModelEntry>>, lane: &Weak<BarLane>, slice: Slice) -> Vec<Arc<LaneEntry>> {
-        let mut pos = 0.0;
+    pub fn new_entries(v: Vec<Arc<ModelEntry>>, lane: &Weak<BarLane>, slice: Slice, voice: usize) -> Vec<Arc<LaneEntry>> {
+        let mut pos = Units::ZERO;
         v.into_iter()
             .enumerate()
             .map(|(index, entry)| {
                 let in_bar_pos = pos;
-                pos += Units::from(entry.as_ref().duration()).0;
-                LaneEntry::new(lane.clone(), slice.clone(), index, index, entry, Units(in_bar_pos))
+                pos += Units::from(entry.as_ref().duration());
+                LaneEntry::new(lane.clone(), slice.clone(), index, index, voice, entry, in_bar_pos)
             })
             .map(Arc::new)
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{
+        Bar, BarLayer, Duration, Form, FrettedEntry6, Pick, PickNote, Section, SectionKind,
+        Slice, SliceBegin, SliceEnd, Tab as ProtoTab, Track, TrackKind, Unit,
+    };
+
+    use super::*;
+
+    fn section(id: &str, track: &str, mark: &str) -> Section {
+        let slice = Slice::new(SliceBegin::Mark(mark.into()), SliceEnd::Count(1), None);
+        let bar = Bar::new(vec![BarLayer::new(track.into(), vec![slice])]);
+        Section::new(id.into(), SectionKind::Verse, vec![bar])
+    }
+
+    fn proto_tab(sections: Vec<Section>, form: Vec<&str>) -> ProtoTab {
+        let mut tab = ProtoTab::new_empty();
+        let pick = Pick::from(PickNote::new_string_fret(1, 0));
+        let entries = vec![
+            ProtoEntry::Mark("a".into()),
+            FrettedEntry6::Pick(pick, Duration::Simple(Unit::Whole)).into(),
+        ];
+        tab.tracks.push(Track::new("guitar".into(), TrackKind::Guitar, entries));
+        tab.sections = sections;
+        tab.form = Form::from(form);
+        tab
+    }
+
+    fn broken_tab() -> ProtoTab {
+        proto_tab(
+            vec![
+                section("verse", "guitar", "a"),
+                section("drums", "drums", "a"),
+                section("missing", "guitar", "x"),
+            ],
+            vec!["verse", "chorus", "missing"],
+        )
+    }
+
+    #[test]
+    fn test_valid_tab() {
+        let tab = proto_tab(vec![section("verse", "guitar", "a")], vec!["verse", "verse"]);
+        let (tab, report) = Tab::try_parse_arc(tab, false, None, true).unwrap();
+        assert!(report.is_empty());
+        assert_eq!(tab.bars.len(), 2);
+    }
+
+    #[test]
+    fn test_lenient_collects_problems() {
+        let (tab, report) = Tab::try_parse_arc(broken_tab(), false, None, false).unwrap();
+        assert_eq!(report.problems.len(), 3);
+        assert!(matches!(&report.problems[0], ParseError::TrackNotFound { track, .. } if track == "drums"));
+        assert!(matches!(&report.problems[1], ParseError::MarkNotFound { mark, .. } if mark == "x"));
+        assert!(matches!(&report.problems[2], ParseError::SectionNotFound { section, .. } if section == "chorus"));
+        assert_eq!(tab.sections.len(), 2);
+        assert_eq!(tab.form.sections.len(), 2);
+    }
+
+    #[test]
+    fn test_strict_stops_at_first_problem() {
+        let err = Tab::try_parse_arc(broken_tab(), false, None, true).unwrap_err();
+        assert!(matches!(err, ParseError::TrackNotFound { track, .. } if track == "drums"));
+        let tab = proto_tab(vec![section("verse", "guitar", "a")], vec!["verse", "chorus"]);
+        let err = Tab::try_parse_arc(tab, false, None, true).unwrap_err();
+        assert!(matches!(err, ParseError::SectionNotFound { section, .. } if section == "chorus"));
+    }
+
+    #[test]
+    fn test_bars_range_out_of_bounds() {
+        let tab = || proto_tab(vec![section("verse", "guitar", "a")], vec!["verse"]);
+        let (parsed, report) = Tab::try_parse_arc(tab(), false, Some((0, 5)), false).unwrap();
+        assert_eq!(parsed.bars.len(), 1);
+        assert!(matches!(report.problems[..], [ParseError::BarsRangeOutOfBounds { bar_count: 1, .. }]));
+        assert!(Tab::try_parse_arc(tab(), false, Some((0, 5)), true).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use fehler::throws;
 use std::fmt::Display;
 use std::sync::{Arc, Weak};
 
+use crate::parse::ParseReport;
 use crate::prelude::{Bar, ParseError, SectionKind, Tab, Track};
 
 #[derive(Debug)]
@@ -49,10 +50,11 @@ impl Section {
         index: usize,
         proto: notation_proto::prelude::Section,
         tracks: &Vec<Arc<Track>>,
+        report: &mut ParseReport,
     ) -> Self {
         let mut bars = Vec::new();
         for (bar_index, bar) in proto.bars.into_iter().enumerate() {
-            bars.push(Bar::try_new(bar_index, bar, tracks).map(Arc::new)?);
+            bars.push(Bar::try_new(&proto.id, bar_index, bar, tracks, report).map(Arc::new)?);
         }
         Self::new(tab, index, proto.kind, proto.id, bars)
     }
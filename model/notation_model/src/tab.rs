@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
 use std::fmt::Display;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 
-use notation_proto::prelude::{BarPosition, Note, TrackKind, Octave};
+use notation_proto::prelude::{
+    BarPosition, Chord, ChordVoicing6, LyricLine, LyricRow, Note, Octave, TrackKind,
+};
 use uuid::Uuid;
 
 use crate::prelude::{
@@ -16,6 +19,8 @@ pub struct Tab {
     pub sections: Vec<Arc<Section>>,
     pub form: Form,
     pub bars: Vec<Arc<TabBar>>,
+    /// Voicings calculated by [`Tab::get_guitar_chord_voicings()`].
+    pub(crate) guitar_chord_voicings: RwLock<BTreeMap<Chord, Arc<Vec<ChordVoicing6>>>>,
 }
 impl Display for Tab {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -60,10 +65,102 @@ impl Tab {
         }
         None
     }
+    /// Lines of all the lyric entries in the tab.
+    pub fn get_lyric_lines(&self) -> Vec<LyricLine> {
+        self.tracks
+            .iter()
+            .flat_map(|track| track.entries.iter())
+            .filter_map(|entry| entry.proto.as_lyric())
+            .flat_map(|x| x.get_lines())
+            .collect()
+    }
+    /// Distinct verses and languages of the lyrics, ordered by verse first.
+    pub fn get_lyric_rows(&self) -> Vec<LyricRow> {
+        let mut rows: Vec<LyricRow> = self.get_lyric_lines().iter().map(|x| x.row()).collect();
+        rows.sort();
+        rows.dedup();
+        rows
+    }
+    pub fn get_lyric_langs(&self) -> Vec<String> {
+        let mut langs: Vec<String> = self.get_lyric_rows().into_iter().map(|x| x.lang).collect();
+        langs.sort();
+        langs.dedup();
+        langs
+    }
     pub fn get_bar_of_ordinal(&self, bar_ordinal: usize) -> Option<Arc<TabBar>> {
         self.bars.get(bar_ordinal).map(|x| x.clone())
     }
     pub fn get_bar(&self, pos: BarPosition) -> Option<Arc<TabBar>> {
         self.get_bar_of_ordinal(pos.bar_ordinal)
     }
+    /// Voicings of the chord on the guitar track, best first, for offering alternatives
+    /// to its shapes, calculated once per chord.
+    pub fn get_guitar_chord_voicings(&self, chord: &Chord) -> Arc<Vec<ChordVoicing6>> {
+        if let Some(voicings) = self.guitar_chord_voicings.read().ok().and_then(|x| x.get(chord).cloned()) {
+            return voicings;
+        }
+        let voicings = Arc::new(self.calc_guitar_chord_voicings(chord));
+        if let Ok(mut cache) = self.guitar_chord_voicings.write() {
+            cache.insert(*chord, voicings.clone());
+        }
+        voicings
+    }
+    fn calc_guitar_chord_voicings(&self, chord: &Chord) -> Vec<ChordVoicing6> {
+        self.get_track_of_kind(TrackKind::Guitar)
+            .and_then(|x| x.get_fretboard6())
+            .map(|fretboard| self.meta.calc_chord_voicings6(&fretboard, chord))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{
+        Bar, BarLayer, Duration, Form, LyricEntry, LyricExtender, ProtoEntry, Section, SectionKind, Slice,
+        SliceBegin, SliceEnd, Tab as ProtoTab, Track as ProtoTrack,
+    };
+
+    use super::*;
+
+    fn line(verse: u8, lang: &str, word: &str) -> LyricLine {
+        LyricLine::new(verse, lang.into(), word.into())
+    }
+
+    /// One plain word, then two verses in English and Chinese on the same notes.
+    fn tab() -> Arc<Tab> {
+        let mut tab = ProtoTab::new_empty();
+        let lines = vec![
+            line(2, "", "Good").with_extender(LyricExtender::Hyphen),
+            line(1, "zh", "你").with_phonetic("nǐ".into()),
+            line(1, "", "Hel"),
+            line(2, "zh", "好"),
+            line(1, "zh", "你"),
+        ];
+        let entries: Vec<ProtoEntry> = vec![
+            LyricEntry::from(("Hi", Duration::_1_2)).into(),
+            LyricEntry::from((lines, Duration::_1_2)).into(),
+        ];
+        tab.tracks.push(ProtoTrack::new("lyrics".into(), TrackKind::Lyrics, entries));
+        let slice = Slice::new(SliceBegin::Index(0), SliceEnd::Count(2), None);
+        let bar = Bar::new(vec![BarLayer::new("lyrics".into(), vec![slice])]);
+        tab.sections = vec![Section::new("verse".into(), SectionKind::Verse, vec![bar])];
+        tab.form = Form::from(vec!["verse"]);
+        let (tab, report) = Tab::try_parse_arc(tab, false, None, true).unwrap();
+        assert!(report.is_empty());
+        tab
+    }
+
+    #[test]
+    fn test_get_lyric_lines() {
+        let words: Vec<String> = tab().get_lyric_lines().iter().map(|x| x.word.text.clone()).collect();
+        assert_eq!(words, vec!["Hi", "Good", "你", "Hel", "好", "你"]);
+    }
+
+    #[test]
+    fn test_get_lyric_rows() {
+        let tab = tab();
+        let rows: Vec<String> = tab.get_lyric_rows().iter().map(|x| x.to_string()).collect();
+        assert_eq!(rows, vec!["1", "1:zh", "2", "2:zh"]);
+        assert_eq!(tab.get_lyric_langs(), vec!["", "zh"]);
+    }
 }
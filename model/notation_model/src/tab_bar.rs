@@ -3,7 +3,7 @@ use std::sync::{Arc, Weak};
 use std::collections::HashMap;
 
 use notation_proto::prelude::{
-    BarPosition, Chord, Fretboard4, Fretboard6, HandShape4, HandShape6, Note, Position,
+    BarPosition, Chord, Fretboard4, Fretboard6, HandShape4, HandShape6, Note, Pick, Position,
     TabPosition, Octave,
 };
 
@@ -25,7 +25,7 @@ pub struct TabBarProps {
 
 impl From<TabBarProps> for BarPosition {
     fn from(v: TabBarProps) -> Self {
-        BarPosition::new(v.bar_units, v.bar_ordinal, Units(0.0))
+        BarPosition::new(v.bar_units, v.bar_ordinal, Units::ZERO)
     }
 }
 
@@ -85,6 +85,7 @@ impl TabBar {
                             index,
                             &layer.track,
                             slice.clone(),
+                            layer.voice,
                         ) {
                             let new_lane = match lanes.get(&(new_lane.kind, new_lane.track.props.index)) {
                                 Some(lane) => {
@@ -119,7 +120,7 @@ impl TabBar {
         })
     }
     pub fn tab_position(&self) -> TabPosition {
-        TabPosition::new(Units(self.props.bar_ordinal as f32 * self.bar_units().0))
+        TabPosition::new(self.bar_units() * self.props.bar_ordinal as i64)
     }
     pub fn tab_meta(&self) -> Arc<TabMeta> {
         match self.tab.upgrade() {
@@ -182,7 +183,7 @@ impl TabBar {
             .iter()
             .filter_map(| ((_k, _i), lane) | {
                 lane.get_entry(&|x: &LaneEntry| {
-                    if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+                    if x.props.in_bar_pos > in_bar_pos {
                         predicate(x)
                     } else {
                         None
@@ -196,13 +197,13 @@ impl TabBar {
         in_bar_pos: Units,
         predicate: &F,
     ) -> Option<T> {
-        let mut result_in_bar_pos = Units(f32::MAX);
+        let mut result_in_bar_pos: Option<Units> = None;
         let mut result = None;
         for ((_k, _i), lane) in self.lanes.iter() {
             if let Some((in_bar_pos, entry)) = lane.get_entry(&|x: &LaneEntry| {
-                if result_in_bar_pos < x.props.in_bar_pos {
+                if result_in_bar_pos.is_some_and(|pos| pos < x.props.in_bar_pos) {
                     None
-                } else if x.props.in_bar_pos.is_bigger_than(&in_bar_pos) {
+                } else if x.props.in_bar_pos > in_bar_pos {
                     if let Some(t) = predicate(x) {
                         Some((x.props.in_bar_pos, t))
                     } else {
@@ -212,7 +213,7 @@ impl TabBar {
                     None
                 }
             }) {
-                result_in_bar_pos = in_bar_pos;
+                result_in_bar_pos = Some(in_bar_pos);
                 result = Some(entry)
             }
         }
@@ -259,7 +260,7 @@ impl TabBar {
 }
 
 macro_rules! impl_get_fretted_shape {
-    ($name:ident, $strings:literal, $as_fretted:ident, $get_fretboard:ident, $fretboard:ident, $hand_shape:ident) => {
+    ($name:ident, $calc_pick_fingers:ident, $calc_shape_chord:ident, $calc_shape_chords:ident, $strings:literal, $as_fretted:ident, $get_fretboard:ident, $fretboard:ident, $hand_shape:ident) => {
         impl TabBar {
             pub fn $name(&self, entry: &LaneEntry) -> Option<($fretboard, $hand_shape)> {
                 entry
@@ -282,12 +283,42 @@ macro_rules! impl_get_fretted_shape {
                         (fretboard, shape)
                     })
             }
+            /// Pick of the entry with the fret fingers filled, see [`calc_pick_fingers()`],
+            /// the hand follows the earlier picks of the same voice and shape in the bar.
+            ///
+            /// [`calc_pick_fingers()`]: notation_proto::prelude::HandShape6::calc_pick_fingers
+            pub fn $calc_pick_fingers(&self, entry: &LaneEntry) -> Option<Pick> {
+                entry.model().$as_fretted().and_then(|x| x.as_pick())?;
+                let (_, shape) = self.$name(entry)?;
+                let lane = entry.lane()?;
+                let picks: Vec<Pick> = lane
+                    .entries
+                    .iter()
+                    .take(entry.props.index + 1)
+                    .filter(|x| x.props.voice == entry.props.voice)
+                    .filter(|x| self.$name(x).map(|(_, y)| y) == Some(shape))
+                    .filter_map(|x| x.model().$as_fretted().and_then(|y| y.as_pick()).copied())
+                    .collect();
+                shape.calc_pick_fingers(&picks).last().copied()
+            }
+            /// Chord named after the notes of a shape entry, for shapes without a chord entry.
+            pub fn $calc_shape_chord(&self, entry: &LaneEntry) -> Option<Chord> {
+                let shape = entry.model().$as_fretted().and_then(|x| x.as_shape())?;
+                let fretboard = entry.track().and_then(|t| t.$get_fretboard())?;
+                self.tab_meta()
+                    .$calc_shape_chords(&fretboard, shape)
+                    .first()
+                    .copied()
+            }
         }
     };
 }
 
 impl_get_fretted_shape!(
     get_fretted_shape6,
+    calc_pick_fingers6,
+    calc_shape_chord6,
+    calc_shape_chords6,
     6,
     as_fretted6,
     get_fretboard6,
@@ -296,6 +327,9 @@ impl_get_fretted_shape!(
 );
 impl_get_fretted_shape!(
     get_fretted_shape4,
+    calc_pick_fingers4,
+    calc_shape_chord4,
+    calc_shape_chords4,
     4,
     as_fretted4,
     get_fretboard4,
@@ -0,0 +1,331 @@
+use std::fmt::Display;
+use std::sync::Arc;
+
+use notation_proto::prelude::{
+    BarPosition, Bpm, Entry, FrettedEntry4, FrettedEntry6, Note, Ornament, ProtoEntry, Units,
+};
+
+use crate::prelude::{LaneEntry, Tab, TabBar, Track};
+
+/// A played entry of the tab, see [`Timeline`].
+#[derive(Clone, Debug)]
+pub struct TimelineEvent {
+    pub in_tab_pos: Units,
+    pub pos: BarPosition,
+    /// Length including tied entries.
+    pub units: Units,
+    pub seconds: f32,
+    pub duration_seconds: f32,
+    pub track: Arc<Track>,
+    pub section_round: usize,
+    pub voice: usize,
+    /// Notes of tones and picks, empty for drums.
+    pub notes: Vec<Note>,
+    pub ornament: Option<Ornament>,
+    pub entry: Arc<LaneEntry>,
+}
+impl Display for TimelineEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<TimelineEvent>({:.3}s {}:{} {} R:{} V:{} N:{})",
+            self.seconds,
+            self.pos.bar_ordinal,
+            self.pos.in_bar_pos.to_f32(),
+            self.track.id,
+            self.section_round,
+            self.voice,
+            self.notes.len()
+        )
+    }
+}
+impl TimelineEvent {
+    pub fn bar_ordinal(&self) -> usize {
+        self.pos.bar_ordinal
+    }
+    pub fn end_pos(&self) -> Units {
+        self.in_tab_pos + self.units
+    }
+    /// Returns `true` if the event is sounding at the position.
+    pub fn is_sounding_at(&self, in_tab_pos: Units) -> bool {
+        self.in_tab_pos <= in_tab_pos && in_tab_pos < self.end_pos()
+    }
+}
+
+/// Played entries of all tracks in time order, with positions in the whole tab
+/// instead of in bars, so consumers don't need to walk bars, lanes and entries.
+///
+/// Positions are exact, seconds are calculated from them with the tempo of the tab.
+#[derive(Clone, Debug)]
+pub struct Timeline {
+    pub bpm: Bpm,
+    pub seconds_per_unit: f32,
+    pub events: Vec<TimelineEvent>,
+}
+impl Display for Timeline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<Timeline>({} E:{})", self.bpm, self.events.len())
+    }
+}
+
+impl Timeline {
+    pub fn new(tab: &Tab) -> Self {
+        let bpm = Bpm::from(tab.meta.tempo);
+        let beat_units = Units::from(tab.beat_unit());
+        let seconds_per_unit = 60.0 / (bpm as f32 * beat_units.to_f32());
+        let mut events = Vec::new();
+        for bar in tab.bars.iter() {
+            for ((_k, _i), lane) in bar.lanes.iter() {
+                for entry in lane.entries.iter() {
+                    if let Some(notes) = Self::calc_played_notes(bar, entry) {
+                        let pos = entry.bar_position();
+                        let in_tab_pos = Units::from(pos);
+                        let units = entry.tied_units();
+                        events.push(TimelineEvent {
+                            in_tab_pos,
+                            pos,
+                            units,
+                            seconds: in_tab_pos.to_f32() * seconds_per_unit,
+                            duration_seconds: units.to_f32() * seconds_per_unit,
+                            track: lane.track.clone(),
+                            section_round: bar.props.section_round,
+                            voice: entry.props.voice,
+                            notes,
+                            ornament: entry.get_ornament(),
+                            entry: entry.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        events.sort_by(|a, b| {
+            a.in_tab_pos
+                .cmp(&b.in_tab_pos)
+                .then(a.track.props.index.cmp(&b.track.props.index))
+                .then(a.voice.cmp(&b.voice))
+        });
+        Self {
+            bpm,
+            seconds_per_unit,
+            events,
+        }
+    }
+    /// Notes of the entry if it is played, the same entries as the midi playback.
+    fn calc_played_notes(bar: &TabBar, entry: &LaneEntry) -> Option<Vec<Note>> {
+        if entry.prev_is_tie() {
+            return None;
+        }
+        let meta = bar.tab_meta();
+        match entry.proto() {
+            ProtoEntry::Core(core_entry) => core_entry
+                .as_tone()
+                .filter(|x| !x.is_none())
+                .map(|x| x.get_notes()),
+            ProtoEntry::Fretted6(FrettedEntry6::Pick(pick, _)) => {
+                let (fretboard, shape) = bar.get_fretted_shape6(entry)?;
+                Some(fretboard.pick_tone(&meta.scale, &meta.key, &shape, pick).get_notes())
+            }
+            ProtoEntry::Fretted4(FrettedEntry4::Pick(pick, _)) => {
+                let (fretboard, shape) = bar.get_fretted_shape4(entry)?;
+                Some(fretboard.pick_tone(&meta.scale, &meta.key, &shape, pick).get_notes())
+            }
+            ProtoEntry::Drum(_) => Some(vec![]),
+            _ => None,
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+    pub fn iter(&self) -> std::slice::Iter<'_, TimelineEvent> {
+        self.events.iter()
+    }
+    pub fn calc_seconds(&self, units: Units) -> f32 {
+        units.to_f32() * self.seconds_per_unit
+    }
+    pub fn calc_units(&self, seconds: f32) -> Units {
+        Units::from_f32(seconds / self.seconds_per_unit)
+    }
+    /// Events starting in `[begin, end)`, in time order.
+    pub fn get_events_in(&self, begin: Units, end: Units) -> &[TimelineEvent] {
+        let first = self.events.partition_point(|x| x.in_tab_pos < begin);
+        let last = self.events.partition_point(|x| x.in_tab_pos < end);
+        &self.events[first..last.max(first)]
+    }
+    /// Events starting between the two times in seconds.
+    pub fn get_events_in_seconds(&self, begin: f32, end: f32) -> &[TimelineEvent] {
+        self.get_events_in(self.calc_units(begin), self.calc_units(end))
+    }
+    /// Events sounding at the position, including the ones started before it.
+    pub fn get_events_at(&self, in_tab_pos: Units) -> Vec<&TimelineEvent> {
+        let last = self.events.partition_point(|x| x.in_tab_pos <= in_tab_pos);
+        self.events[..last]
+            .iter()
+            .filter(|x| x.is_sounding_at(in_tab_pos))
+            .collect()
+    }
+    pub fn get_bar_events(&self, bar_ordinal: usize) -> &[TimelineEvent] {
+        let first = self.events.partition_point(|x| x.pos.bar_ordinal < bar_ordinal);
+        let last = self.events.partition_point(|x| x.pos.bar_ordinal <= bar_ordinal);
+        &self.events[first..last]
+    }
+    pub fn get_track_events<'a>(
+        &'a self,
+        track_id: &'a str,
+    ) -> impl Iterator<Item = &'a TimelineEvent> + 'a {
+        self.events.iter().filter(move |x| x.track.id == track_id)
+    }
+    /// First event starting after the position.
+    pub fn get_next_event(&self, in_tab_pos: Units) -> Option<&TimelineEvent> {
+        let index = self.events.partition_point(|x| x.in_tab_pos <= in_tab_pos);
+        self.events.get(index)
+    }
+}
+
+impl<'a> IntoIterator for &'a Timeline {
+    type Item = &'a TimelineEvent;
+    type IntoIter = std::slice::Iter<'a, TimelineEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.iter()
+    }
+}
+
+impl Tab {
+    /// Played entries in time order, see [`Timeline`].
+    pub fn calc_timeline(&self) -> Timeline {
+        Timeline::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_proto::prelude::{
+        Bar, BarLayer, CoreEntry, Duration, Form, Octave, Pitch, Section, SectionKind, Slice,
+        SliceBegin, SliceEnd, Syllable, Tab as ProtoTab, Tempo, Track as ProtoTrack, TrackKind,
+    };
+
+    use super::*;
+
+    fn tone(duration: Duration) -> ProtoEntry {
+        let note = Note::new(Octave::P4, Pitch::C, Syllable::Do);
+        CoreEntry::Tone(note.into(), duration).into()
+    }
+
+    fn layer(track: &str, index: usize, count: usize) -> BarLayer {
+        let slice = Slice::new(SliceBegin::Index(index), SliceEnd::Count(count), None);
+        BarLayer::new(track.into(), vec![slice])
+    }
+
+    /// Two bars of 4/4 at 120 bpm, so a whole note lasts 2 seconds.
+    fn timeline() -> Timeline {
+        let mut tab = ProtoTab::new_empty();
+        tab.meta.tempo = Tempo::Bpm(120);
+        let vocal = vec![
+            tone(Duration::T_1_2),
+            tone(Duration::T_1_2),
+            tone(Duration::T_1_2),
+            tone(Duration::_1_2),
+            CoreEntry::Tie.into(),
+            tone(Duration::_1_2),
+        ];
+        let piano = vec![
+            tone(Duration::_1),
+            CoreEntry::Rest(Duration::_1_2).into(),
+            tone(Duration::_1_2),
+        ];
+        tab.tracks.push(ProtoTrack::new("vocal".into(), TrackKind::Vocal, vocal));
+        tab.tracks.push(ProtoTrack::new("piano".into(), TrackKind::Piano, piano));
+        let bars = vec![
+            Bar::new(vec![layer("vocal", 0, 3), layer("piano", 0, 1)]),
+            Bar::new(vec![layer("vocal", 3, 3), layer("piano", 1, 2)]),
+        ];
+        tab.sections = vec![Section::new("verse".into(), SectionKind::Verse, bars)];
+        tab.form = Form::from(vec!["verse"]);
+        let (tab, report) = Tab::try_parse_arc(tab, false, None, true).unwrap();
+        assert!(report.is_empty());
+        tab.calc_timeline()
+    }
+
+    fn positions<'a>(events: impl IntoIterator<Item = &'a TimelineEvent>) -> Vec<Units> {
+        events.into_iter().map(|x| x.in_tab_pos).collect()
+    }
+
+    #[test]
+    fn test_events() {
+        let timeline = timeline();
+        assert_eq!(
+            positions(&timeline),
+            vec![
+                Units::ZERO,
+                Units::ZERO,
+                Units::new(1, 3),
+                Units::new(2, 3),
+                Units::new(1, 1),
+                Units::new(3, 2),
+            ]
+        );
+        let ids: Vec<&str> = timeline.iter().map(|x| x.track.id.as_str()).collect();
+        assert_eq!(ids, vec!["vocal", "piano", "vocal", "vocal", "vocal", "piano"]);
+        let tied = &timeline.events[4];
+        assert_eq!(tied.units, Units::new(1, 1));
+        assert_eq!(tied.end_pos(), Units::new(2, 1));
+        assert_eq!(tied.duration_seconds, 2.0);
+    }
+
+    #[test]
+    fn test_get_events_in() {
+        let timeline = timeline();
+        assert_eq!(timeline.get_events_in(Units::ZERO, Units::new(2, 3)).len(), 3);
+        assert_eq!(
+            positions(timeline.get_events_in(Units::new(2, 3), Units::new(1, 1))),
+            vec![Units::new(2, 3)]
+        );
+        assert_eq!(timeline.get_events_in(Units::new(1, 1), Units::new(2, 1)).len(), 2);
+        assert!(timeline.get_events_in(Units::new(1, 1), Units::ZERO).is_empty());
+        assert_eq!(timeline.get_events_in_seconds(0.0, 2.0).len(), 4);
+        assert_eq!(timeline.get_events_in_seconds(2.0, 4.0).len(), 2);
+    }
+
+    #[test]
+    fn test_get_events_at() {
+        let timeline = timeline();
+        let sounding = timeline.get_events_at(Units::new(1, 2));
+        assert_eq!(positions(sounding), vec![Units::ZERO, Units::new(1, 3)]);
+        let sounding = timeline.get_events_at(Units::new(2, 3));
+        assert_eq!(positions(sounding), vec![Units::ZERO, Units::new(2, 3)]);
+        let sounding = timeline.get_events_at(Units::new(5, 4));
+        assert_eq!(positions(sounding), vec![Units::new(1, 1)]);
+        assert!(timeline.get_events_at(Units::new(2, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_bar_track_and_next_events() {
+        let timeline = timeline();
+        assert_eq!(timeline.get_bar_events(0).len(), 4);
+        assert_eq!(
+            positions(timeline.get_bar_events(1)),
+            vec![Units::new(1, 1), Units::new(3, 2)]
+        );
+        assert!(timeline.get_bar_events(2).is_empty());
+        assert_eq!(
+            positions(timeline.get_track_events("piano")),
+            vec![Units::ZERO, Units::new(3, 2)]
+        );
+        let next = timeline.get_next_event(Units::ZERO).unwrap();
+        assert_eq!(next.in_tab_pos, Units::new(1, 3));
+        let next = timeline.get_next_event(Units::new(1, 1)).unwrap();
+        assert_eq!(next.track.id, "piano");
+        assert!(timeline.get_next_event(Units::new(3, 2)).is_none());
+    }
+
+    #[test]
+    fn test_seconds() {
+        let timeline = timeline();
+        assert_eq!(timeline.calc_seconds(Units::new(3, 2)), 3.0);
+        assert_eq!(timeline.calc_units(3.0), Units::new(3, 2));
+        assert!((timeline.events[3].seconds - 4.0 / 3.0).abs() < 1e-6);
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::{Arc, Weak};
 use notation_proto::prelude::Chord;
 
 use crate::prelude::{
-    Fretboard4, Fretboard6, ModelEntry, SliceBegin, SliceEnd, Tab, TabChord, TrackKind,
+    Fretboard4, Fretboard6, ModelEntry, Slice, Tab, TabChord, TrackKind,
 };
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -69,36 +69,18 @@ impl Track {
         }
         None
     }
-    pub fn get_entries(&self, begin: &SliceBegin, end: &SliceEnd) -> Vec<Arc<ModelEntry>> {
-        let (index, count) = match (begin, end) {
-            (SliceBegin::Mark(x), SliceEnd::Mark(y)) => match self.index_of_mark(0, x) {
-                Some(index) => {
-                    let index = index + 1;
-                    match self.index_of_mark(index, y) {
-                        Some(end) => (index, end - index),
-                        None => (index, 0),
-                    }
-                }
-                None => (0, 0),
-            },
-            (SliceBegin::Mark(x), SliceEnd::Count(y)) => match self.index_of_mark(0, x) {
-                Some(index) => (index + 1, *y),
-                None => (0, 0),
-            },
-            (SliceBegin::Index(x), SliceEnd::Mark(y)) => match self.index_of_mark(*x, y) {
-                Some(end) => (*x, end - 1 - *x),
-                None => (*x, 0),
-            },
-            (SliceBegin::Index(x), SliceEnd::Count(y)) => (*x, *y),
-        };
-        let mut entries = vec![];
-        for i in index..(index + count) {
-            let entry = self.entries.get(i);
-            if entry.is_some() {
-                entries.push(entry.unwrap().clone());
-            }
+    fn calc_slice_range(&self, slice: &Slice) -> Result<(usize, usize), String> {
+        slice.calc_range(self.entries.len(), |i, mark| self.entries[i].proto.is_mark_str(mark))
+    }
+    /// The mark of the slice not found in the track, which leaves the lane empty.
+    pub fn find_missing_mark(&self, slice: &Slice) -> Option<String> {
+        self.calc_slice_range(slice).err()
+    }
+    pub fn get_entries(&self, slice: &Slice) -> Vec<Arc<ModelEntry>> {
+        match self.calc_slice_range(slice) {
+            Ok((begin, end)) => self.entries[begin..end].to_vec(),
+            Err(_) => vec![],
         }
-        entries
     }
     pub fn get_tab_chords(&self) -> Vec<TabChord> {
         let mut chord_entries: HashMap<Chord, Vec<Arc<ModelEntry>>> = HashMap::new();
@@ -141,17 +123,19 @@ impl Track {
 }
 
 macro_rules! impl_get_fretboard {
-    ($name:ident, $strings:literal, $as_fretted:ident, $fretboard:ident) => {
+    ($name:ident, $strings:literal, $as_fretted:ident, $fretboard:ident, $default_fretboard:ident) => {
         impl Track {
+            /// Returns the fretboard of the track, fallback to the default one of the track kind.
             pub fn $name(&self) -> Option<$fretboard> {
                 self.get_entry(&|x: &ModelEntry| {
                     x.$as_fretted()
                         .and_then(|x| x.as_fretboard().map(|z| z.to_owned()))
                 })
+                .or_else(|| self.kind.$default_fretboard())
             }
         }
     };
 }
 
-impl_get_fretboard!(get_fretboard6, 6, as_fretted6, Fretboard6);
-impl_get_fretboard!(get_fretboard4, 4, as_fretted4, Fretboard4);
+impl_get_fretboard!(get_fretboard6, 6, as_fretted6, Fretboard6, default_fretboard6);
+impl_get_fretboard!(get_fretboard4, 4, as_fretted4, Fretboard4, default_fretboard4);
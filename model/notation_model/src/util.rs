@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use notation_proto::prelude::Units;
+
 use crate::prelude::{Bar, BarLane, BarLayer, LaneEntry, ModelEntry, TabBar, Track};
 
 pub fn get_track_entry<T, F: Fn(&ModelEntry) -> Option<T>>(
@@ -87,3 +89,47 @@ impl TabBar {
         None
     }
 }
+/// Whether releasing a key at `end` would cut another note of the same key, i.e. one
+/// of the `(start, end)` ranges started before `end` and is still sounding after it.
+///
+/// Voices of a track share the MIDI channel, so their notes of the same key can overlap.
+pub fn is_note_end_shadowed(ranges: &[(Units, Units)], end: Units) -> bool {
+    ranges
+        .iter()
+        .any(|(other_start, other_end)| *other_start < end && *other_end > end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: i64, end: i64) -> (Units, Units) {
+        (Units::new(start, 4), Units::new(end, 4))
+    }
+
+    #[test]
+    fn test_note_end_shadowed_by_overlap() {
+        // A half note in one voice, a whole note of the same key from the same start in another.
+        let ranges = vec![range(0, 2), range(0, 4)];
+        assert!(is_note_end_shadowed(&ranges, Units::new(2, 4)));
+        assert!(!is_note_end_shadowed(&ranges, Units::new(4, 4)));
+        // The whole note starts later, still sounding when the first one ends.
+        let ranges = vec![range(0, 2), range(1, 5)];
+        assert!(is_note_end_shadowed(&ranges, Units::new(2, 4)));
+        assert!(!is_note_end_shadowed(&ranges, Units::new(5, 4)));
+    }
+
+    #[test]
+    fn test_note_end_not_shadowed_without_overlap() {
+        // Repeated notes of the key one after another keep all their note offs.
+        let ranges = vec![range(0, 1), range(1, 2), range(2, 4)];
+        for (_, end) in ranges.iter() {
+            assert!(!is_note_end_shadowed(&ranges, *end));
+        }
+        // A note starting exactly at the end doesn't hold the key over it.
+        assert!(!is_note_end_shadowed(&[range(0, 2), range(2, 3)], Units::new(2, 4)));
+        // Nor does a shorter note finished inside it.
+        assert!(!is_note_end_shadowed(&[range(0, 4), range(1, 2)], Units::new(4, 4)));
+        assert!(!is_note_end_shadowed(&[], Units::ZERO));
+    }
+}
@@ -3,17 +3,27 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BarLayer {
     pub track: String,
     pub slices: Vec<Slice>,
+    /// Layers of the same track with different voices play at the same time,
+    /// e.g. the bass and melody of a fingerstyle part.
+    #[serde(default)]
+    pub voice: usize,
 }
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Bar {
     pub layers: Vec<BarLayer>,
 }
 impl Display for BarLayer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "<BarLayer>({} S:{})", self.track, self.slices.len())
+        write!(f, "<BarLayer>({} S:{}", self.track, self.slices.len())?;
+        if self.voice != Self::MAIN_VOICE {
+            write!(f, " V:{}", self.voice)?;
+        }
+        write!(f, ")")
     }
 }
 impl Display for Bar {
@@ -27,8 +37,16 @@ impl Bar {
     }
 }
 impl BarLayer {
+    pub const MAIN_VOICE: usize = 0;
     pub fn new(track: String, slices: Vec<Slice>) -> Self {
-        Self { track, slices }
+        Self::new_voice(track, Self::MAIN_VOICE, slices)
+    }
+    pub fn new_voice(track: String, voice: usize, slices: Vec<Slice>) -> Self {
+        Self {
+            track,
+            slices,
+            voice,
+        }
     }
 }
 impl From<(String, Vec<Slice>)> for BarLayer {
@@ -0,0 +1,187 @@
+use std::fmt::Display;
+
+use notation_core::prelude::EntryPassMode;
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::{Duration, Entry};
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DrumPiece {
+    Kick,
+    Snare,
+    HiHatClosed,
+    HiHatOpen,
+    HighTom,
+    MidTom,
+    FloorTom,
+    Crash,
+    Ride,
+}
+impl Display for DrumPiece {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_ident())
+    }
+}
+impl DrumPiece {
+    pub const ALL: [Self; 9] = [
+        Self::Crash,
+        Self::Ride,
+        Self::HiHatOpen,
+        Self::HiHatClosed,
+        Self::HighTom,
+        Self::MidTom,
+        Self::Snare,
+        Self::FloorTom,
+        Self::Kick,
+    ];
+    /// Row of this piece in the percussion grid, cymbals on top and kick at the bottom.
+    pub fn row(&self) -> usize {
+        Self::ALL.iter().position(|x| x == self).unwrap_or(0)
+    }
+    /// General MIDI percussion key number (channel 10).
+    pub fn gm_key(&self) -> u8 {
+        match self {
+            Self::Kick => 36,
+            Self::Snare => 38,
+            Self::HiHatClosed => 42,
+            Self::HiHatOpen => 46,
+            Self::HighTom => 50,
+            Self::MidTom => 47,
+            Self::FloorTom => 43,
+            Self::Crash => 49,
+            Self::Ride => 51,
+        }
+    }
+    pub fn to_ident(&self) -> String {
+        match self {
+            Self::Kick => "K",
+            Self::Snare => "S",
+            Self::HiHatClosed => "H",
+            Self::HiHatOpen => "O",
+            Self::HighTom => "T1",
+            Self::MidTom => "T2",
+            Self::FloorTom => "T3",
+            Self::Crash => "C",
+            Self::Ride => "R",
+        }
+        .to_owned()
+    }
+    pub fn from_ident(ident: &str) -> Option<Self> {
+        match ident {
+            "K" | "Kick" => Some(Self::Kick),
+            "S" | "Snare" => Some(Self::Snare),
+            "H" | "HiHat" | "HiHatClosed" => Some(Self::HiHatClosed),
+            "O" | "HiHatOpen" => Some(Self::HiHatOpen),
+            "T1" | "HighTom" => Some(Self::HighTom),
+            "T2" | "MidTom" => Some(Self::MidTom),
+            "T3" | "FloorTom" => Some(Self::FloorTom),
+            "C" | "Crash" => Some(Self::Crash),
+            "R" | "Ride" => Some(Self::Ride),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DrumHit {
+    pub piece: DrumPiece,
+    pub accent: bool,
+    pub flam: bool,
+}
+impl Display for DrumHit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            if self.flam { "~" } else { "" },
+            self.piece,
+            if self.accent { "^" } else { "" }
+        )
+    }
+}
+impl DrumHit {
+    pub fn new(piece: DrumPiece, accent: bool, flam: bool) -> Self {
+        Self {
+            piece,
+            accent,
+            flam,
+        }
+    }
+}
+impl From<DrumPiece> for DrumHit {
+    fn from(v: DrumPiece) -> Self {
+        Self::new(v, false, false)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum DrumEntry {
+    Hits(Vec<DrumHit>, Duration),
+}
+impl Display for DrumEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrumEntry::Hits(hits, duration) => {
+                let hits: Vec<String> = hits.iter().map(|x| x.to_string()).collect();
+                write!(f, "Hits({}, {})", hits.join(" "), duration)
+            }
+        }
+    }
+}
+impl DrumEntry {
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Hits(_, duration) => *duration,
+        }
+    }
+    pub fn pass_mode(&self) -> EntryPassMode {
+        EntryPassMode::Delayed
+    }
+    pub fn get_hits(&self) -> &Vec<DrumHit> {
+        match self {
+            Self::Hits(hits, _) => hits,
+        }
+    }
+}
+
+impl Entry for DrumEntry {
+    fn duration(&self) -> Duration {
+        self.duration()
+    }
+    fn pass_mode(&self) -> EntryPassMode {
+        self.pass_mode()
+    }
+}
+
+impl From<(Vec<DrumHit>, Duration)> for DrumEntry {
+    fn from(v: (Vec<DrumHit>, Duration)) -> Self {
+        DrumEntry::Hits(v.0, v.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gm_keys() {
+        let keys: Vec<u8> = DrumPiece::ALL.iter().map(|x| x.gm_key()).collect();
+        assert_eq!(keys, vec![49, 51, 46, 42, 50, 47, 38, 43, 36]);
+        assert!(keys.iter().all(|x| (35..=81).contains(x)));
+    }
+
+    #[test]
+    fn test_idents() {
+        for piece in DrumPiece::ALL.iter() {
+            assert_eq!(DrumPiece::from_ident(&piece.to_ident()), Some(*piece));
+        }
+        assert_eq!(DrumPiece::from_ident("HiHat"), Some(DrumPiece::HiHatClosed));
+        assert_eq!(DrumPiece::from_ident("X"), None);
+        assert_eq!(DrumPiece::Kick.row(), DrumPiece::ALL.len() - 1);
+        let hit = DrumHit::new(DrumPiece::Snare, true, true);
+        assert_eq!(hit.to_string(), "~S^");
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::RwLock;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use notation_core::prelude::{Duration, Entry, EntryPassMode};
+
+#[derive(Clone, Error, Debug)]
+pub enum ExtensionError {
+    #[error("extension kind mismatch: expected {expected}, found {found}")]
+    KindMismatch { expected: String, found: String },
+    #[error("decode extension {kind} failed: {reason}")]
+    DecodeFailed { kind: String, reason: String },
+    #[error("encode extension {kind} failed: {reason}")]
+    EncodeFailed { kind: String, reason: String },
+}
+
+/// Payload type of an extension entry, defined outside of this crate.
+///
+/// The kind is stored with the entry and picks the type back on load, so it
+/// should be unique, e.g. prefixed with the crate name.
+pub trait ExtensionType: Serialize + DeserializeOwned + Display {
+    const KIND: &'static str;
+
+    fn duration(&self) -> Duration {
+        Duration::Zero
+    }
+    fn pass_mode(&self) -> EntryPassMode {
+        EntryPassMode::Immediate
+    }
+}
+
+/// Entry with a payload only known to the crate that defines its kind.
+///
+/// The payload is kept as RON text, so entries of kinds that are not registered
+/// still round-trip through any [`TabFormat`](crate::prelude::TabFormat).
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtensionEntry {
+    pub kind: String,
+    pub payload: String,
+    pub duration: Duration,
+    pub pass_mode: EntryPassMode,
+}
+impl Display for ExtensionEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<ExtensionEntry>({}: {})", self.kind, self.payload)
+    }
+}
+impl ExtensionEntry {
+    /// Untyped entry with zero duration, the payload is plain text.
+    pub fn new(kind: String, payload: String) -> Self {
+        Self {
+            kind,
+            payload,
+            duration: Duration::Zero,
+            pass_mode: EntryPassMode::Immediate,
+        }
+    }
+    pub fn from_value<T: ExtensionType>(value: &T) -> Result<Self, ExtensionError> {
+        let payload = ron::to_string(value).map_err(|x| ExtensionError::EncodeFailed {
+            kind: T::KIND.to_owned(),
+            reason: x.to_string(),
+        })?;
+        Ok(Self {
+            kind: T::KIND.to_owned(),
+            payload,
+            duration: value.duration(),
+            pass_mode: value.pass_mode(),
+        })
+    }
+    pub fn is<T: ExtensionType>(&self) -> bool {
+        self.kind == T::KIND
+    }
+    pub fn get<T: ExtensionType>(&self) -> Result<T, ExtensionError> {
+        if !self.is::<T>() {
+            return Err(ExtensionError::KindMismatch {
+                expected: T::KIND.to_owned(),
+                found: self.kind.clone(),
+            });
+        }
+        ron::from_str(&self.payload).map_err(|x| ExtensionError::DecodeFailed {
+            kind: self.kind.clone(),
+            reason: x.to_string(),
+        })
+    }
+}
+impl Entry for ExtensionEntry {
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+    fn pass_mode(&self) -> EntryPassMode {
+        self.pass_mode
+    }
+}
+
+type DescribeFn = fn(&ExtensionEntry) -> Result<String, ExtensionError>;
+
+lazy_static! {
+    static ref REGISTRY: RwLock<BTreeMap<String, DescribeFn>> = RwLock::new(BTreeMap::new());
+}
+
+fn describe_value<T: ExtensionType>(entry: &ExtensionEntry) -> Result<String, ExtensionError> {
+    entry.get::<T>().map(|x| x.to_string())
+}
+
+/// Kinds of extension entries known to the running app.
+pub struct ExtensionRegistry();
+
+impl ExtensionRegistry {
+    pub fn register<T: ExtensionType>() {
+        REGISTRY
+            .write()
+            .unwrap()
+            .insert(T::KIND.to_owned(), describe_value::<T>);
+    }
+    pub fn is_registered(kind: &str) -> bool {
+        REGISTRY.read().unwrap().contains_key(kind)
+    }
+    pub fn get_kinds() -> Vec<String> {
+        REGISTRY.read().unwrap().keys().cloned().collect()
+    }
+    /// Text of the payload, decoded by the registered type if there is one.
+    pub fn describe(entry: &ExtensionEntry) -> String {
+        let describe = REGISTRY.read().unwrap().get(&entry.kind).copied();
+        match describe.map(|x| x(entry)) {
+            Some(Ok(text)) => text,
+            _ => entry.payload.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::Unit;
+
+    use super::*;
+    use crate::prelude::{ProtoEntry, Tab, TabFormat, Track, TrackKind};
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+    struct Fermata {
+        beats: u8,
+        text: String,
+    }
+    impl Display for Fermata {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{} ({})", self.text, self.beats)
+        }
+    }
+    impl ExtensionType for Fermata {
+        const KIND: &'static str = "notation_proto::test::Fermata";
+
+        fn duration(&self) -> Duration {
+            Duration::Simple(Unit::Quarter)
+        }
+    }
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+    struct Unregistered(u8);
+    impl Display for Unregistered {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    impl ExtensionType for Unregistered {
+        const KIND: &'static str = "notation_proto::test::Unregistered";
+    }
+
+    fn fermata() -> Fermata {
+        Fermata {
+            beats: 2,
+            text: "hold \"long\", ∞".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let entry = ExtensionEntry::from_value(&fermata()).unwrap();
+        assert_eq!(entry.kind, Fermata::KIND);
+        assert_eq!(entry.duration, Duration::Simple(Unit::Quarter));
+        assert_eq!(entry.pass_mode, EntryPassMode::Immediate);
+        assert!(entry.is::<Fermata>());
+        assert_eq!(entry.get::<Fermata>().unwrap(), fermata());
+    }
+
+    #[test]
+    fn test_tab_format_round_trip() {
+        let mut tab = Tab::new_empty();
+        let entries = vec![ProtoEntry::Extension(ExtensionEntry::from_value(&fermata()).unwrap())];
+        tab.tracks.push(Track::new("vocal".into(), TrackKind::Vocal, entries));
+        for format in TabFormat::ALL {
+            let decoded = Tab::decode(format, &tab.encode(format).unwrap()).unwrap();
+            let entry = decoded.tracks[0].entries[0].as_extension().unwrap();
+            assert_eq!(entry.get::<Fermata>().unwrap(), fermata(), "{}", format);
+            assert_eq!(entry.duration, Duration::Simple(Unit::Quarter), "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let entry = ExtensionEntry::from_value(&fermata()).unwrap();
+        assert!(!entry.is::<Unregistered>());
+        assert!(matches!(
+            entry.get::<Unregistered>(),
+            Err(ExtensionError::KindMismatch { expected, found })
+                if expected == Unregistered::KIND && found == Fermata::KIND
+        ));
+        let broken = ExtensionEntry::new(Fermata::KIND.to_owned(), "(beats: 2)".to_owned());
+        assert!(matches!(
+            broken.get::<Fermata>(),
+            Err(ExtensionError::DecodeFailed { kind, .. }) if kind == Fermata::KIND
+        ));
+    }
+
+    #[test]
+    fn test_describe() {
+        ExtensionRegistry::register::<Fermata>();
+        assert!(ExtensionRegistry::is_registered(Fermata::KIND));
+        assert!(ExtensionRegistry::get_kinds().contains(&Fermata::KIND.to_owned()));
+        let entry = ExtensionEntry::from_value(&fermata()).unwrap();
+        assert_eq!(ExtensionRegistry::describe(&entry), "hold \"long\", ∞ (2)");
+        // Payloads that don't decode, or of unknown kinds, are shown as they are.
+        let broken = ExtensionEntry::new(Fermata::KIND.to_owned(), "(beats: 2)".to_owned());
+        assert_eq!(ExtensionRegistry::describe(&broken), "(beats: 2)");
+        let unknown = ExtensionEntry::from_value(&Unregistered(3)).unwrap();
+        assert!(!ExtensionRegistry::is_registered(Unregistered::KIND));
+        assert_eq!(ExtensionRegistry::describe(&unknown), "(3)");
+    }
+}
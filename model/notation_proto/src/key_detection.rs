@@ -0,0 +1,313 @@
+use std::fmt::Display;
+
+use notation_core::prelude::{
+    Chord, CoreEntry, Key, MetaEntry, Note, Pitch, Scale, Semitones, Tone, Units,
+};
+use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6};
+
+use crate::prelude::{ProtoEntry, Tab, TabMeta, Track};
+
+/// A possible key of the tab, higher score is better.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct KeyCandidate {
+    pub key: Key,
+    pub scale: Scale,
+    pub score: f32,
+}
+impl Display for KeyCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<KeyCandidate>({} {} {:.3})", self.key, self.scale, self.score)
+    }
+}
+
+/// Duration weighted pitch classes of the tab, index 0 is C.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub struct PitchProfile {
+    pub notes: [f32; 12],
+    pub chord_roots: [f32; 12],
+    pub first_root: Option<usize>,
+    pub last_root: Option<usize>,
+}
+
+/// Weights of the scale degrees in the templates, the rest of the scale gets 1.0.
+const TONIC_WEIGHT: f32 = 3.0;
+const FIFTH_WEIGHT: f32 = 2.0;
+const THIRD_WEIGHT: f32 = 1.5;
+/// Bonus for songs starting or ending on the tonic chord.
+const FIRST_ROOT_BONUS: f32 = 0.1;
+const LAST_ROOT_BONUS: f32 = 0.2;
+
+fn pitch_class(semitones: Semitones) -> usize {
+    semitones.0.rem_euclid(12) as usize
+}
+
+impl PitchProfile {
+    pub fn is_empty(&self) -> bool {
+        self.notes.iter().all(|x| *x == 0.0) && self.chord_roots.iter().all(|x| *x == 0.0)
+    }
+    pub fn add_note(&mut self, note: &Note, units: Units) {
+        self.notes[pitch_class(Semitones::from(*note))] += units.to_f32();
+    }
+    pub fn add_chord(&mut self, meta: &TabMeta, chord: &Chord, units: Units) {
+        let root = pitch_class(meta.scale.calc_do_semitones(&meta.key) + Semitones::from(chord.root));
+        self.chord_roots[root] += units.to_f32();
+        if self.first_root.is_none() {
+            self.first_root = Some(root);
+        }
+        self.last_root = Some(root);
+    }
+    /// Template of the scale on the tonic, tonic, fifth and third are weighted more.
+    fn calc_template(scale: &Scale, tonic: usize) -> [f32; 12] {
+        let root = Semitones::from(scale.calc_root_syllable());
+        let mut template = [0.0; 12];
+        for (index, syllable) in scale.get_syllables().iter().enumerate() {
+            let weight = match index {
+                0 => TONIC_WEIGHT,
+                4 => FIFTH_WEIGHT,
+                2 => THIRD_WEIGHT,
+                _ => 1.0,
+            };
+            let interval = Semitones::from(*syllable) - root;
+            template[pitch_class(interval + Semitones(tonic as i8))] = weight;
+        }
+        template
+    }
+    /// Pearson correlation, 0.0 if either side is flat.
+    fn correlate(a: &[f32; 12], b: &[f32; 12]) -> f32 {
+        let mean_a = a.iter().sum::<f32>() / 12.0;
+        let mean_b = b.iter().sum::<f32>() / 12.0;
+        let mut cov = 0.0;
+        let mut var_a = 0.0;
+        let mut var_b = 0.0;
+        for i in 0..12 {
+            cov += (a[i] - mean_a) * (b[i] - mean_b);
+            var_a += (a[i] - mean_a) * (a[i] - mean_a);
+            var_b += (b[i] - mean_b) * (b[i] - mean_b);
+        }
+        if var_a == 0.0 || var_b == 0.0 {
+            0.0
+        } else {
+            cov / (var_a * var_b).sqrt()
+        }
+    }
+    pub fn calc_score(&self, scale: &Scale, tonic: usize) -> f32 {
+        let template = Self::calc_template(scale, tonic);
+        let mut score = Self::correlate(&self.notes, &template) + Self::correlate(&self.chord_roots, &template);
+        if self.first_root == Some(tonic) {
+            score += FIRST_ROOT_BONUS;
+        }
+        if self.last_root == Some(tonic) {
+            score += LAST_ROOT_BONUS;
+        }
+        score
+    }
+    /// All the modes on all the tonics, best match first.
+    pub fn calc_key_candidates(&self) -> Vec<KeyCandidate> {
+        let mut candidates = Vec::new();
+        for scale in Scale::MODES.iter() {
+            for key in scale.get_keys().iter() {
+                let tonic = pitch_class(Semitones::from(*key));
+                candidates.push(KeyCandidate {
+                    key: *key,
+                    scale: *scale,
+                    score: self.calc_score(scale, tonic),
+                });
+            }
+        }
+        candidates.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        candidates
+    }
+}
+
+macro_rules! impl_add_shapes {
+    ($name:ident, $fretted_entry:ident, $as_fretted:ident, $default_fretboard:ident, $strings:literal) => {
+        impl PitchProfile {
+            fn $name(&mut self, meta: &TabMeta, track: &Track) {
+                let fretboard = track
+                    .entries
+                    .iter()
+                    .find_map(|x| match x.$as_fretted() {
+                        Some($fretted_entry::Fretboard(fretboard)) => Some(*fretboard),
+                        _ => None,
+                    })
+                    .or_else(|| track.kind.$default_fretboard());
+                let fretboard = match fretboard {
+                    Some(fretboard) => fretboard,
+                    None => return,
+                };
+                for entry in track.entries.iter() {
+                    if let Some($fretted_entry::Shape(shape, duration)) = entry.$as_fretted() {
+                        for string in 1..=$strings {
+                            if let Some(note) = fretboard.shape_note(&meta.scale, &meta.key, shape, string) {
+                                self.add_note(&note, Units::from(*duration));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_add_shapes!(add_shapes6, FrettedEntry6, as_fretted6, default_fretboard6, 6);
+impl_add_shapes!(add_shapes4, FrettedEntry4, as_fretted4, default_fretboard4, 4);
+
+impl Tab {
+    /// Collect the notes of tones and hand shapes, and the chord roots.
+    pub fn calc_pitch_profile(&self) -> PitchProfile {
+        let mut profile = PitchProfile::default();
+        for track in self.tracks.iter() {
+            for entry in track.entries.iter() {
+                match entry {
+                    ProtoEntry::Core(CoreEntry::Tone(tone, duration)) => {
+                        for note in tone.get_notes() {
+                            profile.add_note(&note, Units::from(*duration));
+                        }
+                    }
+                    ProtoEntry::Core(CoreEntry::Chord(chord, duration)) => {
+                        profile.add_chord(&self.meta, chord, Units::from(*duration));
+                    }
+                    _ => {}
+                }
+            }
+            profile.add_shapes6(&self.meta, track);
+            profile.add_shapes4(&self.meta, track);
+        }
+        profile
+    }
+    /// Keys and modes that fit the tab, best match first, empty if there is nothing to go by.
+    pub fn calc_key_candidates(&self) -> Vec<KeyCandidate> {
+        let profile = self.calc_pitch_profile();
+        if profile.is_empty() {
+            return vec![];
+        }
+        profile.calc_key_candidates()
+    }
+    /// Change the key of the tab without changing the sound, syllables of notes and
+    /// chord roots are recalculated, and pitches respelled for the new key.
+    pub fn rebase_key(&mut self, key: Key, scale: Scale) {
+        let old = self.meta;
+        let new = TabMeta { key, scale, ..old };
+        for track in self.tracks.iter_mut() {
+            for entry in track.entries.iter_mut() {
+                if let Some(rebased) = Self::rebase_entry(&old, &new, entry) {
+                    *entry = rebased;
+                }
+            }
+        }
+        self.meta = new;
+    }
+    fn rebase_entry(old: &TabMeta, new: &TabMeta, entry: &ProtoEntry) -> Option<ProtoEntry> {
+        match entry {
+            ProtoEntry::Core(CoreEntry::Tone(tone, duration)) => {
+                let notes: Vec<Note> = tone
+                    .get_notes()
+                    .into_iter()
+                    .map(|x| new.scale.calc_note_from_semitones(&new.key, Semitones::from(x)))
+                    .collect();
+                Some(CoreEntry::Tone(Tone::from(notes), *duration).into())
+            }
+            ProtoEntry::Core(CoreEntry::Chord(chord, duration)) => {
+                let root = old.scale.calc_do_semitones(&old.key) + Semitones::from(chord.root);
+                let root = new.calc_syllable(&Pitch::from(root));
+                Some(CoreEntry::Chord(Chord { root, ..*chord }, *duration).into())
+            }
+            ProtoEntry::Meta(MetaEntry::Key(x)) if *x == old.key => Some(MetaEntry::Key(new.key).into()),
+            ProtoEntry::Meta(MetaEntry::Scale(x)) if *x == old.scale => Some(MetaEntry::Scale(new.scale).into()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Duration, Interval, Intervals, Octave, Syllable, Unit};
+
+    use super::*;
+    use crate::prelude::TrackKind;
+
+    fn melody_tab(meta: TabMeta, notes: &[(Pitch, Unit)], chords: &[(Syllable, Unit)]) -> Tab {
+        let tones = notes
+            .iter()
+            .map(|(pitch, unit)| {
+                let note = meta.calc_note_from_pitch(pitch, &Octave::P4);
+                CoreEntry::Tone(Tone::from(note), Duration::Simple(*unit)).into()
+            })
+            .collect();
+        let chords = chords
+            .iter()
+            .map(|(root, unit)| {
+                let intervals = Intervals::Triad(Interval::Major3nd, Interval::Perfect5th);
+                let chord = Chord::new(*root, intervals, None);
+                CoreEntry::Chord(chord, Duration::Simple(*unit)).into()
+            })
+            .collect();
+        let mut tab = Tab::new_empty();
+        tab.meta = meta;
+        tab.tracks.push(Track::new("vocal".into(), TrackKind::Vocal, tones));
+        tab.tracks.push(Track::new("chord".into(), TrackKind::Chord, chords));
+        tab
+    }
+
+    fn g_major_melody() -> Vec<(Pitch, Unit)> {
+        vec![
+            (Pitch::G, Unit::Whole),
+            (Pitch::A, Unit::Quarter),
+            (Pitch::B, Unit::Half),
+            (Pitch::C, Unit::Quarter),
+            (Pitch::D, Unit::Half),
+            (Pitch::E, Unit::Quarter),
+            (Pitch::F_SHARP, Unit::Quarter),
+            (Pitch::G, Unit::Whole),
+        ]
+    }
+
+    #[test]
+    fn test_empty_tab_has_no_candidates() {
+        assert!(Tab::new_empty().calc_key_candidates().is_empty());
+    }
+
+    #[test]
+    fn test_detect_major_key_from_melody() {
+        let tab = melody_tab(TabMeta::default(), &g_major_melody(), &[]);
+        let candidates = tab.calc_key_candidates();
+        assert_eq!(candidates.len(), Scale::MODES.len() * 12);
+        assert_eq!(candidates[0].key, Key::G);
+        assert_eq!(candidates[0].scale, Scale::Major);
+        assert!(candidates.windows(2).all(|x| x[0].score >= x[1].score));
+    }
+
+    #[test]
+    fn test_chord_roots_break_ties_between_relative_keys() {
+        let melody = g_major_melody();
+        let chords = [
+            (Syllable::La, Unit::Whole),
+            (Syllable::Re, Unit::Whole),
+            (Syllable::La, Unit::Whole),
+        ];
+        let tab = melody_tab(TabMeta::default(), &melody, &chords);
+        let profile = tab.calc_pitch_profile();
+        assert_eq!(profile.first_root, Some(9));
+        assert_eq!(profile.last_root, Some(9));
+        let e_minor = profile.calc_score(&Scale::Minor, pitch_class(Semitones::from(Key::E)));
+        let a_minor = profile.calc_score(&Scale::Minor, pitch_class(Semitones::from(Key::A)));
+        assert!(a_minor > e_minor);
+    }
+
+    #[test]
+    fn test_rebase_key_keeps_sound() {
+        let mut tab = melody_tab(TabMeta::default(), &g_major_melody(), &[(Syllable::So, Unit::Whole)]);
+        let before = tab.tracks[0].entries.clone();
+        tab.rebase_key(Key::G, Scale::Major);
+        assert_eq!(tab.meta.key, Key::G);
+        for (old, new) in before.iter().zip(tab.tracks[0].entries.iter()) {
+            let old = old.as_core().and_then(|x| x.as_tone()).unwrap();
+            let new = new.as_core().and_then(|x| x.as_tone()).unwrap();
+            let old: Vec<Semitones> = old.get_notes().into_iter().map(Semitones::from).collect();
+            let new: Vec<Semitones> = new.get_notes().into_iter().map(Semitones::from).collect();
+            assert_eq!(old, new);
+        }
+        let chord = tab.tracks[1].entries[0].as_core().and_then(|x| x.as_chord()).unwrap();
+        assert_eq!(chord.root, Syllable::Do);
+    }
+}
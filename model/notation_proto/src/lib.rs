@@ -1,21 +1,39 @@
+#[macro_use]
+extern crate lazy_static;
+
 pub use uuid;
 pub use {notation_core, notation_fretted, notation_guitar};
 
 pub mod bar;
+pub mod drum_entry;
+pub mod extension_entry;
+pub mod key_detection;
 pub mod lyric_entry;
 pub mod position;
 pub mod proto_entry;
 pub mod section;
 pub mod slice;
 pub mod tab;
+pub mod tab_format;
 pub mod track;
+pub mod transpose;
+pub mod validation;
+pub mod vocal_range;
 pub mod prelude {
     #[doc(hidden)]
     pub use uuid::Uuid;
     #[doc(hidden)]
     pub use crate::bar::{Bar, BarLayer};
     #[doc(hidden)]
-    pub use crate::lyric_entry::{LyricEntry, LyricWord};
+    pub use crate::drum_entry::{DrumEntry, DrumHit, DrumPiece};
+    #[doc(hidden)]
+    pub use crate::extension_entry::{
+        ExtensionEntry, ExtensionError, ExtensionRegistry, ExtensionType,
+    };
+    #[doc(hidden)]
+    pub use crate::key_detection::{KeyCandidate, PitchProfile};
+    #[doc(hidden)]
+    pub use crate::lyric_entry::{LyricEntry, LyricExtender, LyricLine, LyricRow, LyricWord};
     #[doc(hidden)]
     pub use crate::position::{BarPosition, Position, TabPosition};
     #[doc(hidden)]
@@ -27,8 +45,19 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::tab::{Tab, TabMeta};
     #[doc(hidden)]
+    pub use crate::tab_format::{
+        BarLayerV2, BarV2, ProtoEntryV1, SectionV2, TabDocument, TabFormat, TabFormatError,
+        TabV1, TabV2, TrackV1,
+    };
+    #[doc(hidden)]
     pub use crate::track::{Track, TrackKind};
     #[doc(hidden)]
+    pub use crate::transpose::{CapoSuggestion, TransposeMode};
+    #[doc(hidden)]
+    pub use crate::validation::{Diagnostic, DiagnosticKind, DiagnosticLocation, Severity};
+    #[doc(hidden)]
+    pub use crate::vocal_range::{VocalFit, VocalRange};
+    #[doc(hidden)]
     pub use notation_core::prelude::*;
     #[doc(hidden)]
     pub use notation_fretted::prelude::*;
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 use crate::prelude::{Duration, Entry};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct LyricWord {
     pub text: String,
 }
@@ -26,21 +27,137 @@ impl From<&str> for LyricWord {
     }
 }
 
+/// How a syllable connects to the notes after it.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum LyricExtender {
+    #[default]
+    None,
+    /// The word goes on with the next syllable, shown as `-`.
+    Hyphen,
+    /// The syllable is held over the following notes, shown as `_`.
+    Melisma,
+}
+impl Display for LyricExtender {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl LyricExtender {
+    pub fn to_text(&self) -> &'static str {
+        match self {
+            Self::None => "",
+            Self::Hyphen => "-",
+            Self::Melisma => "_",
+        }
+    }
+}
+
+/// Verse and language of a lyric line, lines of the same row are shown together.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LyricRow {
+    pub verse: u8,
+    /// Language tag, e.g. `en` or `zh-CN`, empty if not specified.
+    pub lang: String,
+}
+impl Display for LyricRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.lang.is_empty() {
+            write!(f, "{}", self.verse)
+        } else {
+            write!(f, "{}:{}", self.verse, self.lang)
+        }
+    }
+}
+impl LyricRow {
+    pub fn new(verse: u8, lang: String) -> Self {
+        Self { verse, lang }
+    }
+}
+
+/// Syllable of one verse in one language, with optional reading for ruby text.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LyricLine {
+    pub verse: u8,
+    #[serde(default)]
+    pub lang: String,
+    pub word: LyricWord,
+    /// Reading of the word, e.g. pinyin or romaji.
+    #[serde(default)]
+    pub phonetic: Option<String>,
+    #[serde(default)]
+    pub extender: LyricExtender,
+}
+impl Display for LyricLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.word, self.extender.to_text())?;
+        if let Some(phonetic) = &self.phonetic {
+            write!(f, "({})", phonetic)?;
+        }
+        write!(f, "@{}", self.row())
+    }
+}
+impl LyricLine {
+    pub fn new(verse: u8, lang: String, word: LyricWord) -> Self {
+        Self {
+            verse,
+            lang,
+            word,
+            phonetic: None,
+            extender: LyricExtender::None,
+        }
+    }
+    pub fn with_phonetic(&self, phonetic: String) -> Self {
+        Self {
+            phonetic: Some(phonetic),
+            ..self.clone()
+        }
+    }
+    pub fn with_extender(&self, extender: LyricExtender) -> Self {
+        Self {
+            extender,
+            ..self.clone()
+        }
+    }
+    pub fn row(&self) -> LyricRow {
+        LyricRow::new(self.verse, self.lang.clone())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum LyricEntry {
     Word(LyricWord, Duration),
+    /// Syllables of several verses or languages sung on the same notes.
+    Lines(Vec<LyricLine>, Duration),
 }
 impl Display for LyricEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LyricEntry::Word(word, duration) => write!(f, "Word({}, {})", word, duration),
+            LyricEntry::Lines(lines, duration) => {
+                let lines: Vec<String> = lines.iter().map(|x| x.to_string()).collect();
+                write!(f, "Lines({}, {})", lines.join(" "), duration)
+            }
         }
     }
 }
 impl LyricEntry {
+    /// Plain words are the first verse, without language.
+    pub const WORD_VERSE: u8 = 1;
+
     pub fn duration(&self) -> Duration {
         match self {
             Self::Word(_, duration) => *duration,
+            Self::Lines(_, duration) => *duration,
+        }
+    }
+    pub fn get_lines(&self) -> Vec<LyricLine> {
+        match self {
+            Self::Word(word, _) => vec![LyricLine::new(Self::WORD_VERSE, String::new(), word.clone())],
+            Self::Lines(lines, _) => lines.clone(),
         }
     }
     pub fn pass_mode(&self) -> EntryPassMode {
@@ -74,3 +191,51 @@ impl From<(&str, Duration)> for LyricEntry {
         LyricEntry::Word(LyricWord::from(v.0), v.1)
     }
 }
+
+impl From<(Vec<LyricLine>, Duration)> for LyricEntry {
+    fn from(v: (Vec<LyricLine>, Duration)) -> Self {
+        LyricEntry::Lines(v.0, v.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_lines() {
+        let duration = Duration::_1_4;
+        let word = LyricEntry::from(("Hel", duration));
+        assert_eq!(
+            word.get_lines(),
+            vec![LyricLine::new(LyricEntry::WORD_VERSE, "".into(), "Hel".into())]
+        );
+        let lines = vec![
+            LyricLine::new(1, "".into(), "Hel".into()).with_extender(LyricExtender::Hyphen),
+            LyricLine::new(2, "".into(), "Good".into()),
+            LyricLine::new(1, "zh".into(), "你".into()).with_phonetic("nǐ".into()),
+        ];
+        let entry = LyricEntry::from((lines.clone(), duration));
+        assert_eq!(entry.get_lines(), lines);
+        assert_eq!(
+            entry.get_lines().iter().map(|x| x.row().to_string()).collect::<Vec<_>>(),
+            vec!["1", "2", "1:zh"]
+        );
+        assert_eq!(entry.duration(), duration);
+    }
+
+    #[test]
+    fn test_line_serde_defaults() {
+        let line: LyricLine = ron::from_str(r#"(verse: 2, word: (text: "Good"))"#).unwrap();
+        assert_eq!(line, LyricLine::new(2, "".into(), "Good".into()));
+        assert_eq!(line.phonetic, None);
+        assert_eq!(line.extender, LyricExtender::None);
+        let line: LyricLine =
+            serde_json::from_str(r#"{"verse": 1, "lang": "zh", "word": {"text": "你"}, "phonetic": "nǐ"}"#)
+                .unwrap();
+        assert_eq!(line, LyricLine::new(1, "zh".into(), "你".into()).with_phonetic("nǐ".into()));
+        let text = ron::to_string(&line.with_extender(LyricExtender::Melisma)).unwrap();
+        let parsed: LyricLine = ron::from_str(&text).unwrap();
+        assert_eq!(parsed.extender, LyricExtender::Melisma);
+    }
+}
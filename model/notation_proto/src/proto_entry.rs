@@ -2,12 +2,13 @@ use std::fmt::Display;
 
 use serde::{Deserialize, Serialize};
 
-use notation_core::prelude::{CoreEntry, Duration, Entry, EntryPassMode, MetaEntry};
+use notation_core::prelude::{CoreEntry, Duration, Entry, EntryPassMode, MetaEntry, Ornament};
 use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6};
 
-use crate::prelude::LyricEntry;
+use crate::prelude::{DrumEntry, ExtensionEntry, LyricEntry};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum ProtoEntry {
     Mark(String),
     Meta(MetaEntry),
@@ -15,7 +16,8 @@ pub enum ProtoEntry {
     Lyric(LyricEntry),
     Fretted6(FrettedEntry6),
     Fretted4(FrettedEntry4),
-    Extra(String, String),
+    Drum(DrumEntry),
+    Extension(ExtensionEntry),
 }
 impl Display for ProtoEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -26,7 +28,8 @@ impl Display for ProtoEntry {
             ProtoEntry::Lyric(x) => write!(f, "Lyric({})", x),
             ProtoEntry::Fretted6(x) => write!(f, "Fretted6({})", x),
             ProtoEntry::Fretted4(x) => write!(f, "Fretted4({})", x),
-            ProtoEntry::Extra(x, y) => write!(f, "Extra({}, {})", x, y),
+            ProtoEntry::Drum(x) => write!(f, "Drum({})", x),
+            ProtoEntry::Extension(x) => write!(f, "Extension({})", x),
         }
     }
 }
@@ -39,7 +42,8 @@ impl ProtoEntry {
             ProtoEntry::Lyric(entry) => entry.duration(),
             ProtoEntry::Fretted6(entry) => entry.duration(),
             ProtoEntry::Fretted4(entry) => entry.duration(),
-            ProtoEntry::Extra(_, _) => Duration::Zero,
+            ProtoEntry::Drum(entry) => entry.duration(),
+            ProtoEntry::Extension(entry) => entry.duration(),
         }
     }
     pub fn pass_mode(&self) -> EntryPassMode {
@@ -50,7 +54,8 @@ impl ProtoEntry {
             ProtoEntry::Lyric(entry) => entry.pass_mode(),
             ProtoEntry::Fretted6(entry) => entry.pass_mode(),
             ProtoEntry::Fretted4(entry) => entry.pass_mode(),
-            ProtoEntry::Extra(_, _) => EntryPassMode::Immediate,
+            ProtoEntry::Drum(entry) => entry.pass_mode(),
+            ProtoEntry::Extension(entry) => entry.pass_mode(),
         }
     }
     /// Returns `true` if the proto_entry is [`Mark`].
@@ -111,6 +116,13 @@ impl ProtoEntry {
             Err(self)
         }
     }
+    pub fn as_lyric(&self) -> Option<&LyricEntry> {
+        if let Self::Lyric(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
     /// Returns `true` if the proto_entry is [`FrettedSix`].
     pub fn is_fretted6(&self) -> bool {
         matches!(self, Self::Fretted6(..))
@@ -147,12 +159,46 @@ impl ProtoEntry {
             Err(self)
         }
     }
+    /// Returns `true` if the proto_entry is [`Drum`].
+    pub fn is_drum(&self) -> bool {
+        matches!(self, Self::Drum(..))
+    }
+    pub fn as_drum(&self) -> Option<&DrumEntry> {
+        if let Self::Drum(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
+    /// Returns `true` if the proto_entry is [`Extension`].
+    pub fn is_extension(&self) -> bool {
+        matches!(self, Self::Extension(..))
+    }
+    pub fn as_extension(&self) -> Option<&ExtensionEntry> {
+        if let Self::Extension(v) = self {
+            Some(v)
+        } else {
+            None
+        }
+    }
 }
 impl ProtoEntry {
     pub const COMMENT_CHAR: char = ';';
     pub fn is_core_tie(&self) -> bool {
         self.as_core().map(|x| x.is_tie()).unwrap_or(false)
     }
+    pub fn as_core_ornament(&self) -> Option<&Ornament> {
+        self.as_core().and_then(|x| x.as_ornament())
+    }
+    /// Returns `true` if an ornament right before this entry applies to it.
+    pub fn is_ornament_target(&self) -> bool {
+        match self {
+            Self::Core(x) => x.is_tone(),
+            Self::Fretted6(x) => x.as_pick().is_some(),
+            Self::Fretted4(x) => x.as_pick().is_some(),
+            _ => false,
+        }
+    }
     pub fn trim_comments(mark: &String) -> String {
         if let Some(index) = mark.find(Self::COMMENT_CHAR) {
             return mark[..index].to_string();
@@ -183,25 +229,25 @@ impl From<&str> for ProtoEntry {
 
 impl From<(String, String)> for ProtoEntry {
     fn from(v: (String, String)) -> Self {
-        ProtoEntry::Extra(v.0, v.1)
+        ProtoEntry::Extension(ExtensionEntry::new(v.0, v.1))
     }
 }
 
 impl From<(&str, String)> for ProtoEntry {
     fn from(v: (&str, String)) -> Self {
-        ProtoEntry::Extra(String::from(v.0), v.1)
+        ProtoEntry::Extension(ExtensionEntry::new(String::from(v.0), v.1))
     }
 }
 
 impl From<(String, &str)> for ProtoEntry {
     fn from(v: (String, &str)) -> Self {
-        ProtoEntry::Extra(v.0, String::from(v.1))
+        ProtoEntry::Extension(ExtensionEntry::new(v.0, String::from(v.1)))
     }
 }
 
 impl From<(&str, &str)> for ProtoEntry {
     fn from(v: (&str, &str)) -> Self {
-        ProtoEntry::Extra(String::from(v.0), String::from(v.1))
+        ProtoEntry::Extension(ExtensionEntry::new(String::from(v.0), String::from(v.1)))
     }
 }
 
@@ -234,3 +280,15 @@ impl From<FrettedEntry4> for ProtoEntry {
         ProtoEntry::Fretted4(v)
     }
 }
+
+impl From<DrumEntry> for ProtoEntry {
+    fn from(v: DrumEntry) -> Self {
+        ProtoEntry::Drum(v)
+    }
+}
+
+impl From<ExtensionEntry> for ProtoEntry {
+    fn from(v: ExtensionEntry) -> Self {
+        ProtoEntry::Extension(v)
+    }
+}
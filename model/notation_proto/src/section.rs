@@ -5,6 +5,7 @@ use crate::prelude::Bar;
 
 // https://www.masterclass.com/articles/songwriting-101-learn-common-song-structures
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SectionKind {
     Ready,
     Intro,
@@ -38,6 +39,7 @@ impl SectionKind {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Section {
     pub id: String,
     pub kind: SectionKind,
@@ -67,6 +69,7 @@ impl Section {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Form {
     pub sections: Vec<String>,
 }
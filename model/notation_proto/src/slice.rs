@@ -2,18 +2,21 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SliceBegin {
     Mark(String),
     Index(usize),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum SliceEnd {
     Mark(String),
     Count(usize),
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Slice {
     pub begin: SliceBegin,
     pub end: SliceEnd,
@@ -45,6 +48,31 @@ impl Slice {
     pub fn in_round(&self, round: usize) -> bool {
         !self.not_in_round(round)
     }
+    /// Range of the entries in the slice, capped at the entry count, or the mark not
+    /// found, `is_mark` checks whether the entry at the index is the mark.
+    pub fn calc_range<F: Fn(usize, &str) -> bool>(
+        &self,
+        len: usize,
+        is_mark: F,
+    ) -> Result<(usize, usize), String> {
+        let index_of_mark = |begin: usize, mark: &String| {
+            (begin..len)
+                .find(|i| is_mark(*i, mark))
+                .ok_or_else(|| mark.clone())
+        };
+        let (index, count) = match (&self.begin, &self.end) {
+            (SliceBegin::Mark(x), SliceEnd::Mark(y)) => {
+                let index = index_of_mark(0, x)? + 1;
+                (index, index_of_mark(index, y)? - index)
+            }
+            (SliceBegin::Mark(x), SliceEnd::Count(y)) => (index_of_mark(0, x)? + 1, *y),
+            (SliceBegin::Index(x), SliceEnd::Mark(y)) => {
+                (*x, index_of_mark(*x, y)?.saturating_sub(1 + *x))
+            }
+            (SliceBegin::Index(x), SliceEnd::Count(y)) => (*x, *y),
+        };
+        Ok((index.min(len), (index + count).min(len)))
+    }
 }
 impl Display for SliceBegin {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -65,3 +93,36 @@ impl Display for Slice {
         write!(f, ")")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRIES: [&str; 6] = ["a", "1", "2", "b", "3", "4"];
+
+    fn range(begin: SliceBegin, end: SliceEnd) -> Result<(usize, usize), String> {
+        Slice::new(begin, end, None).calc_range(ENTRIES.len(), |i, mark| ENTRIES[i] == mark)
+    }
+
+    #[test]
+    fn test_calc_range() {
+        let mark = |x: &str| x.to_owned();
+        assert_eq!(range(SliceBegin::Mark(mark("a")), SliceEnd::Mark(mark("b"))), Ok((1, 3)));
+        assert_eq!(range(SliceBegin::Mark(mark("b")), SliceEnd::Count(2)), Ok((4, 6)));
+        assert_eq!(range(SliceBegin::Index(1), SliceEnd::Mark(mark("b"))), Ok((1, 2)));
+        assert_eq!(range(SliceBegin::Index(3), SliceEnd::Mark(mark("b"))), Ok((3, 3)));
+        assert_eq!(range(SliceBegin::Index(2), SliceEnd::Count(2)), Ok((2, 4)));
+        assert_eq!(range(SliceBegin::Index(4), SliceEnd::Count(5)), Ok((4, 6)));
+        assert_eq!(range(SliceBegin::Index(8), SliceEnd::Count(1)), Ok((6, 6)));
+    }
+
+    #[test]
+    fn test_calc_range_missing_mark() {
+        let mark = |x: &str| x.to_owned();
+        assert_eq!(range(SliceBegin::Mark(mark("x")), SliceEnd::Count(1)), Err(mark("x")));
+        assert_eq!(range(SliceBegin::Mark(mark("a")), SliceEnd::Mark(mark("y"))), Err(mark("y")));
+        // The end mark is searched after the begin mark.
+        assert_eq!(range(SliceBegin::Mark(mark("b")), SliceEnd::Mark(mark("a"))), Err(mark("a")));
+        assert_eq!(range(SliceBegin::Index(4), SliceEnd::Mark(mark("b"))), Err(mark("b")));
+    }
+}
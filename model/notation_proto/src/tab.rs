@@ -3,12 +3,17 @@ use uuid::Uuid;
 
 use std::fmt::Display;
 
-use crate::prelude::{Form, Section, Track};
+use crate::prelude::{BarLayer, Form, Section, Track};
 use notation_core::prelude::{
-    Key, Note, Pitch, Scale, Signature, Syllable, Tempo, Units, Octave,
+    Chord, ChordSymbol, Key, Note, Pitch, Scale, Signature, Syllable, Tempo, Units, Octave,
+};
+use notation_fretted::prelude::{
+    ChordVoicing4, ChordVoicing6, Fretboard4, Fretboard6, HandShape4, HandShape6, MelodyOptions,
+    VoicingOptions,
 };
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TabMeta {
     pub key: Key,
     pub scale: Scale,
@@ -28,6 +33,21 @@ impl TabMeta {
     pub fn calc_note_from_syllable(&self, syllable: &Syllable, octave: &Octave) -> Note {
         self.scale.calc_note_from_syllable(&self.key, syllable, octave)
     }
+    pub fn calc_chord_voicings6(&self, fretboard: &Fretboard6, chord: &Chord) -> Vec<ChordVoicing6> {
+        fretboard.calc_chord_voicings(&self.scale, &self.key, chord, &VoicingOptions::default())
+    }
+    pub fn calc_chord_voicings4(&self, fretboard: &Fretboard4, chord: &Chord) -> Vec<ChordVoicing4> {
+        fretboard.calc_chord_voicings(&self.scale, &self.key, chord, &VoicingOptions::default())
+    }
+    pub fn calc_shape_chords6(&self, fretboard: &Fretboard6, shape: &HandShape6) -> Vec<Chord> {
+        fretboard.calc_shape_chords(&self.scale, &self.key, shape)
+    }
+    pub fn calc_shape_chords4(&self, fretboard: &Fretboard4, shape: &HandShape4) -> Vec<Chord> {
+        fretboard.calc_shape_chords(&self.scale, &self.key, shape)
+    }
+    pub fn calc_chord_symbol(&self, chord: &Chord) -> Option<ChordSymbol> {
+        ChordSymbol::from_chord(&self.scale, &self.key, chord)
+    }
 }
 impl Display for TabMeta {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -60,7 +80,11 @@ impl Default for TabMeta {
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Tab {
+    /// Format version of the file, see [`Tab::FORMAT_VERSION`], missing in old files.
+    #[serde(default)]
+    pub version: u32,
     pub uuid: Uuid,
     pub meta: TabMeta,
     pub tracks: Vec<Track>,
@@ -92,6 +116,7 @@ impl Tab {
     ) -> Self {
         let uuid = Uuid::parse_str(uuid).unwrap();
         Self {
+            version: Self::FORMAT_VERSION,
             uuid,
             meta,
             tracks,
@@ -108,4 +133,81 @@ impl Tab {
             Form { sections: vec![] },
         )
     }
+    /// Add a guitar track picking the melody of another track, see [`Track::calc_melody_pick_track()`].
+    ///
+    /// Bars playing the melody track get a layer with the same slices for the new track.
+    /// Returns `false` if the melody track is not found or the id is already used.
+    pub fn add_melody_pick_track(
+        &mut self,
+        melody_track: &str,
+        id: &str,
+        fretboard: &Fretboard6,
+        options: &MelodyOptions,
+    ) -> bool {
+        if self.tracks.iter().any(|x| x.id == id) {
+            return false;
+        }
+        let track = match self.tracks.iter().find(|x| x.id == melody_track) {
+            Some(track) => track.calc_melody_pick_track(id.to_owned(), fretboard, options),
+            None => return false,
+        };
+        self.tracks.push(track);
+        for section in self.sections.iter_mut() {
+            for bar in section.bars.iter_mut() {
+                let layers: Vec<BarLayer> = bar
+                    .layers
+                    .iter()
+                    .filter(|x| x.track == melody_track)
+                    .map(|x| BarLayer::new_voice(id.to_owned(), x.voice, x.slices.clone()))
+                    .collect();
+                bar.layers.extend(layers);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{CoreEntry, Duration, Tone};
+    use notation_fretted::prelude::FrettedEntry6;
+
+    use super::*;
+    use crate::prelude::{
+        Bar, GuitarUtil, ProtoEntry, SectionKind, Slice, SliceBegin, SliceEnd, TrackKind,
+    };
+
+    fn tab() -> Tab {
+        let note = Note::new(Octave::P3, Pitch::G, Syllable::So);
+        let entries: Vec<ProtoEntry> = vec![
+            CoreEntry::Tone(Tone::from(note), Duration::_1_2).into(),
+            CoreEntry::Rest(Duration::_1_2).into(),
+        ];
+        let mut tab = Tab::new_empty();
+        tab.tracks.push(Track::new("vocal".into(), TrackKind::Vocal, entries));
+        let slice = Slice::new(SliceBegin::Index(0), SliceEnd::Count(2), None);
+        let bar = Bar::new(vec![BarLayer::new_voice("vocal".into(), 1, vec![slice])]);
+        tab.sections.push(Section::new("verse".into(), SectionKind::Verse, vec![bar]));
+        tab.form = Form::from(vec!["verse"]);
+        tab
+    }
+
+    #[test]
+    fn test_add_melody_pick_track() {
+        let mut tab = tab();
+        let fretboard = GuitarUtil::new_default_fretboard();
+        let options = MelodyOptions::default();
+        assert!(tab.add_melody_pick_track("vocal", "picks", &fretboard, &options));
+        let track = tab.tracks.iter().find(|x| x.id == "picks").unwrap();
+        assert_eq!(track.entries.len(), 3);
+        assert!(matches!(track.entries[0], ProtoEntry::Fretted6(FrettedEntry6::Pick(_, Duration::_1_2))));
+        let layers = &tab.sections[0].bars[0].layers;
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1].track, "picks");
+        assert_eq!(layers[1].voice, 1);
+        assert_eq!(layers[1].slices.len(), 1);
+        assert!(!tab.add_melody_pick_track("vocal", "picks", &fretboard, &options));
+        assert!(!tab.add_melody_pick_track("missing", "more_picks", &fretboard, &options));
+        assert_eq!(tab.tracks.len(), 2);
+    }
 }
@@ -0,0 +1,502 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use thiserror::Error;
+
+use notation_core::prelude::{CoreEntry, MetaEntry};
+use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6};
+
+use crate::prelude::{
+    Bar, BarLayer, DrumEntry, ExtensionEntry, Form, LyricEntry, ProtoEntry, Section,
+    SectionKind, Slice, Tab, TabMeta, Track, TrackKind, Uuid,
+};
+
+#[derive(Clone, Error, Debug)]
+pub enum TabFormatError {
+    #[error("unknown tab file extension: {0}")]
+    UnknownExtension(String),
+    #[error("decode {format} tab header failed: {reason}")]
+    DecodeHeaderFailed { format: TabFormat, reason: String },
+    #[error("decode {format} tab of format version {version} failed: {reason}")]
+    DecodeFailed {
+        format: TabFormat,
+        version: u32,
+        reason: String,
+    },
+    #[error("encode {format} tab failed: {reason}")]
+    EncodeFailed { format: TabFormat, reason: String },
+    #[error("tab format version {version} is newer than the supported version {supported}")]
+    UnsupportedVersion { version: u32, supported: u32 },
+    #[error("migrate tab from format version {from} failed: {reason}")]
+    MigrateFailed { from: u32, reason: String },
+}
+
+/// Encodings of tab files, picked by the file extension.
+#[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum TabFormat {
+    /// Pretty printed, the format of the files in the repository.
+    Ron,
+    /// For web tooling.
+    Json,
+    /// Compact bincode, for fast loading of large libraries.
+    Binary,
+}
+impl Display for TabFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+impl TabFormat {
+    pub const ALL: [TabFormat; 3] = [TabFormat::Ron, TabFormat::Json, TabFormat::Binary];
+    pub const EXTENSIONS: [&'static str; 3] = ["ron", "json", "ntab"];
+
+    pub fn to_extension(&self) -> &'static str {
+        match self {
+            Self::Ron => "ron",
+            Self::Json => "json",
+            Self::Binary => "ntab",
+        }
+    }
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|x| x.to_extension().eq_ignore_ascii_case(extension))
+            .copied()
+    }
+    /// Format of the path by its extension.
+    pub fn from_path(path: &str) -> Result<Self, TabFormatError> {
+        let extension = path.rsplit_once('.').map(|x| x.1).unwrap_or("");
+        Self::from_extension(extension)
+            .ok_or_else(|| TabFormatError::UnknownExtension(extension.to_owned()))
+    }
+    fn decode<'a, T: Deserialize<'a>>(&self, bytes: &'a [u8]) -> Result<T, String> {
+        match self {
+            Self::Ron => ron::de::from_bytes(bytes).map_err(|x| x.to_string()),
+            Self::Json => serde_json::from_slice(bytes).map_err(|x| x.to_string()),
+            Self::Binary => bincode::deserialize(bytes).map_err(|x| x.to_string()),
+        }
+    }
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Ron => {
+                let pretty = ron::ser::PrettyConfig::new()
+                    .separate_tuple_members(true)
+                    .enumerate_arrays(true);
+                ron::ser::to_string_pretty(value, pretty)
+                    .map(|x| x.into_bytes())
+                    .map_err(|x| x.to_string())
+            }
+            Self::Json => serde_json::to_vec_pretty(value).map_err(|x| x.to_string()),
+            Self::Binary => bincode::serialize(value).map_err(|x| x.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "schema")]
+impl TabFormat {
+    /// JSON Schema of the [`TabFormat::Json`] files of the current version.
+    pub fn json_schema() -> Result<String, TabFormatError> {
+        let schema = schemars::schema_for!(Tab);
+        serde_json::to_string_pretty(&schema).map_err(|x| TabFormatError::EncodeFailed {
+            format: TabFormat::Json,
+            reason: x.to_string(),
+        })
+    }
+}
+
+/// Only the version is decoded first, to pick the document type for the rest.
+///
+/// The version is the first field of [`Tab`], so this works for bincode too.
+#[derive(Deserialize)]
+struct TabHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Entries up to version 1, with untyped key/value extras.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ProtoEntryV1 {
+    Mark(String),
+    Meta(MetaEntry),
+    Core(CoreEntry),
+    Lyric(LyricEntry),
+    Fretted6(FrettedEntry6),
+    Fretted4(FrettedEntry4),
+    Drum(DrumEntry),
+    Extra(String, String),
+}
+impl From<ProtoEntryV1> for ProtoEntry {
+    fn from(v: ProtoEntryV1) -> Self {
+        match v {
+            ProtoEntryV1::Mark(x) => Self::Mark(x),
+            ProtoEntryV1::Meta(x) => Self::Meta(x),
+            ProtoEntryV1::Core(x) => Self::Core(x),
+            ProtoEntryV1::Lyric(x) => Self::Lyric(x),
+            ProtoEntryV1::Fretted6(x) => Self::Fretted6(x),
+            ProtoEntryV1::Fretted4(x) => Self::Fretted4(x),
+            ProtoEntryV1::Drum(x) => Self::Drum(x),
+            ProtoEntryV1::Extra(key, value) => Self::Extension(ExtensionEntry::new(key, value)),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TrackV1 {
+    pub id: String,
+    pub kind: TrackKind,
+    pub entries: Vec<ProtoEntryV1>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TabV1 {
+    #[serde(default)]
+    pub version: u32,
+    pub uuid: Uuid,
+    pub meta: TabMeta,
+    pub tracks: Vec<TrackV1>,
+    pub sections: Vec<SectionV2>,
+    pub form: Form,
+}
+
+/// Layers up to version 2, without voices.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BarLayerV2 {
+    pub track: String,
+    pub slices: Vec<Slice>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct BarV2 {
+    pub layers: Vec<BarLayerV2>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SectionV2 {
+    pub id: String,
+    pub kind: SectionKind,
+    pub bars: Vec<BarV2>,
+}
+impl From<SectionV2> for Section {
+    fn from(v: SectionV2) -> Self {
+        let bars = v
+            .bars
+            .into_iter()
+            .map(|bar| {
+                let layers: Vec<BarLayer> = bar
+                    .layers
+                    .into_iter()
+                    .map(|x| BarLayer::new(x.track, x.slices))
+                    .collect();
+                Bar::from(layers)
+            })
+            .collect();
+        Section::new(v.id, v.kind, bars)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TabV2 {
+    #[serde(default)]
+    pub version: u32,
+    pub uuid: Uuid,
+    pub meta: TabMeta,
+    pub tracks: Vec<Track>,
+    pub sections: Vec<SectionV2>,
+    pub form: Form,
+}
+
+/// A tab as stored in one format version.
+///
+/// When a change breaks decoding of older files, the old layout keeps its own
+/// document type here, with an [`upgrade()`] step to the next version.
+///
+/// [`upgrade()`]: Self::upgrade
+#[derive(Clone, Debug)]
+pub enum TabDocument {
+    /// Files written before the version field, otherwise the same as version 1.
+    V0(TabV1),
+    V1(TabV1),
+    /// `Extra` entries replaced by typed [`ExtensionEntry`].
+    V2(TabV2),
+    /// Voices of bar layers.
+    V3(Tab),
+}
+
+impl TabDocument {
+    pub fn version(&self) -> u32 {
+        match self {
+            Self::V0(_) => 0,
+            Self::V1(_) => 1,
+            Self::V2(_) => 2,
+            Self::V3(_) => 3,
+        }
+    }
+    pub fn decode(format: TabFormat, bytes: &[u8]) -> Result<Self, TabFormatError> {
+        let header: TabHeader = format
+            .decode(bytes)
+            .map_err(|reason| TabFormatError::DecodeHeaderFailed { format, reason })?;
+        let decode_failed = |reason| TabFormatError::DecodeFailed {
+            format,
+            version: header.version,
+            reason,
+        };
+        match header.version {
+            0 => Ok(Self::V0(format.decode(bytes).map_err(decode_failed)?)),
+            1 => Ok(Self::V1(format.decode(bytes).map_err(decode_failed)?)),
+            2 => Ok(Self::V2(format.decode(bytes).map_err(decode_failed)?)),
+            3 => Ok(Self::V3(format.decode(bytes).map_err(decode_failed)?)),
+            version => Err(TabFormatError::UnsupportedVersion {
+                version,
+                supported: Tab::FORMAT_VERSION,
+            }),
+        }
+    }
+    /// Migrate to the next version, the current version is returned as is.
+    pub fn upgrade(self) -> Result<Self, TabFormatError> {
+        match self {
+            Self::V0(tab) => Ok(Self::V1(TabV1 {
+                version: 1,
+                ..tab
+            })),
+            Self::V1(tab) => Ok(Self::V2(TabV2 {
+                version: 2,
+                uuid: tab.uuid,
+                meta: tab.meta,
+                tracks: tab
+                    .tracks
+                    .into_iter()
+                    .map(|x| {
+                        let entries = x.entries.into_iter().map(ProtoEntry::from).collect();
+                        Track::new(x.id, x.kind, entries)
+                    })
+                    .collect(),
+                sections: tab.sections,
+                form: tab.form,
+            })),
+            Self::V2(tab) => Ok(Self::V3(Tab {
+                version: 3,
+                uuid: tab.uuid,
+                meta: tab.meta,
+                tracks: tab.tracks,
+                sections: tab.sections.into_iter().map(Section::from).collect(),
+                form: tab.form,
+            })),
+            Self::V3(_) => Ok(self),
+        }
+    }
+    /// Migrate step by step to the current version.
+    pub fn into_tab(self) -> Result<Tab, TabFormatError> {
+        let mut document = self;
+        while document.version() < Tab::FORMAT_VERSION {
+            let from = document.version();
+            document = document.upgrade()?;
+            if document.version() <= from {
+                return Err(TabFormatError::MigrateFailed {
+                    from,
+                    reason: "no migration to the next version".to_owned(),
+                });
+            }
+        }
+        match document {
+            Self::V3(tab) => Ok(tab),
+            _ => Err(TabFormatError::MigrateFailed {
+                from: document.version(),
+                reason: "not the current version".to_owned(),
+            }),
+        }
+    }
+}
+
+impl Tab {
+    /// Version of the stored layout, bumped with a new [`TabDocument`] variant
+    /// whenever older files can't be decoded as they are.
+    pub const FORMAT_VERSION: u32 = 3;
+
+    /// Decode a file of any supported version, migrated to the current one.
+    pub fn decode(format: TabFormat, bytes: &[u8]) -> Result<Self, TabFormatError> {
+        TabDocument::decode(format, bytes)?.into_tab()
+    }
+    pub fn encode(&self, format: TabFormat) -> Result<Vec<u8>, TabFormatError> {
+        format
+            .encode(self)
+            .map_err(|reason| TabFormatError::EncodeFailed { format, reason })
+    }
+    pub fn from_ron_bytes(bytes: &[u8]) -> Result<Self, TabFormatError> {
+        Self::decode(TabFormat::Ron, bytes)
+    }
+    pub fn from_ron_str(text: &str) -> Result<Self, TabFormatError> {
+        Self::from_ron_bytes(text.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Duration, Unit};
+    use notation_fretted::prelude::{HandShape6, Pick, PickNote};
+
+    use super::*;
+
+    /// Layout of the files written before the version field.
+    const V0_RON: &str = r#"(
+    uuid: "ef6bb44b-17cf-47e6-a50e-0ab636868334",
+    meta: (
+        key: Natural(A),
+        scale: Ionian,
+        signature: (
+            beat_unit: Quarter,
+            bar_beats: 4,
+        ),
+        tempo: Bpm(72),
+    ),
+    tracks: [
+        (
+            id: "chord",
+            kind: Chord,
+            entries: [
+                Extra("dsl::context::duration", "_1"),
+                Mark("1"),
+            ],
+        ),
+    ],
+    sections: [
+        (
+            id: "intro",
+            kind: Intro,
+            bars: [
+                (
+                    layers: [
+                        (
+                            track: "chord",
+                            slices: [
+                                (
+                                    begin: Mark("1"),
+                                    end: Count(1),
+                                    rounds: None,
+                                ),
+                            ],
+                        ),
+                    ],
+                ),
+            ],
+        ),
+    ],
+    form: (
+        sections: ["intro"],
+    ),
+)"#;
+
+    #[test]
+    fn test_v0_upgraded_step_by_step() {
+        let mut document = TabDocument::decode(TabFormat::Ron, V0_RON.as_bytes()).unwrap();
+        assert_eq!(document.version(), 0);
+        for version in 1..=Tab::FORMAT_VERSION {
+            document = document.upgrade().unwrap();
+            assert_eq!(document.version(), version);
+        }
+        assert_eq!(document.upgrade().unwrap().version(), Tab::FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_v0_to_current() {
+        let tab = Tab::from_ron_str(V0_RON).unwrap();
+        assert_eq!(tab.version, Tab::FORMAT_VERSION);
+        match &tab.tracks[0].entries[0] {
+            ProtoEntry::Extension(x) => {
+                assert_eq!(x.kind, "dsl::context::duration");
+                assert_eq!(x.payload, "_1");
+            }
+            x => panic!("extra not migrated: {:?}", x),
+        }
+        let layer = &tab.sections[0].bars[0].layers[0];
+        assert_eq!(layer.track, "chord");
+        assert_eq!(layer.voice, 0);
+        assert_eq!(tab.form.sections, vec!["intro".to_owned()]);
+    }
+
+    #[test]
+    fn test_migrated_tab_encoded_as_current() {
+        let tab = Tab::from_ron_str(V0_RON).unwrap();
+        let bytes = tab.encode(TabFormat::Ron).unwrap();
+        let document = TabDocument::decode(TabFormat::Ron, &bytes).unwrap();
+        assert_eq!(document.version(), Tab::FORMAT_VERSION);
+    }
+
+    #[test]
+    fn test_version_errors() {
+        let newer = V0_RON.replacen("(\n", "(\n    version: 99,\n", 1);
+        assert!(matches!(
+            Tab::from_ron_str(&newer),
+            Err(TabFormatError::UnsupportedVersion { version: 99, .. })
+        ));
+        let broken = V0_RON.replacen("(\n", "(\n    version: 2,\n", 1);
+        assert!(matches!(
+            Tab::from_ron_str(&broken),
+            Err(TabFormatError::DecodeFailed { version: 2, .. })
+        ));
+    }
+
+    fn guitar_tab() -> Tab {
+        let mut tab = Tab::from_ron_str(V0_RON).unwrap();
+        let shape = HandShape6::from([Some(0), Some(1), Some(0), Some(2), Some(3), None]);
+        let pick = Pick::from(PickNote::new_string_fret(2, 1));
+        let entries = vec![
+            ProtoEntry::Mark("1".into()),
+            FrettedEntry6::Shape(shape, Duration::Simple(Unit::Whole)).into(),
+            FrettedEntry6::Pick(pick, Duration::Simple(Unit::Quarter)).into(),
+        ];
+        tab.tracks.push(Track::new("guitar".into(), TrackKind::Guitar, entries));
+        tab
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(TabFormat::from_path("tabs/a.ron").unwrap(), TabFormat::Ron);
+        assert_eq!(TabFormat::from_path("tabs/a.JSON").unwrap(), TabFormat::Json);
+        assert_eq!(TabFormat::from_path("a.b.ntab").unwrap(), TabFormat::Binary);
+        assert!(matches!(
+            TabFormat::from_path("tabs/a.txt"),
+            Err(TabFormatError::UnknownExtension(x)) if x == "txt"
+        ));
+        assert!(matches!(
+            TabFormat::from_path("tabs/a"),
+            Err(TabFormatError::UnknownExtension(x)) if x.is_empty()
+        ));
+    }
+
+    #[test]
+    fn test_format_round_trip() {
+        let tab = guitar_tab();
+        let expected = tab.encode(TabFormat::Ron).unwrap();
+        for format in TabFormat::ALL {
+            let bytes = tab.encode(format).unwrap();
+            let decoded = Tab::decode(format, &bytes).unwrap();
+            assert_eq!(decoded.encode(TabFormat::Ron).unwrap(), expected, "{}", format);
+            assert_eq!(decoded.encode(format).unwrap(), bytes, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_wrong_format() {
+        let bytes = guitar_tab().encode(TabFormat::Json).unwrap();
+        assert!(matches!(
+            Tab::decode(TabFormat::Ron, &bytes),
+            Err(TabFormatError::DecodeHeaderFailed { format: TabFormat::Ron, .. })
+                | Err(TabFormatError::DecodeFailed { format: TabFormat::Ron, .. })
+        ));
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_json_schema() {
+        let schema: serde_json::Value = serde_json::from_str(&TabFormat::json_schema().unwrap()).unwrap();
+        assert_eq!(schema["title"], "Tab");
+        assert!(schema["definitions"]["ProtoEntry"].is_object());
+    }
+}
@@ -2,9 +2,13 @@ use serde::{Deserialize, Serialize};
 
 use std::fmt::Display;
 
-use crate::prelude::ProtoEntry;
+use crate::prelude::{
+    BassUtil, CoreEntry, Fretboard4, Fretboard6, FrettedEntry6, GuitarUtil, MandolinUtil,
+    MelodyOptions, Note, Pick, ProtoEntry, Semitones, UkuleleUtil,
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub enum TrackKind {
     Unsupported,
     Meta,
@@ -16,6 +20,8 @@ pub enum TrackKind {
     Piano,
     Drums,
     Bass,
+    Ukulele,
+    Mandolin,
 }
 impl Display for TrackKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -34,15 +40,45 @@ impl TrackKind {
             "Piano" => Self::Piano,
             "Drums" => Self::Drums,
             "Bass" => Self::Bass,
+            "Ukulele" => Self::Ukulele,
+            "Mandolin" => Self::Mandolin,
             _ => {
                 println!("TrackKind::from_ident() Unsupported ident: {}", ident);
                 Self::Unsupported
             }
         }
     }
+    /// Number of strings for fretted instruments, `None` for other kinds.
+    pub fn string_num(&self) -> Option<usize> {
+        match self {
+            Self::Guitar => Some(6),
+            Self::Bass | Self::Ukulele | Self::Mandolin => Some(4),
+            _ => None,
+        }
+    }
+    pub fn is_fretted(&self) -> bool {
+        self.string_num().is_some()
+    }
+    /// Fretboard used when the track doesn't have a `Fretboard` entry.
+    pub fn default_fretboard6(&self) -> Option<Fretboard6> {
+        match self {
+            Self::Guitar => Some(GuitarUtil::new_default_fretboard()),
+            _ => None,
+        }
+    }
+    /// Fretboard used when the track doesn't have a `Fretboard` entry.
+    pub fn default_fretboard4(&self) -> Option<Fretboard4> {
+        match self {
+            Self::Bass => Some(BassUtil::new_default_fretboard()),
+            Self::Ukulele => Some(UkuleleUtil::new_default_fretboard()),
+            Self::Mandolin => Some(MandolinUtil::new_default_fretboard()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct Track {
     pub id: String,
     pub kind: TrackKind,
@@ -52,6 +88,48 @@ impl Track {
     pub fn new(id: String, kind: TrackKind, entries: Vec<ProtoEntry>) -> Self {
         Self { kind, id, entries }
     }
+    /// Guitar track picking the melody of this track's tones on the fretboard.
+    ///
+    /// Entries are mapped one by one so slices of this track can be reused, the highest
+    /// note of each tone is picked, tones out of range become rests, and the fretboard
+    /// is appended at the end.
+    pub fn calc_melody_pick_track(&self, id: String, fretboard: &Fretboard6, options: &MelodyOptions) -> Track {
+        let notes: Vec<Note> = self
+            .entries
+            .iter()
+            .filter_map(Self::melody_note)
+            .collect();
+        let mut picks = fretboard.calc_melody_picks(&notes, options).into_iter();
+        let mut entries: Vec<ProtoEntry> = self
+            .entries
+            .iter()
+            .map(|entry| match (entry, Self::melody_note(entry)) {
+                (ProtoEntry::Core(CoreEntry::Tone(_, duration)), Some(_)) => {
+                    match picks.next().flatten() {
+                        Some(pick_note) => {
+                            FrettedEntry6::Pick(Pick::from(vec![pick_note]), *duration).into()
+                        }
+                        None => CoreEntry::Rest(*duration).into(),
+                    }
+                }
+                (ProtoEntry::Core(CoreEntry::Tone(_, duration)), None) => {
+                    CoreEntry::Rest(*duration).into()
+                }
+                _ => entry.clone(),
+            })
+            .collect();
+        entries.push(FrettedEntry6::Fretboard(*fretboard).into());
+        Track::new(id, TrackKind::Guitar, entries)
+    }
+    fn melody_note(entry: &ProtoEntry) -> Option<Note> {
+        match entry {
+            ProtoEntry::Core(CoreEntry::Tone(tone, _)) => tone
+                .get_notes()
+                .into_iter()
+                .max_by_key(|x| Semitones::from(*x).0),
+            _ => None,
+        }
+    }
 }
 impl Display for Track {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -64,3 +142,59 @@ impl Display for Track {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Duration, Octave, Pitch, Syllable, Tone};
+    use notation_fretted::prelude::PickNote;
+
+    use super::*;
+
+    fn tone(pitch: Pitch, octave: Octave, duration: Duration) -> ProtoEntry {
+        let note = Note::new(octave, pitch, Syllable::Do);
+        CoreEntry::Tone(Tone::from(note), duration).into()
+    }
+
+    fn vocal() -> Track {
+        let entries = vec![
+            ProtoEntry::Mark("verse".into()),
+            tone(Pitch::E, Octave::P3, Duration::_1_4),
+            CoreEntry::Rest(Duration::_1_8).into(),
+            tone(Pitch::D, Octave::P2, Duration::_1_2),
+            tone(Pitch::G, Octave::P3, Duration::D_1_8),
+            CoreEntry::Tone(Tone::None, Duration::_1_4).into(),
+        ];
+        Track::new("vocal".into(), TrackKind::Vocal, entries)
+    }
+
+    fn is_rest(entry: &ProtoEntry) -> bool {
+        matches!(entry, ProtoEntry::Core(CoreEntry::Rest(_)))
+    }
+
+    #[test]
+    fn test_melody_pick_track() {
+        let vocal = vocal();
+        let fretboard = GuitarUtil::new_default_fretboard();
+        let track = vocal.calc_melody_pick_track("picks".into(), &fretboard, &MelodyOptions::default());
+        assert_eq!(track.kind, TrackKind::Guitar);
+        assert_eq!(track.entries.len(), vocal.entries.len() + 1);
+        for (generated, entry) in track.entries.iter().zip(vocal.entries.iter()) {
+            assert_eq!(generated.duration(), entry.duration());
+        }
+        let picks: Vec<Option<&Pick>> = track
+            .entries
+            .iter()
+            .map(|x| x.as_fretted6().and_then(|y| y.as_pick()))
+            .collect();
+        assert_eq!(picks[1], Some(&Pick::from(vec![PickNote::new_string_fret(4, 2)])));
+        assert!(is_rest(&track.entries[2]));
+        // out of range, and no note at all
+        assert!(is_rest(&track.entries[3]));
+        assert_eq!(picks[4], Some(&Pick::from(vec![PickNote::new_string_fret(3, 0)])));
+        assert!(is_rest(&track.entries[5]));
+        assert_eq!(
+            track.entries.last().and_then(|x| x.as_fretted6()),
+            Some(&FrettedEntry6::Fretboard(fretboard))
+        );
+    }
+}
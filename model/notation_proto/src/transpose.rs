@@ -0,0 +1,375 @@
+use std::fmt::Display;
+
+use notation_core::prelude::{Chord, CoreEntry, Key, MetaEntry, Note, Semitones, Tone};
+use notation_fretted::prelude::{
+    Fretboard6, FrettedEntry4, FrettedEntry6, HandShape4, HandShape6, Pick, PickNote,
+    VoicingOptions,
+};
+
+use crate::prelude::{ProtoEntry, Tab, TabMeta, Track};
+
+/// How fretted tracks follow the new key in [`Tab::transpose()`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TransposeMode {
+    /// Keep the shapes and frets, move the capo by the semitones.
+    MoveCapo,
+    /// Keep the capo, move the frets of shapes and picks by the semitones.
+    RewriteFrets,
+}
+impl Display for TransposeMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// How many chords of the tab can be played with open shapes at the capo.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct CapoSuggestion {
+    pub capo: u8,
+    pub open_chords: usize,
+    pub total_chords: usize,
+}
+impl Display for CapoSuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<CapoSuggestion>(C: {}, {}/{})",
+            self.capo, self.open_chords, self.total_chords
+        )
+    }
+}
+impl CapoSuggestion {
+    pub fn is_all_open(&self) -> bool {
+        self.open_chords == self.total_chords
+    }
+}
+
+/// Highest capo position to move to.
+pub const MAX_CAPO: u8 = 12;
+/// Open shapes only use the first frets.
+const OPEN_SHAPE_SPAN: u8 = 3;
+
+fn pitch_class(semitones: Semitones) -> i8 {
+    semitones.0.rem_euclid(12)
+}
+
+fn shift_fret(fret: u8, semitones: Semitones, fret_num: usize) -> Option<u8> {
+    let fret = fret as i32 + semitones.0 as i32;
+    if fret < 0 || fret as usize >= fret_num {
+        None
+    } else {
+        Some(fret as u8)
+    }
+}
+
+macro_rules! impl_transpose_frets {
+    ($name:ident, $shift_shape:ident, $fretted_entry:ident, $as_fretted:ident, $default_fretboard:ident, $hand_shape:ident) => {
+        impl Track {
+            /// Entries of the track with frets or capo moved, `None` if out of the fretboard.
+            fn $name(&self, semitones: Semitones, mode: TransposeMode) -> Option<Vec<ProtoEntry>> {
+                if !self.entries.iter().any(|x| x.$as_fretted().is_some()) {
+                    return Some(self.entries.clone());
+                }
+                let fretboard = self
+                    .entries
+                    .iter()
+                    .find_map(|x| match x.$as_fretted() {
+                        Some($fretted_entry::Fretboard(fretboard)) => Some(*fretboard),
+                        _ => None,
+                    })
+                    .or_else(|| self.kind.$default_fretboard());
+                let mut entries = Vec::new();
+                for entry in self.entries.iter() {
+                    let shifted = match (entry.$as_fretted(), mode) {
+                        (Some($fretted_entry::Fretboard(fretboard)), TransposeMode::MoveCapo) => {
+                            let capo = fretboard.capo as i32 + semitones.0 as i32;
+                            if capo < 0 || capo > MAX_CAPO as i32 {
+                                return None;
+                            }
+                            $fretted_entry::Fretboard(fretboard.with_capo(capo as u8)).into()
+                        }
+                        (Some($fretted_entry::Shape(shape, duration)), TransposeMode::RewriteFrets) => {
+                            let fret_num = fretboard.map(|x| x.fret_num()).unwrap_or(usize::MAX);
+                            $fretted_entry::Shape($shift_shape(shape, semitones, fret_num)?, *duration).into()
+                        }
+                        (Some($fretted_entry::Pick(pick, duration)), TransposeMode::RewriteFrets) => {
+                            let fret_num = fretboard.map(|x| x.fret_num()).unwrap_or(usize::MAX);
+                            let notes = pick
+                                .get_notes()
+                                .into_iter()
+                                .map(|x| match x.fret {
+                                    Some(fret) => shift_fret(fret, semitones, fret_num)
+                                        .map(|fret| PickNote { fret: Some(fret), ..x }),
+                                    None => Some(x),
+                                })
+                                .collect::<Option<Vec<PickNote>>>()?;
+                            $fretted_entry::Pick(Pick::from(notes), *duration).into()
+                        }
+                        _ => entry.clone(),
+                    };
+                    entries.push(shifted);
+                }
+                let has_fretboard = self
+                    .entries
+                    .iter()
+                    .any(|x| matches!(x.$as_fretted(), Some($fretted_entry::Fretboard(_))));
+                if mode == TransposeMode::MoveCapo && !has_fretboard {
+                    // Appended rather than inserted, so that slices by index stay valid.
+                    let fretboard = fretboard?;
+                    if semitones.0 < 0 || semitones.0 as u8 > MAX_CAPO {
+                        return None;
+                    }
+                    entries.push($fretted_entry::Fretboard(fretboard.with_capo(semitones.0 as u8)).into());
+                }
+                Some(entries)
+            }
+        }
+
+        /// Move the shape along the neck, barre shapes move the barre, others move every
+        /// fretted string, open strings included.
+        fn $shift_shape(shape: &$hand_shape, semitones: Semitones, fret_num: usize) -> Option<$hand_shape> {
+            if shape.barre.is_some() {
+                let max_fret = shape.max_fret_with_barre();
+                shift_fret(max_fret, semitones, fret_num)?;
+                let barre = shift_fret(shape.barre(), semitones, fret_num)?;
+                return Some($hand_shape::new_barre(barre, shape.frets, shape.fingers));
+            }
+            let mut frets = shape.frets;
+            for fret in frets.iter_mut() {
+                if let Some(x) = fret {
+                    *fret = Some(shift_fret(*x, semitones, fret_num)?);
+                }
+            }
+            Some($hand_shape::new(frets, shape.fingers).with_auto_barre())
+        }
+    };
+}
+
+impl_transpose_frets!(
+    transpose_frets6, shift_shape6, FrettedEntry6, as_fretted6, default_fretboard6, HandShape6
+);
+impl_transpose_frets!(
+    transpose_frets4, shift_shape4, FrettedEntry4, as_fretted4, default_fretboard4, HandShape4
+);
+
+impl Tab {
+    /// Move the tab up or down by the semitones, returns false and leaves the tab
+    /// unchanged if the frets or capo would go off the fretboard.
+    ///
+    /// Syllables are relative to the key, so only the key and the written notes
+    /// of tones change, fretted tracks follow the [`TransposeMode`].
+    pub fn transpose(&mut self, semitones: Semitones, mode: TransposeMode) -> bool {
+        let old = self.meta;
+        let key = match Self::calc_transposed_key(&old, semitones) {
+            Some(key) => key,
+            None => return false,
+        };
+        let new = TabMeta { key, ..old };
+        let mut tracks_entries = Vec::new();
+        for track in self.tracks.iter() {
+            let track6 = Track {
+                entries: match track.transpose_frets6(semitones, mode) {
+                    Some(entries) => entries,
+                    None => return false,
+                },
+                ..track.clone()
+            };
+            match track6.transpose_frets4(semitones, mode) {
+                Some(entries) => tracks_entries.push(entries),
+                None => return false,
+            }
+        }
+        for (track, entries) in self.tracks.iter_mut().zip(tracks_entries) {
+            track.entries = entries
+                .into_iter()
+                .map(|x| Self::transpose_entry(&old, &new, semitones, x))
+                .collect();
+        }
+        self.meta = new;
+        true
+    }
+    /// Key of the same scale with the tonic moved by the semitones.
+    fn calc_transposed_key(meta: &TabMeta, semitones: Semitones) -> Option<Key> {
+        let tonic = pitch_class(Semitones::from(meta.key) + semitones);
+        meta.scale
+            .get_keys()
+            .iter()
+            .find(|x| pitch_class(Semitones::from(**x)) == tonic)
+            .copied()
+    }
+    fn transpose_entry(old: &TabMeta, new: &TabMeta, semitones: Semitones, entry: ProtoEntry) -> ProtoEntry {
+        match entry {
+            ProtoEntry::Core(CoreEntry::Tone(tone, duration)) => {
+                let notes: Vec<Note> = tone
+                    .get_notes()
+                    .into_iter()
+                    .map(|x| new.scale.calc_note_from_semitones(&new.key, Semitones::from(x) + semitones))
+                    .collect();
+                CoreEntry::Tone(Tone::from(notes), duration).into()
+            }
+            ProtoEntry::Meta(MetaEntry::Key(x)) if x == old.key => MetaEntry::Key(new.key).into(),
+            _ => entry,
+        }
+    }
+    /// Distinct chords in the chord entries of all tracks.
+    pub fn get_chords(&self) -> Vec<Chord> {
+        let mut chords: Vec<Chord> = Vec::new();
+        for track in self.tracks.iter() {
+            for entry in track.entries.iter() {
+                if let ProtoEntry::Core(CoreEntry::Chord(chord, _)) = entry {
+                    if !chords.contains(chord) {
+                        chords.push(*chord);
+                    }
+                }
+            }
+        }
+        chords
+    }
+    /// Guitar fretboard of the tab, from the first track that has one.
+    fn find_fretboard6(&self) -> Option<Fretboard6> {
+        self.tracks.iter().find_map(|track| {
+            track
+                .entries
+                .iter()
+                .find_map(|x| match x.as_fretted6() {
+                    Some(FrettedEntry6::Fretboard(fretboard)) => Some(*fretboard),
+                    _ => None,
+                })
+                .or_else(|| track.kind.default_fretboard6())
+        })
+    }
+    /// Capo positions up to `max_capo` keeping the sound of the tab, with how many
+    /// chords have open shapes, best first: more open chords, then lower capo.
+    pub fn calc_capo_suggestions(&self, max_capo: u8) -> Vec<CapoSuggestion> {
+        let chords = self.get_chords();
+        let fretboard = match self.find_fretboard6() {
+            Some(fretboard) => fretboard,
+            None => return vec![],
+        };
+        let options = VoicingOptions {
+            max_span: OPEN_SHAPE_SPAN,
+            max_fret: OPEN_SHAPE_SPAN,
+            ..VoicingOptions::default()
+        };
+        let mut suggestions: Vec<CapoSuggestion> = (0..=max_capo.min(MAX_CAPO))
+            .map(|capo| {
+                let fretboard = fretboard.with_capo(capo);
+                let open_chords = chords
+                    .iter()
+                    .filter(|chord| {
+                        fretboard
+                            .calc_chord_voicings(&self.meta.scale, &self.meta.key, chord, &options)
+                            .iter()
+                            .any(|x| !x.barre && x.open_strings > 0 && x.root_in_bass)
+                    })
+                    .count();
+                CapoSuggestion {
+                    capo,
+                    open_chords,
+                    total_chords: chords.len(),
+                }
+            })
+            .collect();
+        suggestions.sort_by(|a, b| b.open_chords.cmp(&a.open_chords).then(a.capo.cmp(&b.capo)));
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Duration, Interval, Intervals, Octave, Pitch, Syllable, Unit};
+
+    use super::*;
+    use crate::prelude::TrackKind;
+
+    const C_SHAPE: [Option<u8>; 6] = [Some(0), Some(1), Some(0), Some(2), Some(3), None];
+
+    fn guitar_tab() -> Tab {
+        let mut tab = Tab::new_empty();
+        let note = tab.meta.calc_note_from_pitch(&Pitch::C, &Octave::P4);
+        let tone = CoreEntry::Tone(Tone::from(note), Duration::Simple(Unit::Whole)).into();
+        tab.tracks.push(Track::new("vocal".into(), TrackKind::Vocal, vec![tone]));
+        let shape = FrettedEntry6::Shape(HandShape6::from(C_SHAPE), Duration::Simple(Unit::Whole)).into();
+        tab.tracks.push(Track::new("guitar".into(), TrackKind::Guitar, vec![shape]));
+        tab
+    }
+
+    fn get_shape(tab: &Tab) -> HandShape6 {
+        match tab.tracks[1].entries[0].as_fretted6() {
+            Some(FrettedEntry6::Shape(shape, _)) => *shape,
+            _ => panic!("shape expected"),
+        }
+    }
+
+    fn add_chords(tab: &mut Tab, roots: &[Syllable]) {
+        let intervals = Intervals::Triad(Interval::Major3nd, Interval::Perfect5th);
+        let entries = roots
+            .iter()
+            .map(|root| CoreEntry::Chord(Chord::new(*root, intervals, None), Duration::Simple(Unit::Whole)).into())
+            .collect();
+        tab.tracks.push(Track::new("chord".into(), TrackKind::Chord, entries));
+    }
+
+    #[test]
+    fn test_transpose_tones_and_key() {
+        let mut tab = guitar_tab();
+        assert!(tab.transpose(Semitones(2), TransposeMode::MoveCapo));
+        assert_eq!(tab.meta.key, Key::D);
+        let tone = tab.tracks[0].entries[0].as_core().and_then(|x| x.as_tone()).unwrap();
+        let expected = Semitones::from((Pitch::D, Octave::P4));
+        assert_eq!(Semitones::from(tone.get_notes()[0]), expected);
+    }
+
+    #[test]
+    fn test_transpose_move_capo() {
+        let mut tab = guitar_tab();
+        assert!(tab.transpose(Semitones(2), TransposeMode::MoveCapo));
+        assert_eq!(get_shape(&tab), HandShape6::from(C_SHAPE));
+        match tab.tracks[1].entries.last().and_then(|x| x.as_fretted6()) {
+            Some(FrettedEntry6::Fretboard(fretboard)) => assert_eq!(fretboard.capo, 2),
+            _ => panic!("fretboard expected"),
+        }
+        assert!(!tab.transpose(Semitones(-3), TransposeMode::MoveCapo));
+        assert_eq!(tab.meta.key, Key::D);
+    }
+
+    #[test]
+    fn test_transpose_rewrite_frets() {
+        let mut tab = guitar_tab();
+        assert!(tab.transpose(Semitones(2), TransposeMode::RewriteFrets));
+        let shape = get_shape(&tab);
+        for string in 1..=6 {
+            let expected = HandShape6::from(C_SHAPE).string_fret(string).map(|x| x + 2);
+            assert_eq!(shape.string_fret_with_barre(string), expected);
+        }
+        assert_eq!(tab.tracks[1].entries.len(), 1);
+    }
+
+    #[test]
+    fn test_transpose_off_the_fretboard_keeps_tab() {
+        let mut tab = guitar_tab();
+        let entries = tab.tracks[1].entries.clone();
+        assert!(!tab.transpose(Semitones(-1), TransposeMode::RewriteFrets));
+        assert_eq!(tab.meta.key, Key::C);
+        assert_eq!(tab.tracks[1].entries, entries);
+    }
+
+    #[test]
+    fn test_capo_suggestions() {
+        let mut tab = guitar_tab();
+        tab.meta.key = Key::F;
+        add_chords(&mut tab, &[Syllable::Do, Syllable::Fa, Syllable::So]);
+        let suggestions = tab.calc_capo_suggestions(MAX_CAPO);
+        assert_eq!(suggestions.len(), MAX_CAPO as usize + 1);
+        assert_eq!(suggestions[0].capo, 3);
+        assert!(suggestions[0].is_all_open());
+        assert!(suggestions.windows(2).all(|x| x[0].open_chords >= x[1].open_chords));
+    }
+
+    #[test]
+    fn test_capo_suggestions_without_fretboard() {
+        let mut tab = Tab::new_empty();
+        add_chords(&mut tab, &[Syllable::Do]);
+        assert!(tab.calc_capo_suggestions(MAX_CAPO).is_empty());
+    }
+}
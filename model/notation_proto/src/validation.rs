@@ -0,0 +1,486 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use notation_core::prelude::Units;
+use notation_fretted::prelude::{FrettedEntry4, FrettedEntry6, Pick};
+
+use crate::prelude::{ProtoEntry, Slice, Tab, Track};
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum DiagnosticKind {
+    DuplicateTrack(String),
+    DuplicateSection(String),
+    /// A bar layer refers to a missing track, the section is dropped when parsing.
+    TrackNotFound(String),
+    /// A slice refers to a missing mark, it yields an empty lane.
+    MarkNotFound(String),
+    EmptySlice,
+    OverfullBar { units: Units, bar_units: Units },
+    UnderfullBar { units: Units, bar_units: Units },
+    UnusedSection(String),
+    /// A form entry refers to a missing or invalid section, it is skipped when parsing.
+    UnreachableFormEntry(String),
+    StringOutOfFretboard { string: u8, string_num: usize },
+    FretOutOfFretboard { fret: u8, fret_num: usize },
+    /// An ornament is not right before a tone or pick, it is ignored.
+    DanglingOrnament,
+}
+impl Display for DiagnosticKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DuplicateTrack(x) => write!(f, "duplicate track id: {}", x),
+            Self::DuplicateSection(x) => write!(f, "duplicate section id: {}", x),
+            Self::TrackNotFound(x) => write!(f, "track not found: {}", x),
+            Self::MarkNotFound(x) => write!(f, "mark not found: {}", x),
+            Self::EmptySlice => write!(f, "slice has no entries"),
+            Self::OverfullBar { units, bar_units } => {
+                write!(f, "overfull bar: {} units in {}", units.to_f32(), bar_units.to_f32())
+            }
+            Self::UnderfullBar { units, bar_units } => {
+                write!(f, "underfull bar: {} units in {}", units.to_f32(), bar_units.to_f32())
+            }
+            Self::UnusedSection(x) => write!(f, "section not in form: {}", x),
+            Self::UnreachableFormEntry(x) => write!(f, "form entry not reachable: {}", x),
+            Self::StringOutOfFretboard { string, string_num } => {
+                write!(f, "string {} not on a fretboard of {} strings", string, string_num)
+            }
+            Self::FretOutOfFretboard { fret, fret_num } => {
+                write!(f, "fret {} not on a fretboard of {} frets", fret, fret_num)
+            }
+            Self::DanglingOrnament => write!(f, "ornament not followed by a tone or pick"),
+        }
+    }
+}
+impl DiagnosticKind {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::DuplicateTrack(_)
+            | Self::DuplicateSection(_)
+            | Self::TrackNotFound(_)
+            | Self::MarkNotFound(_)
+            | Self::OverfullBar { .. }
+            | Self::UnreachableFormEntry(_)
+            | Self::StringOutOfFretboard { .. }
+            | Self::FretOutOfFretboard { .. } => Severity::Error,
+            Self::EmptySlice | Self::UnderfullBar { .. } | Self::DanglingOrnament => {
+                Severity::Warning
+            }
+            Self::UnusedSection(_) => Severity::Info,
+        }
+    }
+}
+
+/// Where a diagnostic was found, unset fields don't apply.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DiagnosticLocation {
+    pub track: Option<String>,
+    pub entry_index: Option<usize>,
+    pub section: Option<String>,
+    pub bar_index: Option<usize>,
+    pub layer_index: Option<usize>,
+    pub slice_index: Option<usize>,
+    pub form_index: Option<usize>,
+}
+impl Display for DiagnosticLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(x) = self.form_index {
+            parts.push(format!("form[{}]", x));
+        }
+        if let Some(ref x) = self.section {
+            parts.push(format!("section {}", x));
+        }
+        if let Some(x) = self.bar_index {
+            parts.push(format!("bar {}", x));
+        }
+        if let Some(x) = self.layer_index {
+            parts.push(format!("layer {}", x));
+        }
+        if let Some(ref x) = self.track {
+            parts.push(format!("track {}", x));
+        }
+        if let Some(x) = self.slice_index {
+            parts.push(format!("slice {}", x));
+        }
+        if let Some(x) = self.entry_index {
+            parts.push(format!("entry {}", x));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub kind: DiagnosticKind,
+    pub location: DiagnosticLocation,
+}
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} ({})", self.severity, self.kind, self.location)
+    }
+}
+impl Diagnostic {
+    pub fn new(kind: DiagnosticKind, location: DiagnosticLocation) -> Self {
+        Self {
+            severity: kind.severity(),
+            kind,
+            location,
+        }
+    }
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+macro_rules! impl_validate_frets {
+    ($name:ident, $fretted_entry:ident, $as_fretted:ident, $default_fretboard:ident) => {
+        impl Track {
+            fn $name(&self, diagnostics: &mut Vec<Diagnostic>) {
+                let fretboard = self
+                    .entries
+                    .iter()
+                    .find_map(|x| match x.$as_fretted() {
+                        Some($fretted_entry::Fretboard(fretboard)) => Some(*fretboard),
+                        _ => None,
+                    })
+                    .or_else(|| self.kind.$default_fretboard());
+                let fretboard = match fretboard {
+                    Some(fretboard) => fretboard,
+                    None => return,
+                };
+                let (string_num, fret_num) = (fretboard.string_num(), fretboard.fret_num());
+                for (index, entry) in self.entries.iter().enumerate() {
+                    let mut kinds = Vec::new();
+                    match entry.$as_fretted() {
+                        Some($fretted_entry::Pick(pick, _)) => {
+                            Self::check_pick(pick, string_num, fret_num, &mut kinds);
+                        }
+                        Some($fretted_entry::Shape(shape, _)) => {
+                            let fret = shape.max_fret_with_barre();
+                            if fret as usize >= fret_num {
+                                kinds.push(DiagnosticKind::FretOutOfFretboard { fret, fret_num });
+                            }
+                        }
+                        _ => {}
+                    }
+                    for kind in kinds {
+                        diagnostics.push(Diagnostic::new(kind, self.location(Some(index))));
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_validate_frets!(validate_frets6, FrettedEntry6, as_fretted6, default_fretboard6);
+impl_validate_frets!(validate_frets4, FrettedEntry4, as_fretted4, default_fretboard4);
+
+impl Track {
+    fn location(&self, entry_index: Option<usize>) -> DiagnosticLocation {
+        DiagnosticLocation {
+            track: Some(self.id.clone()),
+            entry_index,
+            ..DiagnosticLocation::default()
+        }
+    }
+    fn check_pick(pick: &Pick, string_num: usize, fret_num: usize, kinds: &mut Vec<DiagnosticKind>) {
+        for note in pick.get_notes() {
+            if note.string == 0 || note.string as usize > string_num {
+                kinds.push(DiagnosticKind::StringOutOfFretboard {
+                    string: note.string,
+                    string_num,
+                });
+            }
+            if let Some(fret) = note.fret {
+                if fret as usize >= fret_num {
+                    kinds.push(DiagnosticKind::FretOutOfFretboard { fret, fret_num });
+                }
+            }
+        }
+    }
+    fn validate_ornaments(&self, diagnostics: &mut Vec<Diagnostic>) {
+        for (index, entry) in self.entries.iter().enumerate() {
+            if entry.as_core_ornament().is_none() {
+                continue;
+            }
+            let attached = self
+                .entries
+                .get(index + 1)
+                .map(|x| x.is_ornament_target())
+                .unwrap_or(false);
+            if !attached {
+                let kind = DiagnosticKind::DanglingOrnament;
+                diagnostics.push(Diagnostic::new(kind, self.location(Some(index))));
+            }
+        }
+    }
+    fn calc_entries_units(entries: &[ProtoEntry]) -> Units {
+        entries.iter().map(|x| Units::from(x.duration())).sum()
+    }
+}
+
+impl Tab {
+    /// Check the tab for problems that parsing would skip quietly, errors first.
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        self.validate_tracks(&mut diagnostics);
+        let invalid_sections = self.validate_sections(&mut diagnostics);
+        self.validate_form(&invalid_sections, &mut diagnostics);
+        diagnostics.sort_by_key(|x| std::cmp::Reverse(x.severity));
+        diagnostics
+    }
+    pub fn has_errors(&self) -> bool {
+        self.validate().iter().any(|x| x.is_error())
+    }
+    fn validate_tracks(&self, diagnostics: &mut Vec<Diagnostic>) {
+        let mut ids = HashSet::new();
+        for track in self.tracks.iter() {
+            if !ids.insert(track.id.clone()) {
+                let kind = DiagnosticKind::DuplicateTrack(track.id.clone());
+                diagnostics.push(Diagnostic::new(kind, track.location(None)));
+            }
+            track.validate_frets6(diagnostics);
+            track.validate_frets4(diagnostics);
+            track.validate_ornaments(diagnostics);
+        }
+    }
+    /// Returns the ids of sections that would be dropped when parsing.
+    fn validate_sections(&self, diagnostics: &mut Vec<Diagnostic>) -> HashSet<String> {
+        let mut ids = HashSet::new();
+        let mut invalid_sections = HashSet::new();
+        let bar_units = self.meta.bar_units();
+        for section in self.sections.iter() {
+            let location = DiagnosticLocation {
+                section: Some(section.id.clone()),
+                ..DiagnosticLocation::default()
+            };
+            if !ids.insert(section.id.clone()) {
+                let kind = DiagnosticKind::DuplicateSection(section.id.clone());
+                diagnostics.push(Diagnostic::new(kind, location.clone()));
+            }
+            if !self.form.sections.contains(&section.id) {
+                let kind = DiagnosticKind::UnusedSection(section.id.clone());
+                diagnostics.push(Diagnostic::new(kind, location.clone()));
+            }
+            for (bar_index, bar) in section.bars.iter().enumerate() {
+                for (layer_index, layer) in bar.layers.iter().enumerate() {
+                    let location = DiagnosticLocation {
+                        bar_index: Some(bar_index),
+                        layer_index: Some(layer_index),
+                        track: Some(layer.track.clone()),
+                        ..location.clone()
+                    };
+                    let track = match self.tracks.iter().find(|x| x.id == layer.track) {
+                        Some(track) => track,
+                        None => {
+                            invalid_sections.insert(section.id.clone());
+                            let kind = DiagnosticKind::TrackNotFound(layer.track.clone());
+                            diagnostics.push(Diagnostic::new(kind, location));
+                            continue;
+                        }
+                    };
+                    for (slice_index, slice) in layer.slices.iter().enumerate() {
+                        let location = DiagnosticLocation {
+                            slice_index: Some(slice_index),
+                            ..location.clone()
+                        };
+                        if let Some(kind) = Self::check_slice(track, slice, bar_units) {
+                            diagnostics.push(Diagnostic::new(kind, location));
+                        }
+                    }
+                }
+            }
+        }
+        invalid_sections
+    }
+    fn check_slice(track: &Track, slice: &Slice, bar_units: Units) -> Option<DiagnosticKind> {
+        let (begin, end) = match slice.calc_range(track.entries.len(), |i, mark| track.entries[i].is_mark_str(mark)) {
+            Ok(range) => range,
+            Err(mark) => return Some(DiagnosticKind::MarkNotFound(mark)),
+        };
+        if begin == end {
+            return Some(DiagnosticKind::EmptySlice);
+        }
+        let units = Track::calc_entries_units(&track.entries[begin..end]);
+        if units > bar_units {
+            Some(DiagnosticKind::OverfullBar { units, bar_units })
+        } else if units < bar_units {
+            Some(DiagnosticKind::UnderfullBar { units, bar_units })
+        } else {
+            None
+        }
+    }
+    fn validate_form(&self, invalid_sections: &HashSet<String>, diagnostics: &mut Vec<Diagnostic>) {
+        for (index, id) in self.form.sections.iter().enumerate() {
+            if invalid_sections.contains(id) || !self.sections.iter().any(|x| x.id == *id) {
+                let kind = DiagnosticKind::UnreachableFormEntry(id.clone());
+                let location = DiagnosticLocation {
+                    form_index: Some(index),
+                    section: Some(id.clone()),
+                    ..DiagnosticLocation::default()
+                };
+                diagnostics.push(Diagnostic::new(kind, location));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{CoreEntry, Duration, Ornament, Semitones, Unit};
+    use notation_fretted::prelude::PickNote;
+
+    use super::*;
+    use crate::prelude::{Bar, BarLayer, Form, Section, SectionKind, SliceBegin, SliceEnd, TrackKind};
+
+    fn pick(string: u8, fret: u8, unit: Unit) -> ProtoEntry {
+        let pick = Pick::from(PickNote::new_string_fret(string, fret));
+        FrettedEntry6::Pick(pick, Duration::Simple(unit)).into()
+    }
+
+    fn slice(mark: &str, count: usize) -> Slice {
+        Slice::new(SliceBegin::Mark(mark.into()), SliceEnd::Count(count), None)
+    }
+
+    fn section(id: &str, track: &str, slice: Slice) -> Section {
+        let bar = Bar::new(vec![BarLayer::new(track.into(), vec![slice])]);
+        Section::new(id.into(), SectionKind::Verse, vec![bar])
+    }
+
+    fn valid_tab() -> Tab {
+        let mut tab = Tab::new_empty();
+        let entries = vec![
+            ProtoEntry::Mark("a".into()),
+            pick(1, 0, Unit::Whole),
+            pick(2, 1, Unit::Whole),
+        ];
+        tab.tracks.push(Track::new("guitar".into(), TrackKind::Guitar, entries));
+        tab.sections.push(section("verse", "guitar", slice("a", 1)));
+        tab.form = Form::from(vec!["verse"]);
+        tab
+    }
+
+    fn assert_kind(tab: &Tab, kind: DiagnosticKind) {
+        let diagnostics = tab.validate();
+        assert!(
+            diagnostics.iter().any(|x| x.kind == kind && x.severity == kind.severity()),
+            "{} not in {:?}",
+            kind,
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn test_valid_tab() {
+        let tab = valid_tab();
+        assert!(tab.validate().is_empty());
+        assert!(!tab.has_errors());
+    }
+
+    #[test]
+    fn test_duplicate_track() {
+        let mut tab = valid_tab();
+        tab.tracks.push(tab.tracks[0].clone());
+        assert_kind(&tab, DiagnosticKind::DuplicateTrack("guitar".into()));
+        assert!(tab.has_errors());
+    }
+
+    #[test]
+    fn test_duplicate_section() {
+        let mut tab = valid_tab();
+        tab.sections.push(tab.sections[0].clone());
+        assert_kind(&tab, DiagnosticKind::DuplicateSection("verse".into()));
+    }
+
+    #[test]
+    fn test_track_not_found() {
+        let mut tab = valid_tab();
+        tab.sections[0] = section("verse", "piano", slice("a", 1));
+        assert_kind(&tab, DiagnosticKind::TrackNotFound("piano".into()));
+        assert_kind(&tab, DiagnosticKind::UnreachableFormEntry("verse".into()));
+    }
+
+    #[test]
+    fn test_mark_not_found() {
+        let mut tab = valid_tab();
+        tab.sections[0] = section("verse", "guitar", slice("b", 1));
+        assert_kind(&tab, DiagnosticKind::MarkNotFound("b".into()));
+    }
+
+    #[test]
+    fn test_empty_slice() {
+        let mut tab = valid_tab();
+        tab.sections[0] = section("verse", "guitar", slice("a", 0));
+        assert_kind(&tab, DiagnosticKind::EmptySlice);
+    }
+
+    #[test]
+    fn test_overfull_and_underfull_bar() {
+        let bar_units = Units::from(Unit::Whole);
+        let mut tab = valid_tab();
+        tab.sections[0] = section("verse", "guitar", slice("a", 2));
+        let units = bar_units + bar_units;
+        assert_kind(&tab, DiagnosticKind::OverfullBar { units, bar_units });
+        let mut tab = valid_tab();
+        tab.tracks[0].entries[1] = pick(1, 0, Unit::Half);
+        let units = Units::from(Unit::Half);
+        assert_kind(&tab, DiagnosticKind::UnderfullBar { units, bar_units });
+    }
+
+    #[test]
+    fn test_unused_section() {
+        let mut tab = valid_tab();
+        tab.sections.push(section("chorus", "guitar", slice("a", 1)));
+        assert_kind(&tab, DiagnosticKind::UnusedSection("chorus".into()));
+        assert!(!tab.has_errors());
+    }
+
+    #[test]
+    fn test_unreachable_form_entry() {
+        let mut tab = valid_tab();
+        tab.form.sections.push("chorus".into());
+        assert_kind(&tab, DiagnosticKind::UnreachableFormEntry("chorus".into()));
+    }
+
+    #[test]
+    fn test_out_of_fretboard() {
+        let mut tab = valid_tab();
+        tab.tracks[0].entries[1] = pick(7, 0, Unit::Whole);
+        tab.tracks[0].entries[2] = pick(1, 100, Unit::Whole);
+        assert_kind(&tab, DiagnosticKind::StringOutOfFretboard { string: 7, string_num: 6 });
+        let fret_num = tab.tracks[0].kind.default_fretboard6().unwrap().fret_num();
+        assert_kind(&tab, DiagnosticKind::FretOutOfFretboard { fret: 100, fret_num });
+    }
+
+    #[test]
+    fn test_dangling_ornament() {
+        let mut tab = valid_tab();
+        let ornament: ProtoEntry = CoreEntry::Ornament(Ornament::Grace(Semitones(2))).into();
+        tab.tracks[0].entries.insert(2, ornament.clone());
+        assert!(tab.validate().is_empty());
+        tab.tracks[0].entries.push(ornament);
+        assert_kind(&tab, DiagnosticKind::DanglingOrnament);
+    }
+
+    #[test]
+    fn test_errors_first() {
+        let mut tab = valid_tab();
+        tab.sections.push(section("chorus", "guitar", slice("a", 1)));
+        tab.tracks.push(tab.tracks[0].clone());
+        let severities: Vec<Severity> = tab.validate().iter().map(|x| x.severity).collect();
+        assert_eq!(severities, vec![Severity::Error, Severity::Info]);
+    }
+}
@@ -0,0 +1,294 @@
+use std::fmt::Display;
+
+use notation_core::prelude::{CoreEntry, Note, Semitones, Tone};
+
+use crate::prelude::{ProtoEntry, Tab, Track, TrackKind, TransposeMode};
+
+/// Lowest and highest notes of a melody, or what a singer can reach.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct VocalRange {
+    pub lowest: Note,
+    pub highest: Note,
+}
+impl Display for VocalRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<VocalRange>({} - {})", self.lowest, self.highest)
+    }
+}
+impl VocalRange {
+    pub fn new(lowest: Note, highest: Note) -> Self {
+        Self { lowest, highest }
+    }
+    pub fn span(&self) -> Semitones {
+        Semitones::from(self.highest) - Semitones::from(self.lowest)
+    }
+    /// Semitones of the range that fall outside the target, after moving it by the shift.
+    pub fn calc_overflow(&self, target: &VocalRange, shift: Semitones) -> Semitones {
+        let lowest = Semitones::from(self.lowest).0 as i32 + shift.0 as i32;
+        let highest = Semitones::from(self.highest).0 as i32 + shift.0 as i32;
+        let below = (Semitones::from(target.lowest).0 as i32 - lowest).max(0);
+        let above = (highest - Semitones::from(target.highest).0 as i32).max(0);
+        Semitones((below + above).min(i8::MAX as i32) as i8)
+    }
+    /// Distance between the middles of the ranges after moving by the shift, in half semitones.
+    fn calc_offset(&self, target: &VocalRange, shift: Semitones) -> i32 {
+        let sum = |a: Note, b: Note| Semitones::from(a).0 as i32 + Semitones::from(b).0 as i32;
+        let middle = sum(self.lowest, self.highest) + shift.0 as i32 * 2;
+        (middle - sum(target.lowest, target.highest)).abs()
+    }
+    /// Merge with the notes of another range.
+    pub fn merge(&self, other: &VocalRange) -> Self {
+        let lowest = if Semitones::from(other.lowest) < Semitones::from(self.lowest) {
+            other.lowest
+        } else {
+            self.lowest
+        };
+        let highest = if Semitones::from(other.highest) > Semitones::from(self.highest) {
+            other.highest
+        } else {
+            self.highest
+        };
+        Self::new(lowest, highest)
+    }
+}
+
+/// Key change and octave shift to fit the vocal into a range, see [`Tab::calc_vocal_fits()`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct VocalFit {
+    /// Transposition of the whole tab, within half an octave.
+    pub semitones: Semitones,
+    /// Extra octaves for the vocal only, e.g. -1 for a lower voice singing along.
+    pub octave_shift: i8,
+    /// Semitones of the vocal still out of the target range.
+    pub overflow: Semitones,
+}
+impl Display for VocalFit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "<VocalFit>({} {} {})",
+            self.semitones.0, self.octave_shift, self.overflow.0
+        )
+    }
+}
+impl VocalFit {
+    pub fn calc_vocal_shift(&self) -> Semitones {
+        self.semitones + Semitones(self.octave_shift * 12)
+    }
+}
+
+/// Octaves to try shifting the vocal by.
+const MAX_OCTAVE_SHIFT: i8 = 2;
+
+impl Track {
+    /// Lowest and highest notes of the tones in the track.
+    pub fn calc_vocal_range(&self) -> Option<VocalRange> {
+        let mut range: Option<VocalRange> = None;
+        for entry in self.entries.iter() {
+            if let ProtoEntry::Core(CoreEntry::Tone(tone, _)) = entry {
+                for note in tone.get_notes() {
+                    let note_range = VocalRange::new(note, note);
+                    range = Some(match range {
+                        Some(range) => range.merge(&note_range),
+                        None => note_range,
+                    });
+                }
+            }
+        }
+        range
+    }
+}
+
+impl Tab {
+    /// Range of all the `Vocal` tracks, `None` if there are no vocal notes.
+    pub fn calc_vocal_range(&self) -> Option<VocalRange> {
+        self.tracks
+            .iter()
+            .filter(|x| x.kind == TrackKind::Vocal)
+            .filter_map(|x| x.calc_vocal_range())
+            .reduce(|a, b| a.merge(&b))
+    }
+    /// Ways to fit the vocal into the target, best first: least overflow, smallest key
+    /// change, no octave shift, then closest to the middle of the target.
+    pub fn calc_vocal_fits(&self, target: &VocalRange) -> Vec<VocalFit> {
+        let range = match self.calc_vocal_range() {
+            Some(range) => range,
+            None => return vec![],
+        };
+        let mut fits = Vec::new();
+        for semitones in -6..=6 {
+            for octave_shift in -MAX_OCTAVE_SHIFT..=MAX_OCTAVE_SHIFT {
+                let fit = VocalFit {
+                    semitones: Semitones(semitones),
+                    octave_shift,
+                    overflow: Semitones(0),
+                };
+                fits.push(VocalFit {
+                    overflow: range.calc_overflow(target, fit.calc_vocal_shift()),
+                    ..fit
+                });
+            }
+        }
+        fits.sort_by_key(|x| {
+            (
+                x.overflow,
+                x.semitones.0.abs(),
+                x.octave_shift.abs(),
+                range.calc_offset(target, x.calc_vocal_shift()),
+            )
+        });
+        fits
+    }
+    /// Transpose the tab with the best fit that the fretted tracks can follow in the
+    /// mode, and move the vocal by the octave shift. Returns the applied fit.
+    pub fn fit_vocal_range(&mut self, target: &VocalRange, mode: TransposeMode) -> Option<VocalFit> {
+        for fit in self.calc_vocal_fits(target) {
+            if self.transpose(fit.semitones, mode) {
+                self.shift_vocal_octaves(fit.octave_shift);
+                return Some(fit);
+            }
+        }
+        None
+    }
+    fn shift_vocal_octaves(&mut self, octave_shift: i8) {
+        if octave_shift == 0 {
+            return;
+        }
+        let meta = self.meta;
+        for track in self.tracks.iter_mut().filter(|x| x.kind == TrackKind::Vocal) {
+            for entry in track.entries.iter_mut() {
+                if let ProtoEntry::Core(CoreEntry::Tone(tone, duration)) = entry {
+                    let notes: Vec<Note> = tone
+                        .get_notes()
+                        .into_iter()
+                        .map(|x| {
+                            let semitones = Semitones::from(x) + Semitones(octave_shift * 12);
+                            meta.scale.calc_note_from_semitones(&meta.key, semitones)
+                        })
+                        .collect();
+                    *entry = CoreEntry::Tone(Tone::from(notes), *duration).into();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notation_core::prelude::{Duration, Key, Octave, Pitch, Unit};
+    use notation_fretted::prelude::{FrettedEntry6, HandShape6};
+
+    use super::*;
+    use crate::prelude::TabMeta;
+
+    fn note(pitch: Pitch, octave: Octave) -> Note {
+        TabMeta::default().calc_note_from_pitch(&pitch, &octave)
+    }
+
+    fn range(lowest: (Pitch, Octave), highest: (Pitch, Octave)) -> VocalRange {
+        VocalRange::new(note(lowest.0, lowest.1), note(highest.0, highest.1))
+    }
+
+    fn tones(notes: &[(Pitch, Octave)]) -> Vec<ProtoEntry> {
+        let duration = Duration::Simple(Unit::Quarter);
+        notes
+            .iter()
+            .map(|(pitch, octave)| CoreEntry::Tone(Tone::from(note(*pitch, *octave)), duration).into())
+            .collect()
+    }
+
+    /// Vocal from C4 to G4 in C major, with a piano and an open C shape on the guitar.
+    fn tab() -> Tab {
+        let mut tab = Tab::new_empty();
+        let vocal = tones(&[(Pitch::E, Octave::P4), (Pitch::C, Octave::P4), (Pitch::G, Octave::P4)]);
+        tab.tracks.push(Track::new("vocal".into(), TrackKind::Vocal, vocal));
+        tab.tracks.push(Track::new("piano".into(), TrackKind::Piano, tones(&[(Pitch::C, Octave::P2)])));
+        let shape = HandShape6::from([Some(0), Some(1), Some(0), Some(2), Some(3), None]);
+        let guitar = vec![FrettedEntry6::Shape(shape, Duration::Simple(Unit::Whole)).into()];
+        tab.tracks.push(Track::new("guitar".into(), TrackKind::Guitar, guitar));
+        tab
+    }
+
+    fn fit(semitones: i8, octave_shift: i8, overflow: i8) -> VocalFit {
+        VocalFit {
+            semitones: Semitones(semitones),
+            octave_shift,
+            overflow: Semitones(overflow),
+        }
+    }
+
+    fn best_fits(target: &VocalRange, count: usize) -> Vec<VocalFit> {
+        tab().calc_vocal_fits(target).into_iter().take(count).collect()
+    }
+
+    #[test]
+    fn test_vocal_range() {
+        let mut tab = tab();
+        let expected = range((Pitch::C, Octave::P4), (Pitch::G, Octave::P4));
+        assert_eq!(tab.calc_vocal_range(), Some(expected));
+        assert_eq!(expected.span(), Semitones(7));
+        let low = tones(&[(Pitch::A, Octave::P3)]);
+        tab.tracks.push(Track::new("backing".into(), TrackKind::Vocal, low));
+        let merged = tab.calc_vocal_range().unwrap();
+        assert_eq!(merged.lowest, note(Pitch::A, Octave::P3));
+        assert_eq!(merged.highest, note(Pitch::G, Octave::P4));
+        let target = range((Pitch::D, Octave::P4), (Pitch::E, Octave::P4));
+        assert_eq!(expected.calc_overflow(&target, Semitones(0)), Semitones(5));
+        assert_eq!(expected.calc_overflow(&target, Semitones(2)), Semitones(5));
+        assert!(Tab::new_empty().calc_vocal_range().is_none());
+        assert!(Tab::new_empty().calc_vocal_fits(&target).is_empty());
+    }
+
+    #[test]
+    fn test_overflow_before_key_change() {
+        let target = range((Pitch::D, Octave::P4), (Pitch::A, Octave::P4));
+        assert_eq!(best_fits(&target, 1), vec![fit(2, 0, 0)]);
+    }
+
+    #[test]
+    fn test_key_change_before_octave_shift() {
+        // Both a semitone down and an octave down fit, keeping the key wins.
+        let target = range((Pitch::C, Octave::P3), (Pitch::F_SHARP, Octave::P4));
+        assert_eq!(best_fits(&target, 2), vec![fit(0, -1, 0), fit(-1, 0, 0)]);
+    }
+
+    #[test]
+    fn test_octave_shift_before_centring() {
+        // An octave down is closer to the middle, but no shift is preferred.
+        let target = range((Pitch::C, Octave::P2), (Pitch::C, Octave::P5));
+        assert_eq!(best_fits(&target, 3), vec![fit(0, 0, 0), fit(0, -1, 0), fit(0, -2, 0)]);
+    }
+
+    #[test]
+    fn test_centring() {
+        let target = range((Pitch::A_SHARP, Octave::P3), (Pitch::G_SHARP, Octave::P4));
+        assert_eq!(
+            best_fits(&target, 4),
+            vec![fit(0, 0, 0), fit(-1, 0, 0), fit(1, 0, 0), fit(-2, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_octave_shift_vocal_only() {
+        let mut tab = tab();
+        let target = range((Pitch::C, Octave::P3), (Pitch::G, Octave::P3));
+        let applied = tab.fit_vocal_range(&target, TransposeMode::RewriteFrets);
+        assert_eq!(applied, Some(fit(0, -1, 0)));
+        assert_eq!(tab.meta.key, Key::C);
+        assert_eq!(tab.calc_vocal_range(), Some(target));
+        assert_eq!(tab.tracks[1].entries, tones(&[(Pitch::C, Octave::P2)]));
+    }
+
+    #[test]
+    fn test_fall_back_when_transpose_refuses() {
+        let mut tab = tab();
+        // A semitone down fits best, but takes the open strings off the fretboard.
+        let target = range((Pitch::B, Octave::P3), (Pitch::F_SHARP, Octave::P4));
+        assert_eq!(tab.calc_vocal_fits(&target)[0], fit(-1, 0, 0));
+        let entries = tab.tracks[2].entries.clone();
+        let applied = tab.fit_vocal_range(&target, TransposeMode::RewriteFrets);
+        assert_eq!(applied, Some(fit(0, 0, 1)));
+        assert_eq!(tab.meta.key, Key::C);
+        assert_eq!(tab.tracks[2].entries, entries);
+    }
+}
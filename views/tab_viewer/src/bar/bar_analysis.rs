@@ -0,0 +1,59 @@
+use edger_bevy::bevy_prelude::*;
+use notation_model::prelude::{ChordAnalysis, TabBar, Units};
+
+use crate::prelude::{NotationAssets, NotationTheme};
+use crate::tab::tab_events::BarViewDoLayoutEvent;
+
+/// Roman numeral of a chord, shown on top of the bar at the chord's position.
+#[derive(Clone, Debug, Component)]
+pub struct BarAnalysisText {
+    pub in_bar_pos: Units,
+    pub bar_units: Units,
+}
+
+impl BarAnalysisText {
+    pub fn calc_text(analysis: &ChordAnalysis) -> String {
+        match analysis.cadence {
+            Some(cadence) => format!("{} ({})", analysis.numeral, cadence.to_text()),
+            None => analysis.numeral.to_string(),
+        }
+    }
+    pub fn spawn(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        bar_entity: Entity,
+        bar: &TabBar,
+        analysis: &[ChordAnalysis],
+    ) {
+        for chord in analysis.iter().filter(|x| x.bar_ordinal == bar.props.bar_ordinal) {
+            let text_entity = theme.texts.tab.spawn_analysis_text(
+                commands,
+                assets,
+                bar_entity,
+                Self::calc_text(chord).as_str(),
+            );
+            commands.entity(text_entity).insert(BarAnalysisText {
+                in_bar_pos: chord.in_bar_pos,
+                bar_units: bar.props.bar_units,
+            });
+        }
+    }
+    pub fn update_text(
+        theme: Res<NotationTheme>,
+        mut evts: EventReader<BarViewDoLayoutEvent>,
+        mut text_query: Query<(&Parent, &BarAnalysisText, &mut Transform)>,
+    ) {
+        if theme._bypass_systems {
+            return;
+        }
+        for evt in evts.read() {
+            for (parent, text, mut transform) in text_query.iter_mut() {
+                if parent.get() == evt.entity {
+                    let x = evt.layout.size.width * text.in_bar_pos.to_f32() / text.bar_units.to_f32();
+                    theme.texts.tab.update_analysis_x(&mut transform, x);
+                }
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ use edger_bevy::prelude::AssetsStates;
 
 use crate::tab::tab_events::BarViewDoLayoutEvent;
 
+use super::bar_analysis::BarAnalysisText;
 use super::bar_view::BarView;
 
 pub struct BarPlugin;
@@ -14,6 +15,7 @@ impl Plugin for BarPlugin {
         app.add_systems(Update, (
             BarView::do_layout,
             BarView::update_number_text,
+            BarAnalysisText::update_text,
         ).run_if(in_state(AssetsStates::Loaded)));
     }
 }
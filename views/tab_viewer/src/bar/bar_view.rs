@@ -14,11 +14,19 @@ use edger_bevy::prelude::{
 };
 use notation_model::prelude::TabBar;
 
+use super::bar_analysis::BarAnalysisText;
 use super::bar_beat::{BarBeatData, BarBeatValue};
 use super::bar_separator::{BarSeparatorData, BarSeparatorValue};
 
 pub type BarView = BarData<BarLayoutData>;
 
+pub type BarNumberTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Parent, &'static mut Transform),
+    (With<Text>, Without<BarAnalysisText>),
+>;
+
 impl<'a> View<NotationLayout<'a>> for BarView {}
 impl<'a> GridCell<NotationLayout<'a>> for BarView {
     fn order(&self) -> usize {
@@ -80,7 +88,7 @@ impl BarView {
         theme: Res<NotationTheme>,
         settings: Res<NotationSettings>,
         mut evts: EventReader<BarViewDoLayoutEvent>,
-        mut text_query: Query<(&Parent, &mut Transform), With<Text>>,
+        mut text_query: BarNumberTextQuery,
     ) {
         if theme._bypass_systems {
             return;
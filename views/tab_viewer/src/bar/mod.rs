@@ -1,3 +1,4 @@
+pub mod bar_analysis;
 pub mod bar_beat;
 pub mod bar_bundle;
 pub mod bar_layout;
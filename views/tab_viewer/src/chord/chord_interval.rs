@@ -20,7 +20,9 @@ impl ChordNoteExtra for ChordIntervalExtra {
         self.diagram_radius = diagram_radius;
     }
     fn radius(&self, theme: &NotationTheme) -> f32 {
-        self.diagram_radius * theme.sizes.chord.diagram_interval_radius_factor
+        // shrink the notes to fit around the diagram for extended chords
+        let fit_factor = (4.0 / self.total as f32).min(1.0);
+        self.diagram_radius * theme.sizes.chord.diagram_interval_radius_factor * fit_factor
     }
     fn offset(&self, theme: &NotationTheme) -> Vec2 {
         let angle_offset = match self.total {
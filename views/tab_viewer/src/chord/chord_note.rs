@@ -124,6 +124,10 @@ impl<T: ChordNoteExtra + 'static> ChordNoteData<T> {
                 let dot_data = IntervalDotData::new(quality, dot_count, index, note_radius);
                 dot_data.create(commands, theme, note_entity);
             }
+            if self.value.interval.is_compound() {
+                let ring_data = IntervalDotData::new_ring(quality, note_radius);
+                ring_data.create(commands, theme, note_entity);
+            }
         }
     }
 }
@@ -7,7 +7,7 @@ use edger_bevy::prelude::{
 };
 use notation_model::prelude::TabChord;
 
-use crate::prelude::{NotationAssets, NotationTheme};
+use crate::prelude::{NotationAssets, NotationSettings, NotationTheme};
 use crate::prelude::NotationLayout;
 
 use super::chord_base::ChordBaseData;
@@ -27,6 +27,27 @@ impl Display for ChordView {
     }
 }
 
+/// Letter name of the chord, e.g. `Am7/G`, shown under the diagram.
+#[derive(Clone, Debug, Component)]
+pub struct ChordSymbolText;
+
+/// Frets of the best guitar voicing of the chord, e.g. `[x32010]`.
+#[derive(Clone, Debug, Component)]
+pub struct ChordVoicingText;
+
+pub type ChordBarsTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    &'static mut Transform,
+    (With<Text>, Without<ChordSymbolText>, Without<ChordVoicingText>),
+>;
+pub type ChordVoicingTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    &'static mut Transform,
+    (With<Text>, With<ChordVoicingText>, Without<ChordSymbolText>),
+>;
+
 impl<'a> View<NotationLayout<'a>> for ChordView {
     fn pivot(&self) -> LayoutAnchor {
         LayoutAnchor::CENTER
@@ -43,7 +64,9 @@ impl ChordView {
         mut interval_query: Query<(Entity, &mut ChordIntervalData, &Children)>,
         mut base_query: Query<(Entity, &mut ChordBaseData, &Children)>,
         mut dot_query: Query<(Entity, &mut IntervalDotData)>,
-        mut text_query: Query<&mut Transform, With<Text>>,
+        mut text_query: ChordBarsTextQuery,
+        mut symbol_query: Query<&mut Transform, (With<Text>, With<ChordSymbolText>)>,
+        mut voicing_query: ChordVoicingTextQuery,
     ) {
         if theme._bypass_systems {
             return;
@@ -68,6 +91,12 @@ impl ChordView {
                 if let Ok(mut transform) = text_query.get_mut(*child) {
                     theme.texts.chord.update_bars_xy(&mut transform, layout);
                 }
+                if let Ok(mut transform) = symbol_query.get_mut(*child) {
+                    theme.texts.chord.update_symbol_xy(&mut transform, layout);
+                }
+                if let Ok(mut transform) = voicing_query.get_mut(*child) {
+                    theme.texts.chord.update_voicing_xy(&mut transform, layout);
+                }
             }
         }
     }
@@ -75,6 +104,7 @@ impl ChordView {
         commands: &mut Commands,
         assets: &NotationAssets,
         theme: &NotationTheme,
+        settings: &NotationSettings,
         entity: Entity,
         chord: &TabChord,
     ) -> Entity {
@@ -108,6 +138,36 @@ impl ChordView {
                 theme.z.chord_text,
             );
         }
+        if settings.show_chord_symbol {
+            if let Some(text) = chord
+                .bars
+                .first()
+                .and_then(|x| x.tab_meta().calc_chord_symbol(&chord.chord))
+                .map(|x| x.to_text())
+            {
+                let text_entity = theme.texts.chord.spawn_symbol_text(
+                    commands,
+                    assets,
+                    chord_entity,
+                    text.as_str(),
+                    theme.z.chord_text,
+                );
+                commands.entity(text_entity).insert(ChordSymbolText);
+            }
+        }
+        if settings.show_chord_voicing {
+            let voicings = chord.tab().map(|x| x.get_guitar_chord_voicings(&chord.chord));
+            if let Some(voicing) = voicings.as_ref().and_then(|x| x.first()) {
+                let text_entity = theme.texts.chord.spawn_voicing_text(
+                    commands,
+                    assets,
+                    chord_entity,
+                    voicing.shape.to_string().as_str(),
+                    theme.z.chord_text,
+                );
+                commands.entity(text_entity).insert(ChordVoicingText);
+            }
+        }
         chord_entity
     }
     pub fn on_chord_playing_changed(
@@ -13,6 +13,8 @@ pub struct IntervalDotData {
     pub index: usize,
     pub note_radius: f32,
     pub is_orphan: bool,
+    /// Ring around the note for compound intervals (9th, 11th, 13th).
+    pub is_ring: bool,
 }
 
 pub type DotQuery<'w, 's, 'p, 'd> = Query<'w, 's, (&'p Parent, Entity, &'d mut IntervalDotData)>;
@@ -25,6 +27,13 @@ impl IntervalDotData {
             index,
             note_radius,
             is_orphan: false,
+            is_ring: false,
+        }
+    }
+    pub fn new_ring(quality: IntervalQuality, note_radius: f32) -> Self {
+        Self {
+            is_ring: true,
+            ..Self::new(quality, 0, 0, note_radius)
         }
     }
     fn circle_offset(total: usize, index: usize, note_radius: f32, factor: f32) -> Vec3 {
@@ -79,6 +88,15 @@ impl IntervalDotData {
 
 impl ShapeOp<NotationTheme, OutlineCircle> for IntervalDotData {
     fn get_shape(&self, theme: &NotationTheme) -> OutlineCircle {
+        if self.is_ring {
+            return OutlineCircle {
+                radius: self.note_radius * theme.sizes.chord.compound_ring_radius_factor,
+                color: Color::NONE,
+                outline_width: theme.sizes.chord.compound_ring_outline,
+                outline_color: theme.colors.chord.dot_outline.of_quality(&self.quality),
+                offset: Vec3::new(0.0, 0.0, 1.0),
+            };
+        }
         let radius = if self.total == 1 {
             self.note_radius * theme.sizes.chord.interval_dot_big_radius_factor
         } else {
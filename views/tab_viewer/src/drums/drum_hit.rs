@@ -0,0 +1,79 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{offscreen, OutlineCircle, ShapeOp};
+use notation_model::prelude::DrumHit;
+use notation_midi::prelude::PlayingState;
+
+use crate::prelude::{EntryData, NotationTheme};
+
+pub type DrumHitData = EntryData<DrumHitValue>;
+
+#[derive(Clone, Debug)]
+pub struct DrumHitValue {
+    pub hit: DrumHit,
+    pub playing_state: PlayingState,
+    pub bar_size: f32,
+}
+
+impl DrumHitValue {
+    pub fn new(hit: DrumHit) -> Self {
+        Self {
+            hit,
+            playing_state: PlayingState::Idle,
+            bar_size: 0.0,
+        }
+    }
+}
+
+impl DrumHitData {
+    pub fn calc_radius(&self, theme: &NotationTheme) -> f32 {
+        if self.value.hit.accent {
+            theme.sizes.drums.accent_hit_radius
+        } else {
+            theme.sizes.drums.hit_radius
+        }
+    }
+    pub fn calc_outline(&self, theme: &NotationTheme) -> f32 {
+        theme
+            .sizes
+            .drums
+            .hit_outline
+            .of_state(&self.value.playing_state)
+    }
+    pub fn calc_outline_color(&self, theme: &NotationTheme) -> Color {
+        if self.value.hit.flam {
+            theme.colors.drums.flam
+        } else {
+            theme
+                .colors
+                .drums
+                .outline
+                .of_state(&self.value.playing_state)
+        }
+    }
+}
+
+impl ShapeOp<NotationTheme, OutlineCircle> for DrumHitData {
+    fn get_shape(&self, theme: &NotationTheme) -> OutlineCircle {
+        let radius = self.calc_radius(theme);
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x =
+                self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.in_bar_pos.to_f32();
+            let y = theme.sizes.drums.calc_row_y(self.value.hit.piece.row());
+            let extra_z = if self.value.playing_state.is_current() {
+                1.0
+            } else {
+                0.0
+            };
+            Vec3::new(x + radius, y, theme.z.pick + extra_z)
+        };
+        OutlineCircle {
+            radius,
+            color: theme.colors.drums.hit.of_state(&self.value.playing_state),
+            outline_width: self.calc_outline(theme),
+            outline_color: self.calc_outline_color(theme),
+            offset,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{ShapeOp, StrokeLine};
+
+use crate::prelude::{LaneData, NotationTheme};
+
+#[derive(Clone, Debug)]
+pub struct DrumRowValue {
+    pub row: usize,
+    pub bar_size: f32,
+}
+
+pub type DrumRowData = LaneData<DrumRowValue>;
+
+impl ShapeOp<NotationTheme, StrokeLine> for DrumRowData {
+    fn get_shape(&self, theme: &NotationTheme) -> StrokeLine {
+        let y = theme.sizes.drums.calc_row_y(self.value.row);
+        StrokeLine {
+            from: Vec2::ZERO,
+            to: Vec2::new(self.value.bar_size, 0.0),
+            line_width: 1.0,
+            color: theme.colors.drums.row,
+            offset: Vec3::new(0.0, y, theme.z.string),
+        }
+    }
+}
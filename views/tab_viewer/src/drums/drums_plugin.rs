@@ -0,0 +1,78 @@
+use edger_bevy::bevy::ecs::system::EntityCommands;
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{AssetsStates, ShapeOp};
+
+use crate::prelude::{EntryPlaying, NotationSettings, NotationTheme, SingleData};
+use notation_model::prelude::{BarLane, DrumEntry, LaneEntry};
+
+use super::drum_hit::{DrumHitData, DrumHitValue};
+use super::drums_grid::DrumsGrid;
+
+pub struct DrumsPlugin;
+
+impl Plugin for DrumsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            on_add_drums_grid,
+            on_entry_playing_changed,
+        ).run_if(in_state(AssetsStates::Loaded)));
+    }
+}
+
+fn on_add_drums_grid(
+    mut commands: Commands,
+    theme: Res<NotationTheme>,
+    query: Query<(Entity, &SingleData<BarLane>, &DrumsGrid), Added<DrumsGrid>>,
+) {
+    if theme._bypass_systems {
+        return;
+    }
+    for (entity, lane, drums_grid) in query.iter() {
+        drums_grid.add_rows(&mut commands, &theme, entity, &lane.0);
+    }
+}
+
+fn on_entry_playing_changed(
+    mut commands: Commands,
+    settings: Res<NotationSettings>,
+    theme: Res<NotationTheme>,
+    query: Query<(&EntryPlaying, &Children), Changed<EntryPlaying>>,
+    mut hit_query: Query<(Entity, &mut DrumHitData)>,
+) {
+    if theme._bypass_systems {
+        return;
+    }
+    if settings.hide_drums_lane {
+        return;
+    }
+    for (playing, children) in query.iter() {
+        for child in children.iter() {
+            if let Ok((entity, mut data)) = hit_query.get_mut(*child) {
+                data.value.playing_state = playing.value;
+                data.update(&mut commands, &theme, entity);
+            }
+        }
+    }
+}
+
+impl DrumsPlugin {
+    pub fn insert_lane_extra(commands: &mut EntityCommands, _lane: &BarLane) {
+        commands.insert(DrumsGrid::default());
+    }
+    pub fn insert_entry_extra(
+        commands: &mut Commands,
+        theme: &NotationTheme,
+        settings: &NotationSettings,
+        entity: Entity,
+        entry: &LaneEntry,
+        drum_entry: &DrumEntry,
+    ) {
+        if settings.hide_drums_lane {
+            return;
+        }
+        for hit in drum_entry.get_hits().iter() {
+            let data = DrumHitData::new(entry, DrumHitValue::new(*hit));
+            data.create(commands, theme, entity);
+        }
+    }
+}
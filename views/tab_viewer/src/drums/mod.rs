@@ -0,0 +1,4 @@
+pub mod drum_hit;
+pub mod drum_row;
+pub mod drums_grid;
+pub mod drums_plugin;
@@ -18,7 +18,7 @@ impl EntryPlaying {
                 if tab_state.is_bar_in_range(entry_playing.bar_props.bar_ordinal) {
                     if entry.0.bar_props().bar_ordinal
                         == tab_state.play_control.position.bar.bar_ordinal
-                        && entry.0.props.in_bar_pos.0 == 0.0
+                        && entry.0.props.in_bar_pos.is_zero()
                     {
                         entry_playing.value = PlayingState::Current;
                     } else {
@@ -6,10 +6,14 @@ use edger_bevy::prelude::AssetsStates;
 use notation_model::lane_kind::LaneKind;
 
 use crate::chord::chord_view::ChordView;
+use crate::drums::drum_hit::DrumHitData;
+use crate::drums::drum_row::DrumRowData;
+use crate::extension::extension_text::ExtensionTextData;
 use crate::tone::tone_line::ToneLineData;
 use crate::lane::lane_layout::LaneLayoutData;
+use crate::ornament::ornament_note::OrnamentNoteData;
 use crate::prelude::{
-    entity, ChordBundle, EntryBundle, LyricsPlugin, NotationAssets,
+    entity, ChordBundle, DrumsPlugin, EntryBundle, ExtensionPlugin, LyricsPlugin, NotationAssets,
     NotationSettings, NotationTheme, ShapesPlugin, StringsPlugin, ToneBundle,
 };
 use crate::shapes::shape_diagram::{ShapeDiagramData4, ShapeDiagramData6};
@@ -18,7 +22,7 @@ use crate::strings::single_string::SingleStringData;
 use crate::tab::tab_events::TabBarsResizedEvent;
 use crate::tone::tone_note::ToneNoteData;
 use crate::word::word_text::WordTextData;
-use notation_model::prelude::{CoreEntry, LaneEntry, ProtoEntry};
+use notation_model::prelude::{CoreEntry, LaneEntry, ProtoEntry, TrackKind};
 
 pub struct EntryPlugin;
 
@@ -112,6 +116,23 @@ fn insert_entry_extra(
                 fretted_entry,
             );
         }
+        ProtoEntry::Drum(drum_entry) => DrumsPlugin::insert_entry_extra(
+            commands,
+            theme,
+            settings,
+            entry_entity,
+            entry,
+            drum_entry,
+        ),
+        ProtoEntry::Extension(extension_entry) => ExtensionPlugin::insert_entry_extra(
+            commands,
+            assets,
+            theme,
+            settings,
+            entry_entity,
+            entry,
+            extension_entry,
+        ),
         _ => {}
     }
 }
@@ -142,6 +163,27 @@ fn insert_core_entry_extra(
                 .entity(entity)
                 .insert(ChordBundle::from(*chord));
         }
+        CoreEntry::Ornament(ornament) => match lane_kind {
+            LaneKind::Melody | LaneKind::Harmony => {
+                crate::ornament::ornament_systems::create_tone_ornament_notes(
+                    commands, theme, lane_kind.into(), entity, entry, ornament,
+                );
+            }
+            LaneKind::Strings => match entry.track_kind() {
+                TrackKind::Guitar => {
+                    crate::ornament::ornament_systems::create_pick_ornament_notes6(
+                        commands, theme, entity, entry, ornament,
+                    );
+                }
+                TrackKind::Bass | TrackKind::Ukulele | TrackKind::Mandolin => {
+                    crate::ornament::ornament_systems::create_pick_ornament_notes4(
+                        commands, theme, entity, entry, ornament,
+                    );
+                }
+                _ => (),
+            },
+            _ => (),
+        },
     };
 }
 
@@ -153,10 +195,14 @@ fn on_tab_bars_resized(
     mut tone_note_query: Query<(Entity, &mut ToneNoteData), With<ToneNoteData>>,
     mut tone_line_query: Query<(Entity, &mut ToneLineData), With<ToneLineData>>,
     mut pick_note_query: Query<(Entity, &mut PickNoteData), With<PickNoteData>>,
+    mut ornament_note_query: Query<(Entity, &mut OrnamentNoteData), With<OrnamentNoteData>>,
     mut single_string_query: Query<(Entity, &mut SingleStringData), With<SingleStringData>>,
     mut word_text_query: Query<(Entity, &mut WordTextData), With<WordTextData>>,
     mut shape_diagram_6_query: Query<(Entity, &mut ShapeDiagramData6), With<ShapeDiagramData6>>,
     mut shape_diagram_4_query: Query<(Entity, &mut ShapeDiagramData4), With<ShapeDiagramData4>>,
+    mut drum_row_query: Query<(Entity, &mut DrumRowData), With<DrumRowData>>,
+    mut drum_hit_query: Query<(Entity, &mut DrumHitData), With<DrumHitData>>,
+    mut extension_text_query: Query<(Entity, &mut ExtensionTextData), With<ExtensionTextData>>,
 ) {
     if theme._bypass_systems {
         return;
@@ -179,6 +225,14 @@ fn on_tab_bars_resized(
                 }
             }
         }
+        for (entity, mut data) in ornament_note_query.iter_mut() {
+            for (view, layout) in bars.iter() {
+                if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                    data.value.bar_size = layout.size.width;
+                    data.update(&mut commands, &theme, entity);
+                }
+            }
+        }
         for (entity, mut data) in word_text_query.iter_mut() {
             for (view, layout) in bars.iter() {
                 if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
@@ -223,5 +277,33 @@ fn on_tab_bars_resized(
                 }
             }
         }
+        if !settings.hide_drums_lane {
+            for (entity, mut data) in drum_row_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
+            for (entity, mut data) in drum_hit_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
+        }
+        if !settings.hide_extension_lane {
+            for (entity, mut data) in extension_text_query.iter_mut() {
+                for (view, layout) in bars.iter() {
+                    if data.bar_props.bar_ordinal == view.bar_props.bar_ordinal {
+                        data.value.bar_size = layout.size.width;
+                        data.update(&mut commands, &theme, entity);
+                    }
+                }
+            }
+        }
     }
 }
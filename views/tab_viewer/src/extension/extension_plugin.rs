@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use edger_bevy::bevy_prelude::*;
+use lazy_static::lazy_static;
+use edger_bevy::prelude::ShapeOp;
+
+use crate::prelude::{NotationAssets, NotationSettings, NotationTheme};
+use notation_model::prelude::{ExtensionEntry, ExtensionRegistry, ExtensionType, LaneEntry};
+
+use super::extension_text::{ExtensionText, ExtensionTextData, ExtensionTextValue};
+
+/// Spawns the views of an extension entry under the entry entity.
+pub type ExtensionRenderer = fn(
+    commands: &mut Commands,
+    assets: &NotationAssets,
+    theme: &NotationTheme,
+    settings: &NotationSettings,
+    entity: Entity,
+    entry: &LaneEntry,
+    extension_entry: &ExtensionEntry,
+);
+
+lazy_static! {
+    static ref RENDERERS: RwLock<HashMap<String, ExtensionRenderer>> =
+        RwLock::new(HashMap::new());
+}
+
+pub struct ExtensionPlugin;
+
+impl Plugin for ExtensionPlugin {
+    fn build(&self, _app: &mut App) {}
+}
+
+impl ExtensionPlugin {
+    /// Replace the default text view for entries of the extension type.
+    pub fn register_renderer<T: ExtensionType>(renderer: ExtensionRenderer) {
+        RENDERERS
+            .write()
+            .unwrap()
+            .insert(T::KIND.to_owned(), renderer);
+    }
+    pub fn get_renderer(kind: &str) -> Option<ExtensionRenderer> {
+        RENDERERS.read().unwrap().get(kind).copied()
+    }
+    /// Only registered kinds are shown, others are kept in the tab as they are.
+    pub fn insert_entry_extra(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        settings: &NotationSettings,
+        entity: Entity,
+        entry: &LaneEntry,
+        extension_entry: &ExtensionEntry,
+    ) {
+        if settings.hide_extension_lane || !ExtensionRegistry::is_registered(&extension_entry.kind) {
+            return;
+        }
+        let renderer = Self::get_renderer(&extension_entry.kind).unwrap_or(create_extension_text);
+        renderer(commands, assets, theme, settings, entity, entry, extension_entry);
+    }
+}
+
+/// Default view, the described payload at the position of the entry.
+pub fn create_extension_text(
+    commands: &mut Commands,
+    assets: &NotationAssets,
+    theme: &NotationTheme,
+    _settings: &NotationSettings,
+    entity: Entity,
+    entry: &LaneEntry,
+    extension_entry: &ExtensionEntry,
+) {
+    let value = ExtensionTextValue::new(ExtensionRegistry::describe(extension_entry));
+    commands
+        .entity(entity)
+        .insert(ExtensionText::from(value.clone()));
+    let data = ExtensionTextData::new(entry, value);
+    let text_entity = data.create(commands, theme, entity);
+    theme
+        .texts
+        .extension
+        .spawn_text(commands, text_entity, assets, data.value.text.as_str());
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use std::fmt::Display;
+
+    use super::*;
+
+    #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+    struct Cue(String);
+    impl Display for Cue {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+    impl ExtensionType for Cue {
+        const KIND: &'static str = "tab_viewer::test::Cue";
+    }
+
+    fn render_nothing(
+        _commands: &mut Commands,
+        _assets: &NotationAssets,
+        _theme: &NotationTheme,
+        _settings: &NotationSettings,
+        _entity: Entity,
+        _entry: &LaneEntry,
+        _extension_entry: &ExtensionEntry,
+    ) {
+    }
+
+    #[test]
+    fn test_register_renderer() {
+        assert!(ExtensionPlugin::get_renderer(Cue::KIND).is_none());
+        ExtensionPlugin::register_renderer::<Cue>(render_nothing);
+        assert!(ExtensionPlugin::get_renderer(Cue::KIND).is_some());
+        assert!(ExtensionPlugin::get_renderer("tab_viewer::test::Unknown").is_none());
+    }
+}
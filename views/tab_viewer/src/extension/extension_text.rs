@@ -0,0 +1,49 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::{offscreen, ShapeOp, StrokeLine};
+
+use std::fmt::Display;
+
+use crate::prelude::{EntryData, NotationTheme, SingleBundle};
+
+#[derive(Clone, Debug, Component)]
+pub struct ExtensionTextValue {
+    pub text: String,
+    pub bar_size: f32,
+}
+impl ExtensionTextValue {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            bar_size: 0.0,
+        }
+    }
+}
+impl Display for ExtensionTextValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+pub type ExtensionText = SingleBundle<ExtensionTextValue>;
+
+pub type ExtensionTextData = EntryData<ExtensionTextValue>;
+
+impl ShapeOp<NotationTheme, StrokeLine> for ExtensionTextData {
+    fn get_shape(&self, theme: &NotationTheme) -> StrokeLine {
+        let height = theme.sizes.layout.extension_height;
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let x =
+                self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.in_bar_pos.to_f32();
+            Vec3::new(x, 0.0, theme.z.word)
+        };
+        StrokeLine {
+            from: Vec2::ZERO,
+            to: Vec2::new(0.0, -height),
+            line_width: 1.0,
+            color: theme.colors.extension.line,
+            offset,
+        }
+    }
+}
@@ -0,0 +1,2 @@
+pub mod extension_plugin;
+pub mod extension_text;
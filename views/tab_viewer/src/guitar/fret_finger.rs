@@ -190,6 +190,7 @@ impl FretFingerData {
                 assets,
                 settings,
                 &meta.scale, &key,
+                None,
                 &syllable,
                 size_scale,
             )
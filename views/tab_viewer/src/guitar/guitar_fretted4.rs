@@ -0,0 +1,41 @@
+use notation_model::prelude::{Fretboard4, Fretboard6, HandShape4, HandShape6, Pick, PickNote};
+
+/// Instruments with 4 strings (bass, ukulele, mandolin) are shown on the middle strings
+/// of the guitar view, these helpers map their fretboard, shapes and picks to 6 strings.
+pub struct GuitarFretted4();
+
+impl GuitarFretted4 {
+    pub const GUITAR_STRINGS: [u8; 4] = [2, 3, 4, 5];
+
+    pub fn to_guitar_string(string: u8) -> u8 {
+        string + 1
+    }
+    pub fn to_guitar_fretboard(fretboard: &Fretboard4) -> Fretboard6 {
+        let notes = fretboard.string_notes;
+        Fretboard6 {
+            total_fret_num: fretboard.total_fret_num,
+            string_notes: [notes[0], notes[0], notes[1], notes[2], notes[3], notes[3]],
+            capo: fretboard.capo,
+        }
+    }
+    pub fn to_guitar_shape(shape: &HandShape4) -> HandShape6 {
+        let frets = shape.frets;
+        let fingers = shape.fingers;
+        HandShape6 {
+            barre: shape.barre,
+            frets: [None, frets[0], frets[1], frets[2], frets[3], None],
+            fingers: [None, fingers[0], fingers[1], fingers[2], fingers[3], None],
+        }
+    }
+    pub fn to_guitar_pick(pick: &Pick) -> Pick {
+        Pick::from(
+            pick.get_notes()
+                .into_iter()
+                .map(|x| PickNote {
+                    string: Self::to_guitar_string(x.string),
+                    ..x
+                })
+                .collect::<Vec<PickNote>>(),
+        )
+    }
+}
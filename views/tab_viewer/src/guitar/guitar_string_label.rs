@@ -0,0 +1,57 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::prelude::LayoutSize;
+use notation_model::prelude::{Fretboard6, Pitch, Semitones};
+
+use crate::prelude::{NotationAssets, NotationTheme};
+
+/// Open string pitch shown above the nut, follows the tuning of the fretboard.
+#[derive(Clone, Debug, Component)]
+pub struct GuitarStringLabel {
+    pub string: u8,
+    pub note: Semitones,
+}
+
+impl GuitarStringLabel {
+    pub fn new(string: u8, fretboard: &Fretboard6) -> Option<Self> {
+        fretboard
+            .string_notes
+            .get(string as usize - 1)
+            .map(|note| Self { string, note: *note })
+    }
+    pub fn label(&self) -> String {
+        Pitch::from(self.note).to_text()
+    }
+    pub fn spawn(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        entity: Entity,
+        fretboard: &Fretboard6,
+        strings: &[u8],
+    ) {
+        for string in strings.iter() {
+            if let Some(label) = Self::new(*string, fretboard) {
+                let label_entity = theme.guitar.string_label_text.spawn_label_text(
+                    commands,
+                    entity,
+                    assets,
+                    label.label().as_str(),
+                );
+                commands.entity(label_entity).insert(label);
+            }
+        }
+    }
+    pub fn update_transform(
+        &self,
+        theme: &NotationTheme,
+        guitar_size: LayoutSize,
+        transform: &mut Transform,
+    ) {
+        let scale = theme.guitar.calc_scale(guitar_size.width);
+        let x = theme.guitar.calc_string_x(self.string, guitar_size.width);
+        let y = theme.guitar.calc_fret_y(0, guitar_size.height)
+            + theme.guitar.string_label_text.text_y * scale;
+        transform.translation = Vec3::new(x, y, theme.z.guitar_string + theme.guitar.string_label_text.text_z);
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+}
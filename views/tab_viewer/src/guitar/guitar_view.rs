@@ -2,13 +2,14 @@ use float_eq::float_ne;
 use std::fmt::Display;
 use std::sync::Arc;
 
+use edger_bevy::bevy::ecs::system::SystemParam;
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::prelude::{
     entity, offscreen, LayoutAnchor, LayoutChangedQuery, LayoutSize, ShapeOp, View, ViewBundle, SingleData,
 };
 use notation_model::prelude::{
-    Duration, Entry, HandShape6, Interval, LaneEntry, LaneKind, ModelEntryProps, Pick, Syllable,
-    Tab, TrackKind, Units,
+    Duration, Entry, Fretboard6, HandShape4, HandShape6, Interval, LaneEntry, LaneKind,
+    ModelEntryProps, Pick, Syllable, Tab, Track, TrackKind, Units, GUITAR_STRING_NUM,
 };
 
 use crate::chord::interval_dot::DotQuery;
@@ -18,18 +19,86 @@ use crate::prelude::NotationLayout;
 use super::fret_finger::FretFingerData;
 use super::guitar_barre::GuitarBarreData;
 use super::guitar_capo::GuitarCapoData;
+use super::guitar_fretted4::GuitarFretted4;
 use super::guitar_string::GuitarStringData;
+use super::guitar_string_label::GuitarStringLabel;
 
 #[cfg(feature = "midi")]
 use notation_midi::prelude::MidiState;
 
+pub type ChangedShapeQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (&'static SingleData<LaneEntry>, &'static SingleData<T>, &'static EntryPlaying),
+    Changed<EntryPlaying>,
+>;
+
+/// Parts of the guitar view updated when the current hand shape changes.
+#[derive(SystemParam)]
+pub struct HandShapeQueries<'w, 's> {
+    finger_query: Query<'w, 's, (Entity, &'static mut FretFingerData), With<FretFingerData>>,
+    string_query: Query<'w, 's, (Entity, &'static mut GuitarStringData), With<GuitarStringData>>,
+    capo_query: Query<'w, 's, (Entity, &'static mut GuitarCapoData), With<GuitarCapoData>>,
+    barre_query: Query<'w, 's, (Entity, &'static mut GuitarBarreData), With<GuitarBarreData>>,
+    dot_query: DotQuery<'w, 's, 'static, 'static>,
+    text_query: Query<'w, 's, (&'static Parent, Entity), With<Text>>,
+    tab_state_query: Query<'w, 's, (Entity, &'static TabState), With<TabState>>,
+}
+
 #[derive(Clone, Debug, Component)]
 pub struct GuitarView {
     pub tab: Arc<Tab>,
+    pub track_id: Option<String>,
+    pub string_num: usize,
 }
 impl GuitarView {
     pub fn new(tab: Arc<Tab>) -> Self {
-        Self { tab }
+        let track = Self::get_fretted_track(&tab);
+        let track_id = track.as_ref().map(|x| x.id.clone());
+        let string_num = track
+            .and_then(|x| x.kind.string_num())
+            .unwrap_or(GUITAR_STRING_NUM);
+        Self {
+            tab,
+            track_id,
+            string_num,
+        }
+    }
+    /// Guitar track is preferred, otherwise the first track of other fretted instruments.
+    pub fn get_fretted_track(tab: &Tab) -> Option<Arc<Track>> {
+        tab.get_track_of_kind(TrackKind::Guitar).or_else(|| {
+            tab.tracks
+                .iter()
+                .find(|x| x.kind.is_fretted())
+                .cloned()
+        })
+    }
+    /// Fretboard of the track, for instruments with 4 strings, it's mapped to the guitar one.
+    pub fn get_fretboard(track: &Track) -> Option<Fretboard6> {
+        match track.kind.string_num() {
+            Some(4) => track
+                .get_fretboard4()
+                .map(|x| GuitarFretted4::to_guitar_fretboard(&x)),
+            _ => track.get_fretboard6(),
+        }
+    }
+    pub fn guitar_strings(&self) -> Vec<u8> {
+        match self.string_num {
+            4 => GuitarFretted4::GUITAR_STRINGS.to_vec(),
+            _ => (1..=GUITAR_STRING_NUM as u8).collect(),
+        }
+    }
+    pub fn is_track_entry(&self, entry: &LaneEntry) -> bool {
+        match (&self.track_id, entry.track()) {
+            (Some(track_id), Some(track)) => track.id == *track_id,
+            _ => false,
+        }
+    }
+    pub fn to_guitar_pick(&self, pick: &Pick) -> Pick {
+        match self.string_num {
+            4 => GuitarFretted4::to_guitar_pick(pick),
+            _ => *pick,
+        }
     }
 }
 impl Display for GuitarView {
@@ -53,10 +122,13 @@ impl GuitarView {
         entity: Entity,
         tab: &Arc<Tab>,
     ) -> Entity {
+        let view = GuitarView::new(tab.clone());
+        let guitar_strings = view.guitar_strings();
+        let fretboard = GuitarView::get_fretted_track(tab).and_then(|x| GuitarView::get_fretboard(&x));
         let guitar_entity = entity::spawn_child_bundle(
             commands,
             entity,
-            ViewBundle::from(GuitarView::new(tab.clone())),
+            ViewBundle::from(view),
         );
         let sprite_bundle = SpriteBundle {
             sprite: Sprite{
@@ -70,17 +142,16 @@ impl GuitarView {
             texture: assets.fretboard.clone(),
             ..Default::default()
         };
-        let fretboard = tab
-            .get_track_of_kind(TrackKind::Guitar)
-            .and_then(|x| x.get_fretboard6());
-
         entity::spawn_child_bundle(commands, guitar_entity, sprite_bundle);
-        for string in 1..=6 {
+        for string in guitar_strings.iter() {
             for upper in [true, false] {
-                let string_data = GuitarStringData::new(string as u8, upper, fretboard);
+                let string_data = GuitarStringData::new(*string, upper, fretboard);
                 string_data.create(commands, theme, guitar_entity);
             }
         }
+        if let Some(fretboard) = fretboard.as_ref() {
+            GuitarStringLabel::spawn(commands, assets, theme, guitar_entity, fretboard, &guitar_strings);
+        }
         let capo_data = GuitarCapoData::default();
         capo_data.create(commands, theme, guitar_entity);
         let barre_data = GuitarBarreData::default();
@@ -92,7 +163,7 @@ impl GuitarView {
                 let mut finger_data = FretFingerData::new_data(
                     ModelEntryProps {
                         index: 0,
-                        tied_units: Units(0.0),
+                        tied_units: Units::ZERO,
                     },
                     Syllable::Do,
                     Interval::Unison,
@@ -110,16 +181,16 @@ impl GuitarView {
                 fret = fret + 1;
             }
         } else {
-            for index in 1..=6 {
+            for index in guitar_strings.iter() {
                 for pick in [true, false] {
                     let finger_data = FretFingerData::new_data(
                         ModelEntryProps {
                             index: 0,
-                            tied_units: Units(0.0),
+                            tied_units: Units::ZERO,
                         },
                         Syllable::Do,
                         Interval::Unison,
-                        index as u8,
+                        *index,
                         pick,
                         None,
                         None,
@@ -139,6 +210,7 @@ impl GuitarView {
         mut capo_query: Query<(&Parent, Entity, &mut GuitarCapoData), With<GuitarCapoData>>,
         mut barre_query: Query<(&Parent, Entity, &mut GuitarBarreData), With<GuitarBarreData>>,
         mut finger_query: Query<(&Parent, Entity, &mut FretFingerData), With<FretFingerData>>,
+        mut label_query: Query<(&Parent, &GuitarStringLabel, &mut Transform), Without<Sprite>>,
     ) {
         if theme._bypass_systems {
             return;
@@ -160,6 +232,11 @@ impl GuitarView {
                     string_data.update(&mut commands, &theme, string_entity);
                 }
             }
+            for (parent, label, mut transform) in label_query.iter_mut() {
+                if parent.get() == entity {
+                    label.update_transform(&theme, guitar_size, &mut transform);
+                }
+            }
             for (parent, finger_entity, mut finger_data) in finger_query.iter_mut() {
                 if parent.get() == entity {
                     finger_data.value.extra.guitar_size = guitar_size;
@@ -190,6 +267,7 @@ impl GuitarView {
         midi_state: Res<MidiState>,
         time: Res<Time>,
         theme: Res<NotationTheme>,
+        view_query: Query<&GuitarView>,
         query: Query<(&SingleData<LaneEntry>, &SingleData<Pick>, &EntryPlaying), Changed<EntryPlaying>>,
         mut string_query: Query<(Entity, &mut GuitarStringData), With<GuitarStringData>>,
         mut finger_query: Query<(Entity, &mut FretFingerData), With<FretFingerData>>,
@@ -206,14 +284,21 @@ impl GuitarView {
         if settings.hide_guitar_view {
             return;
         }
+        let Ok(view) = view_query.get_single() else {
+            return;
+        };
         let mut current_entry_pick = None;
         let mut string_states = [None; 6];
         let mut hit_strings = [(false, Duration::Zero); 6];
         for (entry, pick, playing) in query.iter() {
+            if !view.is_track_entry(&entry.0) {
+                continue;
+            }
+            let pick = view.to_guitar_pick(&pick.0);
             if playing.value.is_current() {
-                current_entry_pick = Some((entry.0.clone(), pick.0));
+                current_entry_pick = Some((entry.0.clone(), pick));
             }
-            for pick_note in pick.0.get_notes() {
+            for pick_note in pick.get_notes() {
                 if pick_note.string >= 1 && pick_note.string <= 6 {
                     string_states[(pick_note.string - 1) as usize] = Some(playing.value);
                     hit_strings[(pick_note.string - 1) as usize] =
@@ -222,7 +307,7 @@ impl GuitarView {
             }
         }
         let fretboard = current_entry_pick.clone()
-            .and_then(|(entry, _)| entry.track().and_then(|x| x.get_fretboard6()));
+            .and_then(|(entry, _)| entry.track().and_then(|x| Self::get_fretboard(&x)));
         let meta = current_entry_pick.clone().and_then(|(entry, _)| entry.bar().map(|x| x.tab_meta()));
 
         for (string_entity, mut string_data) in string_query.iter_mut() {
@@ -279,14 +364,9 @@ impl GuitarView {
         assets: Res<NotationAssets>,
         theme: Res<NotationTheme>,
         settings: Res<NotationSettings>,
-        query: Query<(&SingleData<LaneEntry>, &SingleData<HandShape6>, &EntryPlaying), Changed<EntryPlaying>>,
-        mut finger_query: Query<(Entity, &mut FretFingerData), With<FretFingerData>>,
-        mut string_query: Query<(Entity, &mut GuitarStringData), With<GuitarStringData>>,
-        mut capo_query: Query<(Entity, &mut GuitarCapoData), With<GuitarCapoData>>,
-        mut barre_query: Query<(Entity, &mut GuitarBarreData), With<GuitarBarreData>>,
-        mut dot_query: DotQuery,
-        text_query: Query<(&Parent, Entity), With<Text>>,
-        tab_state_query: Query<(Entity, &TabState), With<TabState>>,
+        view_query: Query<&GuitarView>,
+        query: ChangedShapeQuery<HandShape6>,
+        mut queries: HandShapeQueries,
     ) {
         if Self::CHECKING_FRETS {
             return;
@@ -297,78 +377,149 @@ impl GuitarView {
         if settings.hide_guitar_view {
             return;
         }
+        let Ok(view) = view_query.get_single() else {
+            return;
+        };
         let mut current_shape = None;
         for (entry, shape, playing) in query.iter() {
-            if playing.value.is_current() {
+            if playing.value.is_current() && view.is_track_entry(&entry.0) {
                 //println!("GuitarView::update_hand_shape6(): found changed playing shape: {}", shape);
-                current_shape = Some((entry.0.clone(), shape.0));
+                let pick = entry.0.bar().and_then(|x| {
+                    x.get_entry_in_other_lane(
+                        LaneKind::Strings,
+                        entry.0.track_index(),
+                        Some(entry.0.in_bar_pos()),
+                        &|x: &LaneEntry| {
+                            x.proto()
+                                .as_fretted6()
+                                .and_then(|y| y.as_pick())
+                                .map(|z| z.to_owned())
+                        },
+                    )
+                });
+                current_shape = Some((entry.0.clone(), shape.0, pick));
+            }
+        }
+        Self::update_hand_shape(
+            &mut commands,
+            &assets,
+            &theme,
+            &settings,
+            current_shape,
+            &mut queries,
+        );
+    }
+    pub fn update_hand_shape4(
+        mut commands: Commands,
+        assets: Res<NotationAssets>,
+        theme: Res<NotationTheme>,
+        settings: Res<NotationSettings>,
+        view_query: Query<&GuitarView>,
+        query: ChangedShapeQuery<HandShape4>,
+        mut queries: HandShapeQueries,
+    ) {
+        if Self::CHECKING_FRETS {
+            return;
+        }
+        if theme._bypass_systems {
+            return;
+        }
+        if settings.hide_guitar_view {
+            return;
+        }
+        let Ok(view) = view_query.get_single() else {
+            return;
+        };
+        if view.string_num != 4 {
+            return;
+        }
+        let mut current_shape = None;
+        for (entry, shape, playing) in query.iter() {
+            if playing.value.is_current() && view.is_track_entry(&entry.0) {
+                let pick = entry.0.bar().and_then(|x| {
+                    x.get_entry_in_other_lane(
+                        LaneKind::Strings,
+                        entry.0.track_index(),
+                        Some(entry.0.in_bar_pos()),
+                        &|x: &LaneEntry| {
+                            x.proto()
+                                .as_fretted4()
+                                .and_then(|y| y.as_pick())
+                                .map(GuitarFretted4::to_guitar_pick)
+                        },
+                    )
+                });
+                current_shape = Some((entry.0.clone(), GuitarFretted4::to_guitar_shape(&shape.0), pick));
             }
         }
-        if let Some((entry, shape)) = current_shape {
-            let fretboard = entry.track().and_then(|x| x.get_fretboard6());
+        Self::update_hand_shape(
+            &mut commands,
+            &assets,
+            &theme,
+            &settings,
+            current_shape,
+            &mut queries,
+        );
+    }
+    fn update_hand_shape(
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        theme: &NotationTheme,
+        settings: &NotationSettings,
+        current_shape: Option<(LaneEntry, HandShape6, Option<Pick>)>,
+        queries: &mut HandShapeQueries,
+    ) {
+        if let Some((entry, shape, pick)) = current_shape {
+            let fretboard = entry.track().and_then(|x| Self::get_fretboard(&x));
             let chord = entry.bar().and_then(|x| x.get_chord_of_entry(&entry));
-            let pick = entry.bar().and_then(|x| {
-                x.get_entry_in_other_lane(
-                    LaneKind::Strings,
-                    entry.track_index(),
-                    Some(entry.in_bar_pos()),
-                    &|x: &LaneEntry| {
-                        x.proto()
-                            .as_fretted6()
-                            .and_then(|y| y.as_pick())
-                            .map(|z| z.to_owned())
-                    },
-                )
-            });
             let meta = entry.bar().map(|x| x.tab_meta());
-            //println!("GuitarView::update_hand_shape6(): {}, {:#?}, {:#?}", shape, fretboard, chord);
-            for (finger_entity, mut finger_data) in finger_query.iter_mut() {
+            for (finger_entity, mut finger_data) in queries.finger_query.iter_mut() {
                 finger_data.update_value(&shape, fretboard, chord, pick, meta.clone());
-                finger_data.respawn_dots(&mut commands, &theme, Some(&mut dot_query), finger_entity);
+                finger_data.respawn_dots(commands, theme, Some(&mut queries.dot_query), finger_entity);
                 finger_data.update_with_syllable(
-                    &mut commands,
-                    &assets,
-                    &theme,
-                    &settings,
-                    &text_query,
+                    commands,
+                    assets,
+                    theme,
+                    settings,
+                    &queries.text_query,
                     finger_entity,
                     &meta.clone().unwrap_or_default(),
                 );
             }
-            for (string_entity, mut string_data) in string_query.iter_mut() {
+            for (string_entity, mut string_data) in queries.string_query.iter_mut() {
                 string_data.update_value(&shape, fretboard, pick, meta.clone());
-                string_data.update(&mut commands, &theme, string_entity);
+                string_data.update(commands, theme, string_entity);
             }
             if let Some(fretboard) = fretboard {
-                for (capo_entity, mut capo_data) in capo_query.iter_mut() {
+                for (capo_entity, mut capo_data) in queries.capo_query.iter_mut() {
                     if fretboard.capo != capo_data.capo {
                         capo_data.capo = fretboard.capo;
-                        capo_data.update(&mut commands, &theme, capo_entity);
+                        capo_data.update(commands, theme, capo_entity);
                     }
                 }
-                for (barre_entity, mut barre_data) in barre_query.iter_mut() {
+                for (barre_entity, mut barre_data) in queries.barre_query.iter_mut() {
                     barre_data.capo = fretboard.capo;
                     barre_data.shape = Some(shape.clone());
                     barre_data.pick = None;
-                    barre_data.update(&mut commands, &theme, barre_entity);
+                    barre_data.update(commands, theme, barre_entity);
                 }
             }
         } else {
-            let position = TabState::get_position(&tab_state_query, None);
+            let position = TabState::get_position(&queries.tab_state_query, None);
             if settings.add_ready_section && position.is_some() && position.unwrap().bar.bar_ordinal == 0 {
-                for (finger_entity, mut finger_data) in finger_query.iter_mut() {
+                for (finger_entity, mut finger_data) in queries.finger_query.iter_mut() {
                     finger_data.reset();
-                    finger_data.update(&mut commands, &theme, finger_entity);
+                    finger_data.update(commands, theme, finger_entity);
                 }
-                for (string_entity, mut string_data) in string_query.iter_mut() {
+                for (string_entity, mut string_data) in queries.string_query.iter_mut() {
                     string_data.reset();
-                    string_data.update(&mut commands, &theme, string_entity);
+                    string_data.update(commands, theme, string_entity);
                 }
-                for (barre_entity, mut barre_data) in barre_query.iter_mut() {
+                for (barre_entity, mut barre_data) in queries.barre_query.iter_mut() {
                     if barre_data.shape.is_some() {
                         barre_data.shape = None;
                         barre_data.pick = None;
-                        barre_data.update(&mut commands, &theme, barre_entity);
+                        barre_data.update(commands, theme, barre_entity);
                     }
                 }
             }
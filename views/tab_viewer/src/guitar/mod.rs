@@ -1,5 +1,7 @@
 pub mod fret_finger;
 pub mod guitar_barre;
 pub mod guitar_capo;
+pub mod guitar_fretted4;
 pub mod guitar_string;
+pub mod guitar_string_label;
 pub mod guitar_view;
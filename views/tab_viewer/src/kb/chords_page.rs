@@ -62,7 +62,7 @@ impl KbPage for ChordsPage {
                     );
                     index += 1;
                 }
-                for _ in index..=3 {
+                for _ in index..=5 {
                     ui.label("");
                     ui.label("");
                 }
@@ -84,17 +84,17 @@ impl KbPage for ChordsPage {
                 for interval in chord.chord.intervals.get_intervals().iter() {
                     PageHelper::add_interval(ui, theme, interval, true, false);
                     let syllable = interval.syllable_on_root(&chord.chord.root);
-                    PageHelper::add_syllable_pitch(ui, theme, &scale, &key, &syllable, false);
+                    PageHelper::add_chord_syllable_pitch(ui, theme, &scale, &key, &chord.chord, &syllable, false);
                     index += 1;
                 }
-                for _ in index..=3 {
+                for _ in index..=5 {
                     ui.label("");
                     ui.label("");
                 }
                 if let Some(bass) = chord.chord.bass {
                     ui.label("bass");
                     let syllable = bass.syllable_on_root(&chord.chord.root);
-                    PageHelper::add_syllable_pitch(ui, theme, &scale, &key, &syllable, true);
+                    PageHelper::add_chord_syllable_pitch(ui, theme, &scale, &key, &chord.chord, &syllable, true);
                 }
                 ui.end_row();
             }
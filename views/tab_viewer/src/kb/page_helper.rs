@@ -1,7 +1,7 @@
 use edger_bevy::egui::color_picker::show_color;
 use edger_bevy::egui::{Ui};
 use edger_bevy::prelude::{color_to_hsva, label_from_style, EasyMarkStyle};
-use notation_model::prelude::{Interval, Key, Scale, Semitones, Syllable};
+use notation_model::prelude::{Chord, Interval, Key, Scale, Semitones, Syllable};
 
 use crate::prelude::NotationTheme;
 
@@ -65,6 +65,19 @@ impl PageHelper {
         let text = pitch.to_string();
         Self::add_maybe_strong_text(ui, strong, &text);
     }
+    pub fn add_chord_syllable_pitch(
+        ui: &mut Ui,
+        _theme: &NotationTheme,
+        scale: &Scale,
+        key: &Key,
+        chord: &Chord,
+        syllable: &Syllable,
+        strong: bool,
+    ) {
+        let pitch = scale.calc_pitch_in_chord(&key, &syllable, &chord);
+        let text = pitch.to_string();
+        Self::add_maybe_strong_text(ui, strong, &text);
+    }
     pub fn add_syllable_pitch_with_transpose(
         ui: &mut Ui,
         theme: &NotationTheme,
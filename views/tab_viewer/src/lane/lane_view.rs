@@ -1,5 +1,6 @@
 use edger_bevy::bevy_prelude::*;
 
+use crate::drums::drums_plugin::DrumsPlugin;
 use crate::entry::entry_plugin;
 use crate::harmony::harmony_plugin::HarmonyPlugin;
 use crate::lyrics::lyrics_plugin::LyricsPlugin;
@@ -54,12 +55,14 @@ impl LaneView {
                         LaneKind::Lyrics => ThemeColors::hex_linear("0000FF33"),
                         LaneKind::Melody => ThemeColors::hex_linear("00FFFF33"),
                         LaneKind::Harmony => ThemeColors::hex_linear("FF00FF33"),
+                        LaneKind::Drums => ThemeColors::hex_linear("FFFF0033"),
                         _ => ThemeColors::hex_linear("00000033"),
                     };
                     ColorBackground::spawn(commands, lane_entity, 30.0, color);
                 } else {
                     if let Some(color) = match lane_layout.lane_kind {
                         LaneKind::Strings => Some(theme.colors.strings.background),
+                        LaneKind::Drums => Some(theme.colors.drums.background),
                         _ => None,
                     } {
                         ColorBackground::spawn(commands, lane_entity, 0.0, color);
@@ -120,6 +123,13 @@ impl LaneView {
                 }
                 true
             }
+            LaneKind::Drums => {
+                if !settings.hide_drums_lane {
+                    DrumsPlugin::insert_lane_extra(&mut commands.entity(lane_entity), lane)
+                }
+                !settings.hide_drums_lane
+            }
+            LaneKind::Extension => !settings.hide_extension_lane,
             _ => false,
         }
     }
@@ -22,9 +22,12 @@ pub mod mini;
 pub mod play;
 pub mod tab;
 
+pub mod drums;
+pub mod extension;
 pub mod guitar;
 pub mod lyrics;
 pub mod melody;
+pub mod ornament;
 pub mod harmony;
 pub mod rhythm;
 pub mod shapes;
@@ -76,6 +79,12 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::data::model_entry_data::ModelEntryData;
     #[doc(hidden)]
+    pub use crate::drums::drums_grid::DrumsGrid;
+    #[doc(hidden)]
+    pub use crate::drums::drums_plugin::DrumsPlugin;
+    #[doc(hidden)]
+    pub use crate::extension::extension_plugin::{ExtensionPlugin, ExtensionRenderer};
+    #[doc(hidden)]
     pub use crate::entry::entry_bundle::EntryBundle;
     #[doc(hidden)]
     pub use crate::entry::entry_playing::EntryPlaying;
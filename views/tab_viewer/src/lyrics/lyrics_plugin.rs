@@ -26,12 +26,15 @@ impl LyricsPlugin {
         entry: &LaneEntry,
         lyric_entry: &LyricEntry,
     ) {
-        match lyric_entry {
-            LyricEntry::Word(word, _duration) => {
-                let value = WordTextValue::new(word.clone());
-                commands
-                    .entity(entity)
-                    .insert(WordText::from(value.clone()));
+        if let LyricEntry::Word(word, _duration) = lyric_entry {
+            commands
+                .entity(entity)
+                .insert(WordText::from(WordTextValue::new(word.clone())));
+        }
+        let rows = &theme.sizes.lyrics.visible_rows;
+        for line in lyric_entry.get_lines().iter() {
+            if let Some(row) = rows.iter().position(|x| *x == line.row()) {
+                let value = WordTextValue::new_line(line, row);
                 crate::word::word_systems::create_word_text(
                     commands, assets, theme, settings, entity, entry, &value,
                 );
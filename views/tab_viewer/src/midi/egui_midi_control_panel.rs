@@ -3,7 +3,7 @@ use notation_midi::prelude::{MidiSettings, MidiState};
 use edger_bevy::egui::{CollapsingHeader, Slider, Ui};
 use float_eq::float_ne;
 
-use crate::prelude::{NotationSettings, PlayControlEvent, Control, EguiControlPanel, NotationState, NotationTheme, Octave};
+use crate::prelude::{NotationSettings, PlayControlEvent, Control, EguiControlPanel, NotationState, NotationTheme, Octave, TrackKind};
 use super::midi_control::MidiControl;
 
 impl EguiControlPanel {
@@ -111,6 +111,13 @@ impl EguiControlPanel {
                 }
             });
     }
+    fn has_track(state: &NotationState, kind: TrackKind) -> bool {
+        state
+            .tab
+            .as_ref()
+            .and_then(|tab| tab.get_track_of_kind(kind))
+            .is_some()
+    }
     pub fn midi_settings_ui(
         ui: &mut Ui,
         state: &mut NotationState,
@@ -164,6 +171,38 @@ impl EguiControlPanel {
                             Slider::new(&mut midi_settings.guitar_velocity, 0..=127).text("Guitar"),
                         );
                     });
+                    if Self::has_track(state, TrackKind::Bass) {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut midi_settings.bass_mute, "Mute");
+                            ui.add(
+                                Slider::new(&mut midi_settings.bass_velocity, 0..=127).text("Bass"),
+                            );
+                        });
+                    }
+                    if Self::has_track(state, TrackKind::Ukulele) {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut midi_settings.ukulele_mute, "Mute");
+                            ui.add(
+                                Slider::new(&mut midi_settings.ukulele_velocity, 0..=127).text("Ukulele"),
+                            );
+                        });
+                    }
+                    if Self::has_track(state, TrackKind::Mandolin) {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut midi_settings.mandolin_mute, "Mute");
+                            ui.add(
+                                Slider::new(&mut midi_settings.mandolin_velocity, 0..=127).text("Mandolin"),
+                            );
+                        });
+                    }
+                    if Self::has_track(state, TrackKind::Drums) {
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut midi_settings.drums_mute, "Mute");
+                            ui.add(
+                                Slider::new(&mut midi_settings.drums_velocity, 0..=127).text("Drums"),
+                            );
+                        });
+                    }
                     if ui.button("Reset Audio").clicked() {
                         let default = MidiSettings::default();
                         midi_settings.click_mute = default.click_mute;
@@ -172,6 +211,14 @@ impl EguiControlPanel {
                         midi_settings.vocal_velocity = default.vocal_velocity;
                         midi_settings.guitar_mute = default.guitar_mute;
                         midi_settings.guitar_velocity = default.guitar_velocity;
+                        midi_settings.bass_mute = default.bass_mute;
+                        midi_settings.bass_velocity = default.bass_velocity;
+                        midi_settings.ukulele_mute = default.ukulele_mute;
+                        midi_settings.ukulele_velocity = default.ukulele_velocity;
+                        midi_settings.mandolin_mute = default.mandolin_mute;
+                        midi_settings.mandolin_velocity = default.mandolin_velocity;
+                        midi_settings.drums_mute = default.drums_mute;
+                        midi_settings.drums_velocity = default.drums_velocity;
                     }
                 }
             });
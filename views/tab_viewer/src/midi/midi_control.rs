@@ -101,7 +101,7 @@ impl MidiControl {
         if midi_state.play_control.stop() {
             midi_state.play_control.position.bar.bar_ordinal =
                 midi_state.play_control.begin_bar_ordinal;
-            midi_state.play_control.position.bar.in_bar_pos = Units(0.0);
+            midi_state.play_control.position.bar.in_bar_pos = Units::ZERO;
             Self::send_play_state_evt(midi_state, play_control_evts);
         }
     }
@@ -147,7 +147,7 @@ impl MidiControl {
         jump_to_bar_evts: &mut EventWriter<JumpToBarEvent>,
     ) {
         Self::jump_to_bar(midi_state, jump_to_bar_evts, &|tab, pos| {
-            if pos.in_bar_pos.0 > 0.0 {
+            if pos.in_bar_pos > Units::ZERO {
                 tab.get_bar_of_ordinal(pos.bar_ordinal)
             } else if pos.bar_ordinal > 0 {
                 tab.get_bar_of_ordinal(pos.bar_ordinal - 1)
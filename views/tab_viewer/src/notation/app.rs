@@ -29,6 +29,8 @@ impl PluginGroup for NotationPlugins {
             .add(HarmonyPlugin)
             .add(StringsPlugin)
             .add(ShapesPlugin)
+            .add(DrumsPlugin)
+            .add(ExtensionPlugin)
             .add(MiniPlugin)
             .add(TabPlugin)
             .add(PlayPlugin)
@@ -158,8 +160,12 @@ impl NotationApp {
                 if let Some(tab_asset) = load_tab(commands, state.tab_path.clone()) {
                     match tab_asset.tab {
                         Ok(tab) => {
-                            match Tab::try_parse_arc(tab, settings.add_ready_section, state.bars_range) {
-                                Ok(tab) => {
+                            match Tab::try_parse_arc(tab, settings.add_ready_section, state.bars_range, false) {
+                                Ok((tab, report)) => {
+                                    for problem in report.problems.iter() {
+                                        warn!("load_tab(): Parse Problem: {}", problem);
+                                    }
+                                    state.parse_problems = report.problems;
                                     state.tab = Some(tab.clone());
                                     if let Ok(mut window) = window_query.get_single_mut() {
                                         let title = format!("{} - {}", NotationApp::TITLE, state.tab_path);
@@ -188,12 +188,41 @@ impl EguiControlPanel {
                     settings.hide_lyrics_lane = hide_lyrics_lane;
                     Control::reload_tab(state, theme);
                 }
+                let lyric_langs = state
+                    .tab
+                    .as_ref()
+                    .map(|x| x.get_lyric_langs())
+                    .unwrap_or_default();
+                if !settings.hide_lyrics_lane && lyric_langs.len() > 1 {
+                    for lang in lyric_langs.iter() {
+                        let hidden = !settings.is_lyric_lang_visible(lang);
+                        let mut hide_lang = hidden;
+                        let label = if lang.is_empty() { "-" } else { lang.as_str() };
+                        ui.checkbox(&mut hide_lang, format!("Hide Lyrics ({})", label));
+                        if hide_lang != hidden {
+                            settings.set_lyric_lang_visible(lang, !hide_lang);
+                            Control::reload_tab(state, theme);
+                        }
+                    }
+                }
                 let mut hide_melody_lane = settings.hide_melody_lane;
                 ui.checkbox(&mut hide_melody_lane, "Hide Melody");
                 if settings.hide_melody_lane != hide_melody_lane {
                     settings.hide_melody_lane = hide_melody_lane;
                     Control::reload_tab(state, theme);
                 }
+                let mut hide_drums_lane = settings.hide_drums_lane;
+                ui.checkbox(&mut hide_drums_lane, "Hide Drums");
+                if settings.hide_drums_lane != hide_drums_lane {
+                    settings.hide_drums_lane = hide_drums_lane;
+                    Control::reload_tab(state, theme);
+                }
+                let mut hide_extension_lane = settings.hide_extension_lane;
+                ui.checkbox(&mut hide_extension_lane, "Hide Extensions");
+                if settings.hide_extension_lane != hide_extension_lane {
+                    settings.hide_extension_lane = hide_extension_lane;
+                    Control::reload_tab(state, theme);
+                }
                 ui.separator();
                 let show_note_pitch = settings.show_note_pitch;
                 ui.checkbox(
@@ -231,6 +260,11 @@ impl EguiControlPanel {
                 if always_show_fret != settings.always_show_fret {
                     Control::reload_tab(state, theme);
                 }
+                let show_pick_fingers = settings.show_pick_fingers;
+                ui.checkbox(&mut settings.show_pick_fingers, "Show Pick Fingers");
+                if show_pick_fingers != settings.show_pick_fingers {
+                    Control::reload_tab(state, theme);
+                }
                 ui.separator();
                 let mut hide_guitar_view = settings.hide_guitar_view;
                 ui.checkbox(&mut hide_guitar_view, "Hide Guitar View");
@@ -244,6 +278,24 @@ impl EguiControlPanel {
                     settings.hide_chords_view = hide_chords_view;
                     Control::reload_tab(state, theme);
                 }
+                let mut show_chord_symbol = settings.show_chord_symbol;
+                ui.checkbox(&mut show_chord_symbol, "Show Chord Symbols");
+                if settings.show_chord_symbol != show_chord_symbol {
+                    settings.show_chord_symbol = show_chord_symbol;
+                    Control::reload_tab(state, theme);
+                }
+                let mut show_chord_voicing = settings.show_chord_voicing;
+                ui.checkbox(&mut show_chord_voicing, "Show Chord Voicings");
+                if settings.show_chord_voicing != show_chord_voicing {
+                    settings.show_chord_voicing = show_chord_voicing;
+                    Control::reload_tab(state, theme);
+                }
+                let mut show_roman_numerals = settings.show_roman_numerals;
+                ui.checkbox(&mut show_roman_numerals, "Show Roman Numerals");
+                if settings.show_roman_numerals != show_roman_numerals {
+                    settings.show_roman_numerals = show_roman_numerals;
+                    Control::reload_tab(state, theme);
+                }
                 let mut hide_mini_map = settings.hide_mini_map;
                 ui.checkbox(&mut hide_mini_map, "Hide Mini Map");
                 if settings.hide_mini_map != hide_mini_map {
@@ -336,6 +388,16 @@ impl EguiControlPanel {
             }
             ui.separator();
         }
+        if !state.parse_problems.is_empty() {
+            CollapsingHeader::new(format!("Parse Problems ({})", state.parse_problems.len()))
+                .default_open(false)
+                .show(ui, |ui| {
+                    for problem in state.parse_problems.iter() {
+                        ui.label(problem.to_string());
+                    }
+                });
+            ui.separator();
+        }
         ui.horizontal(|ui| {
             if ui.button("Reload Tab").clicked() {
                 state.bars_range = None;
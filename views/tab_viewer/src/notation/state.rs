@@ -13,6 +13,8 @@ pub struct NotationState {
     pub show_kb: bool,
     pub preset: Option<String>,
     pub tab_error: Option<TabError>,
+    /// Problems skipped while parsing the loaded tab, see [`ParseReport`].
+    pub parse_problems: Vec<ParseError>,
     pub debug_str: Option<String>,
     pub _despawn_delay_seconds: f32,
     pub _load_tab_delay_seconds: f32,
@@ -34,6 +36,7 @@ impl NotationState {
 
             preset: None,
             tab_error: None,
+            parse_problems: vec![],
             debug_str: None,
             _despawn_delay_seconds: 0.0,
             _load_tab_delay_seconds: 0.0,
@@ -48,6 +51,7 @@ impl NotationState {
     pub fn reload_tab(&mut self) {
         self.tab = None;
         self.tab_error = None;
+        self.parse_problems.clear();
         self._despawn_delay_seconds = 0.1;
         self._load_tab_delay_seconds = 0.2;
     }
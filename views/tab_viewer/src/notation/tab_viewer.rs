@@ -53,6 +53,8 @@ impl Plugin for TabViewerPlugin {
             GuitarView::on_layout_changed,
             GuitarView::update_hand_shape6
                     .in_set(GuitarViewLabel::UpdateHandShapes),
+            GuitarView::update_hand_shape4
+                    .in_set(GuitarViewLabel::UpdateHandShapes),
             GuitarView::update_string_state
                     .in_set(GuitarViewLabel::UpdateStringStates)
                     .after(GuitarViewLabel::UpdateHandShapes),
@@ -124,6 +126,7 @@ impl TabViewer {
         if let Some(tab) = tab {
             theme.sizes.melody.update_with_tab_vocal(&tab);
             theme.sizes.harmony.update_with_tab_guitar(&tab, None);
+            theme.sizes.lyrics.update_with_tab(&tab, &settings);
             TabViewer::spawn(
                 &mut commands,
                 &assets,
@@ -0,0 +1,2 @@
+pub mod ornament_note;
+pub mod ornament_systems;
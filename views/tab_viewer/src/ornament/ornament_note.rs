@@ -0,0 +1,88 @@
+use edger_bevy::bevy_prelude::*;
+use edger_bevy::bevy_prototype_lyon::prelude::*;
+use edger_bevy::prelude::{offscreen, OutlineRectangle, ShapeOp};
+use notation_model::prelude::{Note, Ornament, Syllable};
+use notation_midi::prelude::PlayingState;
+
+use crate::prelude::{EntryData, NotationTheme};
+use crate::tone::tone_mode::ToneMode;
+
+pub type OrnamentNoteData = EntryData<OrnamentNoteValue>;
+
+/// Where the decorated note is drawn, the ornament note follows it.
+#[derive(Copy, Clone, Debug)]
+pub enum OrnamentTarget {
+    Tone(ToneMode, Note),
+    String(u8),
+}
+
+#[derive(Clone, Debug)]
+pub struct OrnamentNoteValue {
+    pub ornament: Ornament,
+    pub index: usize,
+    pub target: OrnamentTarget,
+    pub syllable: Syllable,
+    pub bar_size: f32,
+}
+
+impl OrnamentNoteValue {
+    pub fn new(ornament: Ornament, index: usize, target: OrnamentTarget, syllable: Syllable) -> Self {
+        Self {
+            ornament,
+            index,
+            target,
+            syllable,
+            bar_size: 0.0,
+        }
+    }
+}
+
+impl OrnamentNoteData {
+    /// Grace notes are drawn right before the main note, the others over its start.
+    fn calc_x(&self, theme: &NotationTheme) -> f32 {
+        let width = theme.sizes.ornament.note_width;
+        let x = self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.in_bar_pos.to_f32();
+        if self.value.ornament.is_grace() {
+            let count = self.value.ornament.get_offsets().len();
+            x - width * (count - self.value.index) as f32
+        } else {
+            x + width * (self.value.index + 1) as f32
+        }
+    }
+    fn calc_center_y(&self, theme: &NotationTheme) -> f32 {
+        match self.value.target {
+            OrnamentTarget::Tone(mode, note) => {
+                let sizes = if mode.is_melody() {
+                    theme.sizes.melody
+                } else {
+                    theme.sizes.harmony
+                };
+                sizes.calc_note_y(note)
+            }
+            OrnamentTarget::String(string) => theme.sizes.strings.calc_string_y(string),
+        }
+    }
+}
+
+impl ShapeOp<NotationTheme, OutlineRectangle> for OrnamentNoteData {
+    fn get_shape(&self, theme: &NotationTheme) -> OutlineRectangle {
+        let sizes = theme.sizes.ornament;
+        let color = theme.colors.of_syllable(self.value.syllable);
+        let outline_color = theme.colors.syllables.outline.of_state(&PlayingState::Idle);
+        let offset = if self.value.bar_size <= 0.0 {
+            offscreen::offset()
+        } else {
+            let y = self.calc_center_y(theme) + sizes.note_height / 2.0;
+            Vec3::new(self.calc_x(theme), y, theme.z.ornament)
+        };
+        OutlineRectangle {
+            width: sizes.note_width - sizes.note_outline * 2.0,
+            height: sizes.note_height,
+            origin: shapes::RectangleOrigin::TopLeft,
+            color,
+            outline_width: sizes.note_outline,
+            outline_color,
+            offset,
+        }
+    }
+}
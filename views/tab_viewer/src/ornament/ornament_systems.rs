@@ -0,0 +1,93 @@
+use edger_bevy::bevy_prelude::*;
+
+use edger_bevy::prelude::ShapeOp;
+use notation_model::prelude::{LaneEntry, Note, Ornament, Semitones, TabBar};
+
+use crate::prelude::NotationTheme;
+use crate::tone::tone_mode::ToneMode;
+
+use super::ornament_note::{OrnamentNoteData, OrnamentNoteValue, OrnamentTarget};
+
+fn calc_ornament_notes<F: Fn(Note) -> OrnamentTarget>(
+    bar: &TabBar,
+    ornament: &Ornament,
+    note: Note,
+    target: F,
+) -> Vec<OrnamentNoteValue> {
+    let meta = bar.tab_meta();
+    ornament
+        .get_offsets()
+        .iter()
+        .enumerate()
+        .map(|(index, offset)| {
+            let neighbor = meta
+                .scale
+                .calc_note_from_semitones(&meta.key, Semitones::from(note) + *offset);
+            OrnamentNoteValue::new(*ornament, index, target(neighbor), neighbor.syllable)
+        })
+        .collect()
+}
+
+pub fn create_tone_ornament_notes(
+    commands: &mut Commands,
+    theme: &NotationTheme,
+    tone_mode: ToneMode,
+    entity: Entity,
+    entry: &LaneEntry,
+    ornament: &Ornament,
+) {
+    let target = match entry.get_ornament_target() {
+        Some(target) => target,
+        None => return,
+    };
+    if let (Some(bar), Some(tone)) = (
+        entry.bar(),
+        target.proto().as_core().and_then(|x| x.as_tone()),
+    ) {
+        for note in tone.get_notes() {
+            let target = |x| OrnamentTarget::Tone(tone_mode, x);
+            for value in calc_ornament_notes(&bar, ornament, note, target) {
+                OrnamentNoteData::new(entry, value).create(commands, theme, entity);
+            }
+        }
+    }
+}
+
+macro_rules! impl_pick_ornament_system {
+    ($create_pick_ornament_notes:ident, $as_fretted:ident, $get_fretted_shape:ident) => {
+        pub fn $create_pick_ornament_notes(
+            commands: &mut Commands,
+            theme: &NotationTheme,
+            entity: Entity,
+            entry: &LaneEntry,
+            ornament: &Ornament,
+        ) {
+            let target = match entry.get_ornament_target() {
+                Some(target) => target,
+                None => return,
+            };
+            let pick = match target.proto().$as_fretted().and_then(|x| x.as_pick()) {
+                Some(pick) => pick,
+                None => return,
+            };
+            if let Some(bar) = entry.bar() {
+                if let Some((fretboard, shape)) = bar.$get_fretted_shape(&target) {
+                    let meta = bar.tab_meta();
+                    for pick_note in pick.get_notes() {
+                        if let Some((_fret, note)) =
+                            fretboard.shape_pick_fret_note(&meta.scale, &meta.key, &shape, pick_note)
+                        {
+                            let target = |_| OrnamentTarget::String(pick_note.string);
+                            for value in calc_ornament_notes(&bar, ornament, note, target) {
+                                OrnamentNoteData::new(entry, value).create(commands, theme, entity);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_pick_ornament_system!(create_pick_ornament_notes6, as_fretted6, get_fretted_shape6);
+impl_pick_ornament_system!(create_pick_ornament_notes4, as_fretted4, get_fretted_shape4);
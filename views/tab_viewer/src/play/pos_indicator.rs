@@ -29,7 +29,7 @@ impl PosIndicatorData {
     }
     pub fn offset_x(&self) -> f32 {
         let mut x = self.bar_layout.offset.x;
-        x += self.bar_layout.size.width * self.bar_position.in_bar_pos.0 / self.bar_units.0;
+        x += self.bar_layout.size.width * self.bar_position.in_bar_pos.to_f32() / self.bar_units.to_f32();
         x
     }
 }
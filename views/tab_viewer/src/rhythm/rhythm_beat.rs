@@ -38,17 +38,17 @@ impl RhythmBeatData {
     pub fn scale(&self, theme: &NotationTheme) -> f32 {
         let bar_units = Units::from(self.value.signature);
         let beat_units = Units::from(self.value.signature.beat_unit);
-        let center = Units(self.value.index as f32 * beat_units.0);
+        let center = beat_units * self.value.index as i64;
         let mut in_bar_pos = self.value.in_bar_pos;
         if self.value.index == 0 {
-            if bar_units.0 - in_bar_pos.0 < beat_units.0 / 2.0 {
+            if bar_units - in_bar_pos < beat_units / 2 {
                 in_bar_pos = in_bar_pos - bar_units;
             }
         }
-        let delta = (in_bar_pos - center).0.abs();
-        if delta < beat_units.0 / 2.0 {
+        let delta = (in_bar_pos - center).to_f32().abs();
+        if delta < beat_units.to_f32() / 2.0 {
             // https://math.stackexchange.com/questions/121720/ease-in-out-function/121755#121755
-            let x = 1.0 - delta / beat_units.0 * 2.0;
+            let x = 1.0 - delta / beat_units.to_f32() * 2.0;
             let y = x * x * (3.0 - 2.0 * x);
             1.0 + y * (theme.sizes.tab_control.rhythm_beat_max_scale - 1.0)
         } else {
@@ -98,7 +98,7 @@ impl RhythmBeatData {
             signature,
             index,
             bar_radius: 0.0,
-            in_bar_pos: Units(0.0),
+            in_bar_pos: Units::ZERO,
         };
         let beat_data = RhythmBeatData {
             bar_props,
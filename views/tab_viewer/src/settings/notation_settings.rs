@@ -20,7 +20,13 @@ pub struct NotationSettings {
     pub show_note_pitch: bool,
     pub show_note_syllable: bool,
     pub show_syllable_as_num: bool,
+    pub show_chord_symbol: bool,
+    /// Show the best guitar voicing of each chord in the chords view.
+    pub show_chord_voicing: bool,
+    pub show_roman_numerals: bool,
     pub always_show_fret: bool,
+    /// Show the fret fingers of picks, calculated when not given in the tab.
+    pub show_pick_fingers: bool,
     pub allow_panning: bool,
     pub panning_line_size: f32,
     pub hide_guitar_view: bool,
@@ -30,7 +36,11 @@ pub struct NotationSettings {
     pub hide_strings_lane: bool,
     pub hide_harmony_lane: bool,
     pub hide_lyrics_lane: bool,
+    /// Languages of lyric lines not shown in the lyrics lane.
+    pub hidden_lyric_langs: Vec<String>,
     pub hide_melody_lane: bool,
+    pub hide_drums_lane: bool,
+    pub hide_extension_lane: bool,
     pub override_beat_size: Option<f32>,
     pub override_chord_size: Option<f32>,
     pub override_guitar_width: Option<f32>,
@@ -52,14 +62,21 @@ impl FromWorld for NotationSettings {
             show_note_pitch: false,
             show_note_syllable: true,
             show_syllable_as_num: true,
+            show_chord_symbol: true,
+            show_chord_voicing: false,
+            show_roman_numerals: false,
             always_show_fret: false,
+            show_pick_fingers: false,
             allow_panning: true,
             panning_line_size: 32.0,
             hide_shapes_lane: false,
             hide_strings_lane: false,
             hide_harmony_lane: false,
             hide_lyrics_lane: false,
+            hidden_lyric_langs: vec![],
             hide_melody_lane: false,
+            hide_drums_lane: false,
+            hide_extension_lane: false,
             hide_guitar_view: false,
             hide_mini_map: false,
             hide_chords_view: false,
@@ -88,12 +105,23 @@ impl NotationSettings {
     pub fn show_note_text(&self) -> bool {
         self.show_note_pitch || self.show_note_syllable
     }
+    pub fn is_lyric_lang_visible(&self, lang: &str) -> bool {
+        !self.hidden_lyric_langs.iter().any(|x| x == lang)
+    }
+    pub fn set_lyric_lang_visible(&mut self, lang: &str, visible: bool) {
+        self.hidden_lyric_langs.retain(|x| x != lang);
+        if !visible {
+            self.hidden_lyric_langs.push(lang.to_owned());
+        }
+    }
     pub fn hide_all_lanes(&mut self) {
         self.hide_shapes_lane = true;
         self.hide_strings_lane = true;
         self.hide_harmony_lane = true;
         self.hide_lyrics_lane = true;
         self.hide_melody_lane = true;
+        self.hide_drums_lane = true;
+        self.hide_extension_lane = true;
     }
     pub fn hack_for_screenshot(&mut self) {
         self.layout.mode = LayoutMode::Grid;
@@ -11,7 +11,7 @@ use crate::prelude::{NotationAssets, NotationSettings, NotationTheme};
 use notation_model::prelude::{HandShape4, HandShape6};
 
 macro_rules! impl_hand_system {
-    ($type:ident, $hand_shape:ident, $diagram_data:ident) => {
+    ($type:ident, $calc_shape_chord:ident, $hand_shape:ident, $diagram_data:ident) => {
         pub fn $type(
             commands: &mut Commands,
             assets: &NotationAssets,
@@ -21,12 +21,23 @@ macro_rules! impl_hand_system {
             entry: &LaneEntry,
             shape: &$hand_shape,
         ) {
+            let shape = &shape.with_auto_fingers();
             let data = $diagram_data::from((entry, *shape));
             let diagram_entity = data.create(commands, theme, entity);
             if let Some(mark) = entry.model().prev_as_mark() {
                 theme
                     .shapes
                     .insert_shape_text(commands, &assets, diagram_entity, &mark);
+            } else if let Some(text) = entry.bar().and_then(|bar| {
+                if bar.get_chord_of_entry(entry).is_some() {
+                    return None;
+                }
+                let chord = bar.$calc_shape_chord(entry)?;
+                bar.tab_meta().calc_chord_symbol(&chord).map(|x| x.to_text())
+            }) {
+                theme
+                    .shapes
+                    .insert_shape_text(commands, &assets, diagram_entity, &text);
             }
             let barre = shape.barre.unwrap_or(0);
             if barre > 0 {
@@ -38,13 +49,21 @@ macro_rules! impl_hand_system {
             }
             for (string, fret) in shape.frets.iter().enumerate() {
                 if fret.is_none() || fret.unwrap() > 0 {
-                    let finger_data = ShapeFingerData::new(string as u8, *fret, None);
-                    finger_data.create(commands, theme, diagram_entity);
+                    let finger = shape.fingers[string];
+                    let finger_data = ShapeFingerData::new(string as u8, *fret, finger);
+                    let finger_entity = finger_data.create(commands, theme, diagram_entity);
+                    if let Some(finger) = finger {
+                        if fret.is_some() {
+                            theme
+                                .shapes
+                                .insert_finger_text(commands, &assets, finger_entity, &finger);
+                        }
+                    }
                 }
             }
         }
     };
 }
 
-impl_hand_system!(create_hand_shape6, HandShape6, ShapeDiagramData6);
-impl_hand_system!(create_hand_shape4, HandShape4, ShapeDiagramData4);
+impl_hand_system!(create_hand_shape6, calc_shape_chord6, HandShape6, ShapeDiagramData6);
+impl_hand_system!(create_hand_shape4, calc_shape_chord4, HandShape4, ShapeDiagramData4);
@@ -1,7 +1,7 @@
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::bevy_prototype_lyon::prelude::*;
 use edger_bevy::prelude::{offscreen, OutlineRectangle, ShapeOp};
-use notation_model::prelude::{PickNote, Syllable};
+use notation_model::prelude::{PickNote, ProtoBarLayer, Syllable};
 use notation_midi::prelude::PlayingState;
 
 use crate::prelude::{EntryData, NotationTheme};
@@ -37,8 +37,12 @@ impl PickNoteData {
     }
     pub fn calc_width_height(&self, theme: &NotationTheme) -> (f32, f32) {
         let width =
-            self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.tied_units.0;
-        let mut height = theme.sizes.strings.note_height;
+            self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.tied_units.to_f32();
+        let mut height = if self.entry_props.voice == ProtoBarLayer::MAIN_VOICE {
+            theme.sizes.strings.note_height
+        } else {
+            theme.sizes.strings.voice_note_height
+        };
         let outline = self.calc_outline(theme);
         if self.value.playing_state.is_current() {
             height += outline * 2.0;
@@ -71,7 +75,7 @@ impl ShapeOp<NotationTheme, OutlineRectangle> for PickNoteData {
             offscreen::offset()
         } else {
             let x =
-                self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0;
+                self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.in_bar_pos.to_f32();
             let y = theme
                 .sizes
                 .strings
@@ -44,7 +44,7 @@ pub fn on_entry_playing_changed(
 }
 
 macro_rules! impl_pick_system {
-    ($create_pick_notes:ident, $create_pick_tones:ident, $fretboard:ident, $hand_shape:ident, $get_fretted_shape:ident
+    ($create_pick_notes:ident, $create_pick_tones:ident, $fretboard:ident, $hand_shape:ident, $get_fretted_shape:ident, $calc_pick_fingers:ident
     ) => {
         pub fn $create_pick_notes(
             commands: &mut Commands,
@@ -63,6 +63,11 @@ macro_rules! impl_pick_system {
             if let Some(bar) = entry.bar() {
                 if let Some((fretboard, shape)) = bar.$get_fretted_shape(entry) {
                     let meta = bar.tab_meta();
+                    let fingered = if settings.show_pick_fingers {
+                        bar.$calc_pick_fingers(entry)
+                    } else {
+                        None
+                    };
                     for pick_note in pick.get_notes() {
                         if let Some((fret, note)) =
                             fretboard.shape_pick_fret_note(&meta.scale, &meta.key, &shape, pick_note)
@@ -81,6 +86,19 @@ macro_rules! impl_pick_system {
                                     fret,
                                 );
                             }
+                            let finger = fingered
+                                .and_then(|x| x.get_pick_note(pick_note.string))
+                                .and_then(|x| x.fret_finger);
+                            if let Some(finger) = finger {
+                                if !settings.hide_strings_lane && fret > 0 {
+                                    theme.texts.strings.spawn_finger_text(
+                                        commands,
+                                        note_entity,
+                                        &assets,
+                                        &finger,
+                                    );
+                                }
+                            }
                         }
                     }
                 }
@@ -121,12 +139,14 @@ impl_pick_system!(
     create_pick_tones6,
     Fretboard6,
     HandShape6,
-    get_fretted_shape6
+    get_fretted_shape6,
+    calc_pick_fingers6
 );
 impl_pick_system!(
     create_pick_notes4,
     create_pick_tones4,
     Fretboard4,
     HandShape4,
-    get_fretted_shape4
+    get_fretted_shape4,
+    calc_pick_fingers4
 );
@@ -24,6 +24,9 @@ impl StringsPlugin {
     pub fn insert_lane_extra(commands: &mut EntityCommands, lane: &BarLane) {
         match lane.track.kind {
             TrackKind::Guitar => Self::insert_lane_extra6(commands, lane),
+            TrackKind::Bass | TrackKind::Ukulele | TrackKind::Mandolin => {
+                Self::insert_lane_extra4(commands, lane)
+            }
             _ => (),
         }
     }
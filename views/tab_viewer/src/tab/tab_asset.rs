@@ -5,7 +5,7 @@ use edger_bevy::bevy::asset::{Asset, AssetLoader, AsyncReadExt, LoadContext, io:
 use edger_bevy::bevy::reflect::TypePath;
 use edger_bevy::bevy::utils::BoxedFuture;
 
-use notation_model::prelude::ProtoTab;
+use notation_model::prelude::{ProtoTab, TabFormat, TabFormatError};
 
 #[derive(Clone, Debug, Asset, TypePath)]
 pub struct TabAsset {
@@ -17,8 +17,8 @@ pub struct TabAssetHandle(pub Handle<TabAsset>);
 
 #[derive(Clone, Error, Debug)]
 pub enum TabError {
-    #[error("decode ron failed")]
-    DecodeRonFailed(ron::error::SpannedError),
+    #[error("decode tab failed: {0}")]
+    DecodeFailed(TabFormatError),
     #[error("get tab failed")]
     GetTabFailed(String),
     #[error("parse tab failed")]
@@ -39,9 +39,9 @@ impl From<TabError> for TabAsset {
 
 impl TabAsset {
     #[cfg(feature = "dsl")]
-    pub const EXTENSIONS: [&'static str; 2] = ["rs", "ron"];
+    pub const EXTENSIONS: [&'static str; 4] = ["rs", "ron", "json", "ntab"];
     #[cfg(not(feature = "dsl"))]
-    pub const EXTENSIONS: [&'static str; 1] = ["ron"];
+    pub const EXTENSIONS: [&'static str; 3] = TabFormat::EXTENSIONS;
 }
 
 #[derive(Default)]
@@ -59,19 +59,22 @@ impl AssetLoader for TabAssetLoader {
         &'a self,
         reader: &'a mut Reader,
         _settings: &'a Self::Settings,
-        _load_context: &'a mut LoadContext,
+        load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, LoadResult> {
         Box::pin(async move {
             let mut bytes = Vec::new();
             reader.read_to_end(&mut bytes).await?;
-            let tab_asset = match ron::de::from_bytes::<ProtoTab>(&bytes) {
+            let path = load_context.path().to_string_lossy();
+            let tab_asset = match TabFormat::from_path(&path)
+                .and_then(|format| ProtoTab::decode(format, &bytes))
+            {
                 Ok(tab) => TabAsset::from(tab),
-                Err(err) => TabAsset::from(TabError::DecodeRonFailed(err)),
+                Err(err) => TabAsset::from(TabError::DecodeFailed(err)),
             };
             Ok(tab_asset)
         })
     }
     fn extensions(&self) -> &[&str] {
-        &["ron"]
+        &TabFormat::EXTENSIONS
     }
 }
@@ -11,6 +11,7 @@ use edger_bevy::prelude::{
 use notation_model::lane_kind::LaneKind;
 use notation_model::prelude::{Tab, TabBar};
 
+use crate::bar::bar_analysis::BarAnalysisText;
 use crate::bar::bar_layout::BarLayoutData;
 use crate::bar::bar_view::BarView;
 use crate::lane::lane_layout::LaneLayoutData;
@@ -279,8 +280,13 @@ impl TabBars {
                 })
             })
             .collect();
+        let analysis = if settings.show_roman_numerals {
+            tab.calc_chord_analysis()
+        } else {
+            vec![]
+        };
         for (bar, bar_layout) in bar_bundles.into_iter() {
-            BarView::spawn(
+            let bar_entity = BarView::spawn(
                 commands,
                 assets,
                 theme,
@@ -289,6 +295,9 @@ impl TabBars {
                 &bar,
                 bar_layout,
             );
+            if settings.show_roman_numerals {
+                BarAnalysisText::spawn(commands, assets, theme, bar_entity, bar, &analysis);
+            }
         }
         bars_entity
     }
@@ -73,7 +73,7 @@ impl TabHeader {
             theme.colors.chord.background,
         );
         RhythmView::spawn(commands, assets, theme, header_entity, tab);
-        TabChords::spawn(commands, assets, theme, header_entity, &tab, &view.chords);
+        TabChords::spawn(commands, assets, theme, settings, header_entity, tab, &view.chords);
         header_entity
     }
     pub fn do_layout(
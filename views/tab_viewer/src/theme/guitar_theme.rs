@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use edger_bevy::bevy_prelude::*;
 use notation_model::prelude::GUITAR_STRING_NUM;
 
-use super::theme_texts::NoteTexts;
+use super::theme_texts::{NoteTexts, StringLabelTexts};
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct GuitarTheme {
@@ -23,6 +23,7 @@ pub struct GuitarTheme {
     pub hit_string_seconds_range: (f32, f32),
     pub syllable_text: NoteTexts,
     pub syllable_base_width: f32,
+    pub string_label_text: StringLabelTexts,
 }
 
 impl Default for GuitarTheme {
@@ -57,6 +58,7 @@ impl Default for GuitarTheme {
                 syllable_font_color: Color::hex("FFFFFF").unwrap(),
             },
             syllable_base_width: 256.0,
+            string_label_text: Default::default(),
         }
     }
 }
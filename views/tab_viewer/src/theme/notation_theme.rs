@@ -8,7 +8,7 @@ use super::theme_sizes::ThemeSizes;
 use super::theme_texts::ThemeTexts;
 use super::theme_z::ThemeZ;
 
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug, Default, Resource)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug, Default, Resource)]
 pub struct NotationTheme {
     pub _bypass_systems: bool,
     pub z: ThemeZ,
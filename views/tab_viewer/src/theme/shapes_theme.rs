@@ -1,4 +1,4 @@
-use notation_model::prelude::ProtoEntry;
+use notation_model::prelude::{Finger, ProtoEntry};
 use serde::{Deserialize, Serialize};
 
 use edger_bevy::bevy::{prelude::*, sprite::Anchor};
@@ -34,6 +34,9 @@ pub struct ShapesTheme {
     pub barre_text_x: f32,
     pub barre_text_y: f32,
     pub barre_text_z: f32,
+    pub finger_font_size: f32,
+    pub finger_font_color: Color,
+    pub finger_text_z: f32,
 }
 
 impl Default for ShapesTheme {
@@ -66,6 +69,9 @@ impl Default for ShapesTheme {
             barre_text_x: 36.0,
             barre_text_y: 6.0,
             barre_text_z: 1.0,
+            finger_font_size: 7.0,
+            finger_font_color: Color::hex("FFFFFF").unwrap(),
+            finger_text_z: 1.0,
         }
     }
 }
@@ -122,4 +128,29 @@ impl ShapesTheme {
         let text_entity = entity_commands.id();
         commands.entity(entity).push_children(&[text_entity]);
     }
+    pub fn insert_finger_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        finger: &Finger,
+    ) {
+        let mut entity_commands = commands.spawn_empty();
+        let font = assets.latin_font.clone();
+        let style = TextStyle {
+            font,
+            font_size: self.finger_font_size,
+            color: self.finger_font_color,
+        };
+        let justify = JustifyText::Center;
+        let anchor = Anchor::Center;
+        entity_commands.insert(Text2dBundle {
+            text: Text::from_section(finger.to_number().to_string(), style).with_justify(justify),
+            transform: Transform::from_xyz(0.0, 0.0, self.finger_text_z),
+            text_anchor: anchor,
+            ..Default::default()
+        });
+        let text_entity = entity_commands.id();
+        commands.entity(entity).push_children(&[text_entity]);
+    }
 }
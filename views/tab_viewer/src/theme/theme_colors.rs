@@ -84,8 +84,10 @@ pub struct ThemeColors {
     pub bar: BarColors,
     pub chord: ChordColors,
     pub lyrics: LyricsColors,
+    pub extension: ExtensionColors,
     pub section: SectionColors,
     pub strings: StringsColors,
+    pub drums: DrumsColors,
     pub rhythm: RhythmColors,
     pub mini_map: MiniMapColors,
     pub ui: UiColors,
@@ -254,6 +256,18 @@ impl Default for LyricsColors {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ExtensionColors {
+    pub line: Color,
+}
+impl Default for ExtensionColors {
+    fn default() -> Self {
+        Self {
+            line: hex_linear("00000033"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct StringsColors {
     pub background: Color,
@@ -288,6 +302,30 @@ impl Default for StringsColors {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct DrumsColors {
+    pub background: Color,
+    pub row: Color,
+    pub hit: PlayingColors,
+    pub outline: PlayingColors,
+    pub flam: Color,
+}
+impl Default for DrumsColors {
+    fn default() -> Self {
+        Self {
+            background: hex_linear("3B3E5644"),
+            row: hex_linear("9CB5D344"),
+            hit: PlayingColors::new(
+                hex_linear("D3D3D3"),
+                hex_linear("FFFFFF"),
+                hex_linear("888888"),
+            ),
+            outline: PlayingColors::default(),
+            flam: hex_linear("FF8800"),
+        }
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct SectionColors {
     pub sections: [Color; 12],
@@ -1,5 +1,5 @@
 use edger_bevy::prelude::LayoutSize;
-use notation_model::prelude::{LaneKind, Note, Semitones, Tab, TrackKind};
+use notation_model::prelude::{DrumPiece, LaneKind, LyricRow, Note, Semitones, Tab, TrackKind};
 use notation_midi::prelude::PlayingState;
 
 use serde::{Deserialize, Serialize};
@@ -29,7 +29,7 @@ impl PlayingSize {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct ThemeSizes {
     pub bar: BarSizes,
     pub chord: ChordSizes,
@@ -37,6 +37,8 @@ pub struct ThemeSizes {
     pub harmony: NotesSizes,
     pub lyrics: LyricsSizes,
     pub strings: StringsSizes,
+    pub ornament: OrnamentSizes,
+    pub drums: DrumsSizes,
     pub mini_map: MiniMapSizes,
     pub tab_control: TabControlSizes,
     pub layout: LayoutSizes,
@@ -51,6 +53,8 @@ impl Default for ThemeSizes {
             harmony: NotesSizes::default_harmony(),
             lyrics: Default::default(),
             strings: Default::default(),
+            ornament: Default::default(),
+            drums: Default::default(),
             mini_map: Default::default(),
             tab_control: Default::default(),
             layout: Default::default(),
@@ -103,6 +107,8 @@ pub struct ChordSizes {
     pub interval_dot_offset_3_4_factor: f32,
     pub interval_dot_offset_5_7_factor: f32,
     pub interval_dot_offset_6_factor: (f32, f32),
+    pub compound_ring_radius_factor: f32,
+    pub compound_ring_outline: f32,
 }
 impl Default for ChordSizes {
     fn default() -> Self {
@@ -122,6 +128,8 @@ impl Default for ChordSizes {
             interval_dot_offset_3_4_factor: 0.4,
             interval_dot_offset_5_7_factor: 0.6,
             interval_dot_offset_6_factor: (0.4, 0.5),
+            compound_ring_radius_factor: 1.2,
+            compound_ring_outline: 1.0,
         }
     }
 }
@@ -234,22 +242,55 @@ impl NotesSizes {
         height + self.top_margin + self.bottom_margin
     }
 }
-#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct LyricsSizes {
     pub line_height: PlayingSize,
     pub word_gap: f32,
+    pub phonetic_height: f32,
+    /// Visible verses and languages of the tab, stacked in the lane.
+    #[serde(skip)]
+    pub visible_rows: Vec<LyricRow>,
+    pub has_phonetic: bool,
 }
 impl Default for LyricsSizes {
     fn default() -> Self {
         Self {
             line_height: PlayingSize::new(20.0, 24.0, 20.0),
             word_gap: 2.0,
+            phonetic_height: 12.0,
+            visible_rows: vec![],
+            has_phonetic: false,
         }
     }
 }
 impl LyricsSizes {
+    pub fn update_with_tab(&mut self, tab: &Tab, settings: &NotationSettings) {
+        let lines = tab.get_lyric_lines();
+        self.visible_rows = tab
+            .get_lyric_rows()
+            .into_iter()
+            .filter(|x| settings.is_lyric_lang_visible(&x.lang))
+            .collect();
+        self.has_phonetic = lines
+            .iter()
+            .any(|x| x.phonetic.is_some() && settings.is_lyric_lang_visible(&x.lang));
+    }
+    pub fn row_num(&self) -> usize {
+        self.visible_rows.len().max(1)
+    }
+    pub fn row_height(&self) -> f32 {
+        let phonetic_height = if self.has_phonetic {
+            self.phonetic_height
+        } else {
+            0.0
+        };
+        self.line_height.of_state(&PlayingState::Current) + phonetic_height
+    }
+    pub fn calc_row_y(&self, row: usize) -> f32 {
+        -self.row_height() * row as f32
+    }
     pub fn layout_height(&self) -> f32 {
-        self.line_height.of_state(&PlayingState::Current)
+        self.row_height() * self.row_num() as f32
     }
 }
 
@@ -257,6 +298,8 @@ impl LyricsSizes {
 pub struct StringsSizes {
     pub string_space: f32,
     pub note_height: f32,
+    /// Height of notes in the other voices of the lane, so overlapping notes stay visible.
+    pub voice_note_height: f32,
     pub note_outline: PlayingSize,
 }
 impl Default for StringsSizes {
@@ -264,6 +307,7 @@ impl Default for StringsSizes {
         Self {
             string_space: 12.0,
             note_height: 6.0,
+            voice_note_height: 3.0,
             note_outline: PlayingSize::new(1.0, 1.5, 1.0),
         }
     }
@@ -277,6 +321,48 @@ impl StringsSizes {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct OrnamentSizes {
+    pub note_width: f32,
+    pub note_height: f32,
+    pub note_outline: f32,
+}
+impl Default for OrnamentSizes {
+    fn default() -> Self {
+        Self {
+            note_width: 5.0,
+            note_height: 4.0,
+            note_outline: 0.5,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct DrumsSizes {
+    pub row_space: f32,
+    pub hit_radius: f32,
+    pub accent_hit_radius: f32,
+    pub hit_outline: PlayingSize,
+}
+impl Default for DrumsSizes {
+    fn default() -> Self {
+        Self {
+            row_space: 8.0,
+            hit_radius: 3.0,
+            accent_hit_radius: 4.5,
+            hit_outline: PlayingSize::new(1.0, 1.5, 1.0),
+        }
+    }
+}
+impl DrumsSizes {
+    pub fn layout_height(&self) -> f32 {
+        self.row_space * DrumPiece::ALL.len() as f32
+    }
+    pub fn calc_row_y(&self, row: usize) -> f32 {
+        -self.row_space * (row as f32 + 0.5)
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct TabControlSizes {
     pub control_width_factor: f32,
@@ -344,6 +430,7 @@ pub struct LayoutSizes {
     pub lane_margin: f32,
     pub bar_min_height: f32,
     pub shapes_height: f32,
+    pub extension_height: f32,
 }
 
 impl Default for LayoutSizes {
@@ -354,6 +441,7 @@ impl Default for LayoutSizes {
             lane_margin: 3.0,
             bar_min_height: 24.0,
             shapes_height: 52.0,
+            extension_height: 20.0,
         }
     }
 }
@@ -403,6 +491,20 @@ impl ThemeSizes {
                     self.layout.shapes_height
                 }
             }
+            LaneKind::Drums => {
+                if settings.hide_drums_lane {
+                    0.0
+                } else {
+                    self.drums.layout_height()
+                }
+            }
+            LaneKind::Extension => {
+                if settings.hide_extension_lane {
+                    0.0
+                } else {
+                    self.layout.extension_height
+                }
+            }
             _ => 0.0,
         }
     }
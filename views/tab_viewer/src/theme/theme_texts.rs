@@ -1,5 +1,5 @@
 use edger_bevy::prelude::{text, LayoutData};
-use notation_model::prelude::{Syllable, Scale, Key};
+use notation_model::prelude::{Chord, Finger, Syllable, Scale, Key};
 use serde::{Deserialize, Serialize};
 
 use edger_bevy::bevy::{prelude::*, sprite::Anchor};
@@ -12,6 +12,7 @@ pub struct ThemeTexts {
     pub chord: ChordTexts,
     pub rhythm: RhythmTexts,
     pub lyrics: LyricsTexts,
+    pub extension: ExtensionTexts,
     pub melody: NoteTexts,
     pub harmony: NoteTexts,
     pub strings: StringsTexts,
@@ -25,6 +26,7 @@ impl Default for ThemeTexts {
             chord: Default::default(),
             rhythm: Default::default(),
             lyrics: Default::default(),
+            extension: Default::default(),
             melody: Default::default(),
             harmony: NoteTexts::default_harmony(),
             strings: Default::default(),
@@ -39,6 +41,10 @@ pub struct TabTexts {
     pub bar_font_color: Color,
     pub bar_x: f32,
     pub bar_y: f32,
+    pub analysis_font_size: f32,
+    pub analysis_font_color: Color,
+    pub analysis_x: f32,
+    pub analysis_y: f32,
 }
 impl Default for TabTexts {
     fn default() -> Self {
@@ -47,6 +53,10 @@ impl Default for TabTexts {
             bar_font_color: ThemeColors::hex_linear("00000066"),
             bar_x: -6.0,
             bar_y: -6.0,
+            analysis_font_size: 16.0,
+            analysis_font_color: ThemeColors::hex_linear("00000099"),
+            analysis_x: 4.0,
+            analysis_y: -6.0,
         }
     }
 }
@@ -75,6 +85,30 @@ impl TabTexts {
     pub fn update_bar_number_x(&self, transform: &mut Transform, bar_width: f32) {
         transform.translation.x = bar_width + self.bar_x;
     }
+    pub fn spawn_analysis_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.analysis_font_size,
+            self.analysis_font_color,
+            JustifyText::Left,
+            Anchor::CenterLeft,
+            self.analysis_x,
+            self.analysis_y,
+            3.0,
+        )
+    }
+    pub fn update_analysis_x(&self, transform: &mut Transform, x: f32) {
+        transform.translation.x = x + self.analysis_x;
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -83,6 +117,13 @@ pub struct ChordTexts {
     pub bars_font_color: Color,
     pub bars_x: f32,
     pub bars_y: f32,
+    pub symbol_font_size: f32,
+    pub symbol_font_color: Color,
+    pub symbol_y: f32,
+    pub voicing_font_size: f32,
+    pub voicing_font_color: Color,
+    pub voicing_x: f32,
+    pub voicing_y: f32,
 }
 impl Default for ChordTexts {
     fn default() -> Self {
@@ -91,6 +132,13 @@ impl Default for ChordTexts {
             bars_font_color: ThemeColors::hex_linear("FFFFFF"),
             bars_x: 2.0,
             bars_y: -2.0,
+            symbol_font_size: 14.0,
+            symbol_font_color: ThemeColors::hex_linear("FFFFFF"),
+            symbol_y: 2.0,
+            voicing_font_size: 12.0,
+            voicing_font_color: ThemeColors::hex_linear("FFFFFF"),
+            voicing_x: -2.0,
+            voicing_y: -2.0,
         }
     }
 }
@@ -121,6 +169,58 @@ impl ChordTexts {
         transform.translation.x = -layout.size.width / 2.0 + self.bars_x;
         transform.translation.y = layout.size.height / 2.0 + self.bars_y;
     }
+    pub fn spawn_symbol_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+        z: f32,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.symbol_font_size,
+            self.symbol_font_color,
+            JustifyText::Center,
+            Anchor::BottomCenter,
+            0.0,
+            self.symbol_y,
+            z,
+        )
+    }
+    pub fn update_symbol_xy(&self, transform: &mut Transform, layout: &LayoutData) {
+        transform.translation.x = 0.0;
+        transform.translation.y = -layout.size.height / 2.0 + self.symbol_y;
+    }
+    pub fn spawn_voicing_text(
+        &self,
+        commands: &mut Commands,
+        assets: &NotationAssets,
+        entity: Entity,
+        text: &str,
+        z: f32,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.voicing_font_size,
+            self.voicing_font_color,
+            JustifyText::Right,
+            Anchor::TopRight,
+            self.voicing_x,
+            self.voicing_y,
+            z,
+        )
+    }
+    pub fn update_voicing_xy(&self, transform: &mut Transform, layout: &LayoutData) {
+        transform.translation.x = layout.size.width / 2.0 + self.voicing_x;
+        transform.translation.y = layout.size.height / 2.0 + self.voicing_y;
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -169,6 +269,8 @@ pub struct LyricsTexts {
     pub text_z: f32,
     pub word_font_size: f32,
     pub word_font_color: Color,
+    pub phonetic_font_size: f32,
+    pub phonetic_font_color: Color,
 }
 impl Default for LyricsTexts {
     fn default() -> Self {
@@ -178,6 +280,8 @@ impl Default for LyricsTexts {
             text_z: 1.0,
             word_font_size: 20.0,
             word_font_color: Color::hex("000000").unwrap(),
+            phonetic_font_size: 11.0,
+            phonetic_font_color: Color::hex("555555").unwrap(),
         }
     }
 }
@@ -203,6 +307,72 @@ impl LyricsTexts {
             self.text_z,
         );
     }
+    /// Reading of the word, above it in the space of `phonetic_height`.
+    pub fn spawn_phonetic_text(
+        &self,
+        commands: &mut Commands,
+        entity: Entity,
+        assets: &NotationAssets,
+        text: &str,
+        phonetic_height: f32,
+    ) {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.lyrics_font.clone(),
+            self.phonetic_font_size,
+            self.phonetic_font_color,
+            JustifyText::Left,
+            Anchor::Center,
+            self.text_x,
+            self.text_y + phonetic_height,
+            self.text_z,
+        );
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ExtensionTexts {
+    pub text_x: f32,
+    pub text_y: f32,
+    pub text_z: f32,
+    pub font_size: f32,
+    pub font_color: Color,
+}
+impl Default for ExtensionTexts {
+    fn default() -> Self {
+        Self {
+            text_x: 2.0,
+            text_y: -6.0,
+            text_z: 1.0,
+            font_size: 14.0,
+            font_color: Color::hex("333333").unwrap(),
+        }
+    }
+}
+impl ExtensionTexts {
+    pub fn spawn_text(
+        &self,
+        commands: &mut Commands,
+        entity: Entity,
+        assets: &NotationAssets,
+        text: &str,
+    ) {
+        text::spawn(
+            commands,
+            entity,
+            text,
+            assets.latin_font.clone(),
+            self.font_size,
+            self.font_color,
+            JustifyText::Left,
+            Anchor::CenterLeft,
+            self.text_x,
+            self.text_y,
+            self.text_z,
+        );
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -242,9 +412,10 @@ impl NoteTexts {
         settings: &NotationSettings,
         scale: &Scale,
         key: &Key,
+        chord: Option<&Chord>,
         syllable: &Syllable,
     ) {
-        self.spawn_scaled_note_text(commands, entity, assets, settings, scale, key, syllable, 1.0);
+        self.spawn_scaled_note_text(commands, entity, assets, settings, scale, key, chord, syllable, 1.0);
     }
     pub fn spawn_scaled_note_text(
         &self,
@@ -254,10 +425,11 @@ impl NoteTexts {
         settings: &NotationSettings,
         scale: &Scale,
         key: &Key,
+        chord: Option<&Chord>,
         syllable: &Syllable,
         size_scale: f32,
     ) {
-        let text = Self::calc_text(settings, scale, key, syllable);
+        let text = Self::calc_text(settings, scale, key, chord, syllable);
         text::spawn(
             commands,
             entity,
@@ -280,10 +452,11 @@ impl NoteTexts {
         settings: &NotationSettings,
         scale: &Scale,
         key: &Key,
+        chord: Option<&Chord>,
         syllable: &Syllable,
     ) -> String {
         let pitch_text = if settings.show_note_pitch {
-            Some(scale.calc_pitch(key, syllable).to_text())
+            Some(scale.calc_pitch_with_chord(key, syllable, chord).to_text())
         } else {
             None
         };
@@ -311,6 +484,10 @@ pub struct StringsTexts {
     pub text_z: f32,
     pub fret_font_size: f32,
     pub fret_font_color: Color,
+    pub finger_x: f32,
+    pub finger_y: f32,
+    pub finger_font_size: f32,
+    pub finger_font_color: Color,
 }
 impl Default for StringsTexts {
     fn default() -> Self {
@@ -320,6 +497,10 @@ impl Default for StringsTexts {
             text_z: 1.0,
             fret_font_size: 18.0,
             fret_font_color: super::theme_colors::hex_linear("000000"),
+            finger_x: 12.0,
+            finger_y: 4.0,
+            finger_font_size: 12.0,
+            finger_font_color: super::theme_colors::hex_linear("555555"),
         }
     }
 }
@@ -346,6 +527,68 @@ impl StringsTexts {
             self.text_z,
         );
     }
+    pub fn spawn_finger_text(
+        &self,
+        commands: &mut Commands,
+        entity: Entity,
+        assets: &NotationAssets,
+        finger: &Finger,
+    ) {
+        text::spawn(
+            commands,
+            entity,
+            finger.to_number().to_string().as_str(),
+            assets.latin_font.clone(),
+            self.finger_font_size,
+            self.finger_font_color,
+            JustifyText::Left,
+            Anchor::Center,
+            self.finger_x,
+            self.finger_y,
+            self.text_z,
+        );
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct StringLabelTexts {
+    pub text_y: f32,
+    pub text_z: f32,
+    pub label_font_size: f32,
+    pub label_font_color: Color,
+}
+impl Default for StringLabelTexts {
+    fn default() -> Self {
+        Self {
+            text_y: 12.0,
+            text_z: 1.0,
+            label_font_size: 20.0,
+            label_font_color: Color::hex("FFFFFF").unwrap(),
+        }
+    }
+}
+impl StringLabelTexts {
+    pub fn spawn_label_text(
+        &self,
+        commands: &mut Commands,
+        entity: Entity,
+        assets: &NotationAssets,
+        label: &str,
+    ) -> Entity {
+        text::spawn(
+            commands,
+            entity,
+            label,
+            assets.latin_font.clone(),
+            self.label_font_size,
+            self.label_font_color,
+            JustifyText::Center,
+            Anchor::BottomCenter,
+            0.0,
+            0.0,
+            self.text_z,
+        )
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -8,6 +8,7 @@ pub struct ThemeZ {
     pub tone: f32,
     pub word: f32,
     pub pick: f32,
+    pub ornament: f32,
     pub bar_separator: f32,
     pub bar_indicator: f32,
     pub pos_indicator: f32,
@@ -36,6 +37,7 @@ impl Default for ThemeZ {
             tone: 8.0,
             word: 9.0,
             pick: 10.0,
+            ornament: 12.0,
             bar_separator: 2.0,
             bar_indicator: 19.0,
             pos_indicator: 20.0,
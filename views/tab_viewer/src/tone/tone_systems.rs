@@ -27,6 +27,7 @@ pub fn create_tone_notes(
         let mode: ToneMode = lane.kind.into();
         let bar = lane.bar().unwrap();
         let meta = bar.tab_meta();
+        let chord = bar.get_chord_of_entry(entry);
         for note in tone.get_notes() {
             let data = ToneNoteData::new(entry, ToneNoteValue::new(&bar, note, mode));
             let note_entity = data.create(commands, theme, entity);
@@ -42,6 +43,7 @@ pub fn create_tone_notes(
                         assets,
                         settings,
                         &meta.scale, &meta.key,
+                        chord.as_ref(),
                         &data.value.syllable(),
                     )
                 }
@@ -1,7 +1,7 @@
 use edger_bevy::bevy_prelude::*;
 
 use edger_bevy::prelude::ShapeOp;
-use notation_model::prelude::LaneEntry;
+use notation_model::prelude::{LaneEntry, LyricExtender};
 
 use crate::prelude::{EntryPlaying, NotationAssets, NotationSettings, NotationTheme};
 
@@ -23,11 +23,20 @@ pub fn create_word_text(
         */
     let data = WordTextData::new(entry, text.clone());
     let text_entity = data.create(commands, theme, entity);
-    if text.word.text != "" {
+    if !text.word.text.is_empty() || text.extender != LyricExtender::None {
         theme
             .texts
             .lyrics
-            .spawn_word_text(commands, text_entity, &assets, text.word.text.as_str())
+            .spawn_word_text(commands, text_entity, assets, text.calc_text().as_str())
+    }
+    if let Some(phonetic) = &text.phonetic {
+        theme.texts.lyrics.spawn_phonetic_text(
+            commands,
+            text_entity,
+            assets,
+            phonetic.as_str(),
+            theme.sizes.lyrics.phonetic_height,
+        )
     }
 }
 
@@ -1,6 +1,6 @@
 use edger_bevy::bevy_prelude::*;
 use edger_bevy::prelude::{offscreen, ShapeOp, StrokeLine};
-use notation_model::prelude::LyricWord;
+use notation_model::prelude::{LyricExtender, LyricLine, LyricWord};
 use notation_midi::prelude::PlayingState;
 
 use std::fmt::Display;
@@ -10,6 +10,10 @@ use crate::prelude::{EntryData, NotationTheme, SingleBundle};
 #[derive(Clone, Debug, Component)]
 pub struct WordTextValue {
     pub word: LyricWord,
+    pub phonetic: Option<String>,
+    pub extender: LyricExtender,
+    /// Index of the verse and language in the stacked rows of the lane.
+    pub row: usize,
     pub playing_state: PlayingState,
     pub bar_size: f32,
 }
@@ -17,10 +21,24 @@ impl WordTextValue {
     pub fn new(word: LyricWord) -> Self {
         Self {
             word,
+            phonetic: None,
+            extender: LyricExtender::None,
+            row: 0,
             playing_state: PlayingState::Idle,
             bar_size: 0.0,
         }
     }
+    pub fn new_line(line: &LyricLine, row: usize) -> Self {
+        Self {
+            phonetic: line.phonetic.clone(),
+            extender: line.extender,
+            row,
+            ..Self::new(line.word.clone())
+        }
+    }
+    pub fn calc_text(&self) -> String {
+        format!("{}{}", self.word.text, self.extender.to_text())
+    }
 }
 impl Display for WordTextValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -34,8 +52,8 @@ pub type WordTextData = EntryData<WordTextValue>;
 
 impl ShapeOp<NotationTheme, StrokeLine> for WordTextData {
     fn get_shape(&self, theme: &NotationTheme) -> StrokeLine {
-        let width = self.value.bar_size / self.bar_props.bar_units.0
-            * self.entry_props.tied_units.0
+        let width = self.value.bar_size / self.bar_props.bar_units.to_f32()
+            * self.entry_props.tied_units.to_f32()
             - theme.sizes.lyrics.word_gap;
         let line_width = theme
             .sizes
@@ -46,11 +64,12 @@ impl ShapeOp<NotationTheme, StrokeLine> for WordTextData {
             offscreen::offset()
         } else {
             let x =
-                self.value.bar_size / self.bar_props.bar_units.0 * self.entry_props.in_bar_pos.0;
-            let y = 0.0;
+                self.value.bar_size / self.bar_props.bar_units.to_f32() * self.entry_props.in_bar_pos.to_f32();
+            let y = theme.sizes.lyrics.calc_row_y(self.value.row)
+                - (theme.sizes.lyrics.row_height() - theme.sizes.lyrics.line_height.of_state(&PlayingState::Current));
             Vec3::new(x, y, theme.z.word)
         };
-        let y = -1.0 * theme.sizes.lyrics.layout_height() / 2.0;
+        let y = -theme.sizes.lyrics.line_height.of_state(&PlayingState::Current) / 2.0;
         StrokeLine {
             from: Vec2::new(0.0, y),
             to: Vec2::new(width, y),